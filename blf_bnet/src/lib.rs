@@ -3,7 +3,7 @@ mod film;
 
 use wasm_bindgen::prelude::*;
 use js_sys::Uint8Array;
-use crate::film::FilmVariants;
+use crate::film::{FilmSummary, FilmVariants};
 use crate::screenshot::ScreenshotData;
 
 #[wasm_bindgen]
@@ -16,3 +16,8 @@ pub fn get_film_variants(bytes: Uint8Array) -> Option<FilmVariants> {
     film::get_film_variants(bytes.to_vec())
 }
 
+#[wasm_bindgen]
+pub fn get_film_summary(bytes: Uint8Array) -> Option<FilmSummary> {
+    film::get_film_summary(bytes.to_vec())
+}
+
@@ -2,7 +2,8 @@ use js_sys::Uint8Array;
 use wasm_bindgen::prelude::wasm_bindgen;
 use blf_lib::blam::common::math::real_math::real_point3d;
 use blf_lib::blf::chunks::search_for_chunk;
-use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship;
+use blf_lib::blf::content::read_screenshot;
+use blf_lib::blf::versions::halo3::{k_title_halo3, v12070_08_09_05_2031_halo3_ship};
 use blf_lib::types::u64::Unsigned64;
 #[wasm_bindgen(getter_with_clone)]
 pub struct ScreenshotData {
@@ -21,66 +22,31 @@ pub struct ScreenshotData {
     pub header: v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header,
 }
 
-fn get_h3_release_screenshot_data(data: Vec<u8>) -> Option<ScreenshotData> {
-    let content_header  = search_for_chunk::<
+// The header is exposed in Halo 3's layout, and Reach's camera and ticks haven't been mapped,
+// so only Halo 3 screenshots are read here.
+pub fn get_screenshot_data(data: Vec<u8>) -> Option<ScreenshotData> {
+    let screenshot = read_screenshot(&data).ok()?;
+    if screenshot.summary.title != k_title_halo3 {
+        return None;
+    }
+
+    let header = search_for_chunk::<
         v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header
-    >(data.to_vec()).unwrap_or(None)?;
-    let screenshot_camera  = search_for_chunk::<
-        v12070_08_09_05_2031_halo3_ship::s_blf_chunk_screenshot_camera
-    >(data.to_vec()).unwrap_or(None)?;
-    let screenshot_data  = search_for_chunk::<
-        v12070_08_09_05_2031_halo3_ship::s_blf_chunk_screenshot_data
-    >(data.to_vec()).unwrap_or(None)?;
+    >(data).unwrap_or(None)?;
 
     Some(ScreenshotData {
-        build_number: content_header.build_number,
-        name: content_header.metadata.name.get_string(),
-        author: content_header.metadata.author.get_string().ok()?,
-        author_xuid: content_header.metadata.author_id,
-        description: content_header.metadata.description.get_string().ok()?,
-        unique_id: content_header.metadata.unique_id,
-        game_id: content_header.metadata.game_id,
-        hopper_id:
-            if content_header.metadata.hopper_id != -1 {
-                Some(content_header.metadata.hopper_id)
-            } else {
-                None
-            },
-        camera_position: screenshot_camera.camera.camera.position,
-        game_tick: screenshot_camera.game_tick,
-        film_tick: screenshot_camera.film_tick,
-        jpeg_data: Uint8Array::from(screenshot_data.jpeg_data.as_slice()),
-        header: content_header
+        build_number: screenshot.summary.build_number,
+        name: screenshot.summary.name,
+        author: screenshot.summary.author,
+        author_xuid: screenshot.summary.author_xuid,
+        description: screenshot.summary.description,
+        unique_id: screenshot.summary.unique_id,
+        game_id: screenshot.summary.game_id,
+        hopper_id: screenshot.summary.hopper_id.map(|hopper_id| hopper_id as i16),
+        camera_position: screenshot.camera_position?,
+        game_tick: screenshot.game_tick?,
+        film_tick: screenshot.film_tick?,
+        jpeg_data: Uint8Array::from(screenshot.jpeg_data.as_slice()),
+        header,
     })
 }
-
-// fn get_h3odst_release_screenshot_data(data: Vec<u8>) -> Option<ScreenshotData> {
-//     let content_header  = search_for_chunk::<
-//         v13895_09_04_27_2201_atlas_release::s_blf_chunk_content_header
-//     >(data.to_vec())?;
-//     let screenshot_camera  = search_for_chunk::<
-//         v13895_09_04_27_2201_atlas_release::s_blf_chunk_screenshot_camera
-//     >(data.to_vec())?;
-//     let screenshot_data  = search_for_chunk::<
-//         v13895_09_04_27_2201_atlas_release::s_blf_chunk_screenshot_data
-//     >(data.to_vec())?;
-
-//     Some(ScreenshotData {
-//         build_number: content_header.build_number,
-//         name: content_header.metadata.name.get_string(),
-//         author: content_header.metadata.author.get_string(),
-//         author_xuid: content_header.metadata.author_id,
-//         description: content_header.metadata.description.get_string(),
-//         unique_id: content_header.metadata.unique_id,
-//         game_id: content_header.metadata.game_id,
-//         hopper_id: None,
-//         camera_position: screenshot_camera.camera.camera.position,
-//         game_tick: screenshot_camera.game_tick,
-//         film_tick: screenshot_camera.film_tick,
-//         jpeg_data: Uint8Array::from(screenshot_data.jpeg_data.as_slice()),
-//     })
-// }
-
-pub fn get_screenshot_data(data: Vec<u8>) -> Option<ScreenshotData> {
-    get_h3_release_screenshot_data(data.clone())
-}
\ No newline at end of file
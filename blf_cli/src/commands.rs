@@ -2,9 +2,17 @@ pub mod import_rsa_signatures;
 pub mod import_variant;
 pub mod export_variant;
 pub mod title_storage;
+pub mod diff_title_storage;
 pub mod convert_halo3mcc_map_variants;
 pub mod unpack_screenshot;
 pub mod dump_film_data;
+pub mod inspect;
+pub mod inspect_map;
+pub mod content_header;
+pub mod serve;
+pub mod decompile_script;
+pub mod compile_script;
+pub mod convert_reach_game_variants;
 
 use clap::Subcommand;
 use crate::commands::title_storage::TitleStorageCommand;
@@ -13,10 +21,26 @@ use crate::commands::title_storage::TitleStorageCommand;
 pub enum Commands {
     #[command(name = "title-storage")]
     TitleStorage(TitleStorageCommand),
+    /// Converts a folder of Halo 3 map variants between MCC and Xbox 360.
     #[command(arg_required_else_help = true)]
     ConvertH3MCCMapVariants {
-        mcc_maps_folder: String,
+        map_variants_folder: String,
         converted_maps_folder: String,
+        /// Convert 360 map variants to MCC, rather than MCC map variants to 360.
+        #[arg(long)]
+        to_mcc: bool,
+        /// A folder of MCC map variants to copy each map's checksum from when converting to MCC.
+        #[arg(long, requires = "to_mcc")]
+        mcc_maps_folder: Option<String>,
+    },
+    /// Converts a folder of Halo: Reach game variants between TU1 and MCC.
+    #[command(arg_required_else_help = true)]
+    ConvertReachGameVariants {
+        variants_folder: String,
+        converted_variants_folder: String,
+        /// Convert TU1 game variants to MCC, rather than MCC game variants to TU1.
+        #[arg(long)]
+        to_mcc: bool,
     },
     #[command(arg_required_else_help = true)]
     UnpackScreenshot {
@@ -26,5 +50,76 @@ pub enum Commands {
     #[command(arg_required_else_help = true)]
     DumpFilmData {
         film_folder: String,
+    },
+    /// Lists every chunk in a BLF file, optionally printing each as JSON.
+    #[command(arg_required_else_help = true)]
+    Inspect {
+        blf_path: String,
+        /// The title to read the file as, identified from the file's chunks if not provided.
+        #[arg(long, requires = "version")]
+        title: Option<String>,
+        /// The build to read the file as, identified from the file's chunks if not provided.
+        #[arg(long, requires = "title")]
+        version: Option<String>,
+        /// Print each chunk as JSON.
+        #[arg(long)]
+        json: bool,
+        /// Print every named bitstream read made while decoding each chunk.
+        #[arg(long)]
+        trace: bool,
+    },
+    /// Prints the header of a cache file (.map), including the checksum hoppers use to refer to it.
+    #[command(arg_required_else_help = true)]
+    InspectMap {
+        map_path: String,
+        /// Print the header as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Checks that a file's content header (chdr) matches the map variant, game variant or film it accompanies.
+    #[command(arg_required_else_help = true)]
+    ValidateContentHeader {
+        blf_path: String,
+    },
+    /// Regenerates a file's content header (chdr) from the map variant, game variant or film it accompanies.
+    #[command(arg_required_else_help = true)]
+    ResyncContentHeader {
+        blf_path: String,
+        /// Where to save the resynced file, the file is overwritten if not provided.
+        output_path: Option<String>,
+        /// A 2048 bit RSA private key (PEM or DER) to re-sign the file with, required if its _eof is signed.
+        #[arg(long)]
+        private_key: Option<String>,
+    },
+    /// Hosts a built title storage folder over HTTP on localhost, at the paths the game requests.
+    #[command(arg_required_else_help = true)]
+    Serve {
+        blf_path: String,
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// The path the title storage folder is requested from, Halo 3's by default.
+        #[arg(long, default_value = "/storage/title/tracked/12070")]
+        url_prefix: String,
+        /// A title storage config folder to build from, which is rebuilt whenever it changes.
+        #[arg(long, requires_all = ["title", "version"])]
+        config: Option<String>,
+        /// The title to build the config as.
+        #[arg(long, requires = "config")]
+        title: Option<String>,
+        /// The build to build the config as.
+        #[arg(long, requires = "config")]
+        version: Option<String>,
+    },
+    /// Prints a Halo: Reach game variant's megalo script as readable pseudo-code.
+    #[command(arg_required_else_help = true)]
+    DecompileScript {
+        variant_path: String,
+    },
+    /// Compiles a megalo script into a Halo: Reach game variant.
+    #[command(arg_required_else_help = true)]
+    CompileScript {
+        variant_path: String,
+        script_path: String,
+        output_path: String,
     }
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_variant::c_game_variant;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_compiler::compile_megalo_script;
+use blf_lib::blf::BlfFileBuilder;
+use blf_lib::blf::versions::haloreach::v12065_11_08_24_1738_tu1actual::{s_blf_chunk_game_variant, s_blf_chunk_matchmaking_game_variant, v12065_11_08_24_1738_tu1actual};
+
+pub fn compile_script(variant_path: String, script_path: String, output_path: String) {
+    let variant_data = std::fs::read(&variant_path).unwrap_or_else(|err| {
+        eprintln!("Could not read {variant_path}: {err}");
+        std::process::exit(1);
+    });
+    let script = std::fs::read_to_string(&script_path).unwrap_or_else(|err| {
+        eprintln!("Could not read {script_path}: {err}");
+        std::process::exit(1);
+    });
+
+    let mut blf_file = BlfFileBuilder::parse_any(&variant_data, &v12065_11_08_24_1738_tu1actual {}).unwrap_or_else(|err| {
+        eprintln!("Could not read {variant_path}: {err}");
+        std::process::exit(1);
+    });
+
+    let game_variant: &mut c_game_variant = if blf_file.get_chunk::<s_blf_chunk_game_variant>().is_ok() {
+        &mut blf_file.get_chunk_mut::<s_blf_chunk_game_variant>().unwrap().game_variant
+    } else if let Ok(chunk) = blf_file.get_chunk_mut::<s_blf_chunk_matchmaking_game_variant>() {
+        &mut chunk.game_variant
+    } else {
+        eprintln!("No Halo: Reach game variant was found in {variant_path}.");
+        std::process::exit(1);
+    };
+
+    let game_engine = game_variant.m_game_engine.clone();
+    let custom_variant = match (&mut game_variant.m_custom_variant, &mut game_variant.m_sandbox_variant) {
+        (Some(custom_variant), _) => custom_variant,
+        (None, Some(sandbox_variant)) => &mut sandbox_variant.m_custom_variant,
+        (None, None) => {
+            eprintln!("{variant_path} is a {game_engine:?} variant, which has no script.");
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(err) = compile_megalo_script(&script, custom_variant) {
+        eprintln!("Could not compile {script_path}: {err}");
+        std::process::exit(1);
+    }
+
+    if let Err(err) = blf_file.write_file(&output_path) {
+        eprintln!("Could not write {output_path}: {err}");
+        std::process::exit(1);
+    }
+}
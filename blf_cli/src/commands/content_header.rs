@@ -0,0 +1,62 @@
+use std::fs;
+use blf_lib::blam::common::memory::rsa_signature::read_rsa_private_key;
+use blf_lib::blf::content;
+
+fn read_blf(blf_path: &String) -> Vec<u8> {
+    fs::read(blf_path).unwrap_or_else(|err| {
+        eprintln!("Could not read {blf_path}: {err}");
+        std::process::exit(1);
+    })
+}
+
+pub fn validate_content_header(
+    blf_path: String,
+) {
+    let mismatches = content::validate_content_header(&read_blf(&blf_path)).unwrap_or_else(|err| {
+        eprintln!("Could not validate {blf_path}: {err}");
+        std::process::exit(1);
+    });
+
+    if mismatches.is_empty() {
+        println!("The content header matches its content.");
+        return;
+    }
+
+    println!("Found {} content header mismatch(es):", mismatches.len());
+    for mismatch in &mismatches {
+        println!("{}: header \"{}\", content \"{}\"", mismatch.field, mismatch.header_value, mismatch.content_value);
+    }
+    std::process::exit(1);
+}
+
+pub fn resync_content_header(
+    blf_path: String,
+    output_path: Option<String>,
+    private_key_path: Option<String>,
+) {
+    let private_key = private_key_path.map(|private_key_path| read_rsa_private_key(&read_blf(&private_key_path)).unwrap_or_else(|err| {
+        eprintln!("Could not read {private_key_path}: {err}");
+        std::process::exit(1);
+    }));
+
+    let data = read_blf(&blf_path);
+    let result = content::validate_content_header(&data)
+        .and_then(|mismatches| Ok((mismatches, content::resync_content_header(&data, private_key.as_ref())?)));
+
+    let (mismatches, resynced) = result.unwrap_or_else(|err| {
+        eprintln!("Could not resync {blf_path}: {err}");
+        std::process::exit(1);
+    });
+
+    for mismatch in &mismatches {
+        println!("{}: \"{}\" -> \"{}\"", mismatch.field, mismatch.header_value, mismatch.content_value);
+    }
+
+    let output_path = output_path.unwrap_or(blf_path);
+    if let Err(err) = fs::write(&output_path, resynced) {
+        eprintln!("Could not write {output_path}: {err}");
+        std::process::exit(1);
+    }
+
+    println!("Resynced {} field(s), saved to {output_path}", mismatches.len());
+}
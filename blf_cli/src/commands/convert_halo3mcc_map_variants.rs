@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs::create_dir_all;
 use blf_lib::blam::halo3::v12070_08_09_05_2031_halo3_ship::saved_games::scenario_map_variant::{c_map_variant, s_variant_object_datum, s_variant_quota};
 use blf_lib::blf::BlfFileBuilder;
+use blf_lib::types::numbers::Float32;
 use blf_lib::blf::chunks::search_for_chunk_in_file;
 use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_content_header, s_blf_chunk_end_of_file, s_blf_chunk_map_variant, s_blf_chunk_packed_map_variant, s_blf_chunk_start_of_file};
 use crate::build_path;
@@ -9,40 +11,52 @@ use crate::console::console_task;
 use crate::io::get_files_in_folder;
 use crate::title_storage::check_file_exists;
 use crate::title_storage::halo3::v12070_08_09_05_2031_halo3_ship::get_map_budget;
+use crate::title_storage::halo3::v12070_08_09_05_2031_halo3_ship::variant_importer::mcc::object_indexes::{get_h3_index_for_mcc_object, get_mcc_index_for_h3_object};
 
-pub fn convert_halo3mcc_map_variants(mcc_maps_folder: String, converted_maps_path: String) {
-    let mut task = console_task::start(String::from("Converting MCC map variants to 360"));
+// MCC's checksums differ from the 360's, so they're copied from existing MCC variants of each map.
+// Variants of maps without one are skipped, as MCC won't load a variant with the wrong checksum.
+struct s_mcc_map_details {
+    original_map_rsa_signature_hash: u32,
+    map_variant_version: u16,
+}
+
+pub fn convert_halo3mcc_map_variants(map_variants_folder: String, converted_maps_path: String, to_mcc: bool, mcc_maps_folder: Option<String>) {
+    let mut task = console_task::start(String::from(if to_mcc {
+        "Converting 360 map variants to MCC"
+    } else {
+        "Converting MCC map variants to 360"
+    }));
 
-    if !check_file_exists(&mcc_maps_folder) {
-        task.fail_with_error("The MCC Maps folder could not be found.");
+    if !check_file_exists(&map_variants_folder) {
+        task.fail_with_error("The map variants folder could not be found.");
         return;
     }
 
     create_dir_all(&converted_maps_path).unwrap();
 
-    let variant_file_names = get_files_in_folder(&mcc_maps_folder).unwrap_or_else(|err|{
+    let variant_file_names = get_files_in_folder(&map_variants_folder).unwrap_or_else(|err|{
         task.fail_with_error(err);
         panic!()
     });
 
+    let mcc_maps = match &mcc_maps_folder {
+        Some(mcc_maps_folder) => read_mcc_map_details(&mut task, mcc_maps_folder),
+        None => HashMap::new(),
+    };
+
     for variant_file_name in variant_file_names {
-        let mut map_variant: Option<c_map_variant> = None;
         let variant_file_path = build_path!(
-            &mcc_maps_folder,
+            &map_variants_folder,
             variant_file_name
         );
-        let map_variant_chunk = search_for_chunk_in_file::<s_blf_chunk_map_variant>(&variant_file_path).unwrap_or(None);
-        if map_variant_chunk.is_some() {
-            map_variant = Some(map_variant_chunk.unwrap().map_variant);
-        }
-        let packed_map_variant = search_for_chunk_in_file::<s_blf_chunk_packed_map_variant>(&variant_file_path).unwrap_or(None);
-        if packed_map_variant.is_some() {
-            map_variant = Some(packed_map_variant.unwrap().map_variant);
-        }
-        if map_variant.is_some() {
-            let mut map_variant = map_variant.unwrap().clone();
 
-            if (map_variant.m_map_variant_version == 13 || map_variant.m_map_variant_version == 14) && !convert_mcc_map(&mut task, &mut map_variant) {
+        if let Some(mut map_variant) = read_map_variant(&variant_file_path) {
+            if to_mcc {
+                if map_variant.m_map_variant_version == 12 && !convert_360_map(&mut task, &mut map_variant, &mcc_maps) {
+                    continue;
+                }
+            }
+            else if (map_variant.m_map_variant_version == 13 || map_variant.m_map_variant_version == 14) && !convert_mcc_map(&mut task, &mut map_variant) {
                 continue;
             }
 
@@ -63,6 +77,43 @@ pub fn convert_halo3mcc_map_variants(mcc_maps_folder: String, converted_maps_pat
     task.complete();
 }
 
+fn read_map_variant(variant_file_path: &String) -> Option<c_map_variant> {
+    let mut map_variant: Option<c_map_variant> = None;
+    let map_variant_chunk = search_for_chunk_in_file::<s_blf_chunk_map_variant>(variant_file_path).unwrap_or(None);
+    if map_variant_chunk.is_some() {
+        map_variant = Some(map_variant_chunk.unwrap().map_variant);
+    }
+    let packed_map_variant = search_for_chunk_in_file::<s_blf_chunk_packed_map_variant>(variant_file_path).unwrap_or(None);
+    if packed_map_variant.is_some() {
+        map_variant = Some(packed_map_variant.unwrap().map_variant);
+    }
+    map_variant
+}
+
+fn read_mcc_map_details(task: &mut console_task, mcc_maps_folder: &String) -> HashMap<u32, s_mcc_map_details> {
+    let mut mcc_maps = HashMap::new();
+
+    let variant_file_names = get_files_in_folder(mcc_maps_folder).unwrap_or_else(|err| {
+        task.add_error(err);
+        Vec::new()
+    });
+
+    for variant_file_name in variant_file_names {
+        let Some(map_variant) = read_map_variant(&build_path!(mcc_maps_folder, variant_file_name)) else {
+            continue;
+        };
+
+        if map_variant.m_map_variant_version == 13 || map_variant.m_map_variant_version == 14 {
+            mcc_maps.entry(map_variant.m_map_id).or_insert(s_mcc_map_details {
+                original_map_rsa_signature_hash: map_variant.m_original_map_rsa_signature_hash,
+                map_variant_version: map_variant.m_map_variant_version,
+            });
+        }
+    }
+
+    mcc_maps
+}
+
 fn convert_mcc_map(task: &mut console_task, map: &mut c_map_variant) -> bool {
     let checksum = get_checksum_for_map(map.m_map_id);
 
@@ -76,41 +127,21 @@ fn convert_mcc_map(task: &mut console_task, map: &mut c_map_variant) -> bool {
     let mut bad_budget_indices = Vec::<i32>::new();
     for i in (0..map.m_number_of_placeable_object_quotas as usize).rev() {
         let quota = map.m_quotas.get_mut()[i];
-        let h3_object_index = crate::title_storage::halo3::v12070_08_09_05_2031_halo3_ship::variant_importer::mcc::object_indexes::get_h3_index_for_mcc_object(map.m_map_id, quota.object_definition_index);
+        let h3_object_index = get_h3_index_for_mcc_object(map.m_map_id, quota.object_definition_index);
 
         if h3_object_index.is_some() {
             map.m_quotas[i].object_definition_index = h3_object_index.unwrap();
         }
         else {
-            map.m_quotas.get_mut().remove(i);
-            map.m_quotas.get_mut().push(s_variant_quota::default()); // Add an empty one to the end TODO: Refactor
-            map.m_number_of_placeable_object_quotas -= 1;
             bad_budget_indices.push(i as i32);
         }
     }
 
-    let mut removed_objects_count = 0;
-    for i in (0..map.m_number_of_variant_objects as usize).rev() {
-        if bad_budget_indices.contains(&map.m_variant_objects.get()[i].variant_quota_index) {
-            map.m_variant_objects.get_mut().remove(i);
-            map.m_variant_objects.get_mut().push(s_variant_object_datum::default());
-            map.m_number_of_variant_objects -= 1;
-            removed_objects_count += 1;
-        }
-    }
+    let removed_objects_count = remove_quotas(map, &bad_budget_indices);
     if removed_objects_count > 0 {
         task.add_warning(format!("{}: {} objects have been removed.", map.m_metadata.name.get_string(), removed_objects_count));
     }
 
-    // Realign indexes after removing from quotas.
-    for bad_index in bad_budget_indices {
-        for object_index in 0..map.m_number_of_variant_objects as usize {
-            if map.m_variant_objects.get()[object_index].variant_quota_index > bad_index {
-                map.m_variant_objects.get_mut()[object_index].variant_quota_index -= 1;
-            }
-        }
-    }
-
     if map.m_spent_budget > get_map_budget(map.m_map_id) {
         task.add_warning(format!(
             "{}: The spent budget ${} exceeds the limit ${}, some objects may not spawn.",
@@ -127,6 +158,92 @@ fn convert_mcc_map(task: &mut console_task, map: &mut c_map_variant) -> bool {
     true
 }
 
+fn convert_360_map(task: &mut console_task, map: &mut c_map_variant, mcc_maps: &HashMap<u32, s_mcc_map_details>) -> bool {
+    let map_name = map.m_metadata.name.get_string();
+
+    if get_checksum_for_map(map.m_map_id).is_err() {
+        task.add_error(format!("Skipped map variant {map_name} because the map is unsupported."));
+        return false;
+    }
+
+    let Some(mcc_map) = mcc_maps.get(&map.m_map_id) else {
+        task.add_error(format!("Skipped map variant {map_name} because no MCC map variant was found for map {}, its checksum is unknown.", map.m_map_id));
+        return false;
+    };
+
+    let mut bad_budget_indices = Vec::<i32>::new();
+    for i in (0..map.m_number_of_placeable_object_quotas as usize).rev() {
+        let quota = map.m_quotas[i];
+        let mcc_object_index = get_mcc_index_for_h3_object(map.m_map_id, quota.object_definition_index);
+
+        if let Some(mcc_object_index) = mcc_object_index {
+            map.m_quotas.get_mut()[i].object_definition_index = mcc_object_index;
+            continue;
+        }
+
+        let placed_objects_count = map.m_variant_objects.get()[0..map.m_number_of_variant_objects as usize].iter()
+            .filter(|variant_object| variant_object.variant_quota_index == i as i32)
+            .count();
+
+        task.add_warning(format!(
+            "{map_name}: Object {:08X} has no MCC equivalent, {placed_objects_count} placed have been removed.",
+            quota.object_definition_index
+        ));
+
+        // Removed objects no longer count towards the budget.
+        map.m_spent_budget = Float32(map.m_spent_budget - f32::from(quota.price_per_item) * placed_objects_count as f32);
+        bad_budget_indices.push(i as i32);
+    }
+
+    remove_quotas(map, &bad_budget_indices);
+
+    if map.m_spent_budget > get_map_budget(map.m_map_id) {
+        task.add_warning(format!(
+            "{map_name}: The spent budget ${} exceeds the limit ${}, some objects may not spawn.",
+            map.m_spent_budget,
+            get_map_budget(map.m_map_id)
+        ));
+    }
+
+    map.m_original_map_rsa_signature_hash = mcc_map.original_map_rsa_signature_hash;
+    map.m_map_variant_version = mcc_map.map_variant_version;
+
+    task.add_message(format!("Converted map {map_name}"));
+
+    true
+}
+
+// Removes quotas by index, along with every object placed from them. Indexes must be in descending order.
+// Returns the number of objects removed.
+fn remove_quotas(map: &mut c_map_variant, bad_budget_indices: &[i32]) -> usize {
+    for bad_index in bad_budget_indices {
+        map.m_quotas.get_mut().remove(*bad_index as usize);
+        map.m_quotas.get_mut().push(s_variant_quota::default()); // Add an empty one to the end TODO: Refactor
+        map.m_number_of_placeable_object_quotas -= 1;
+    }
+
+    let mut removed_objects_count = 0;
+    for i in (0..map.m_number_of_variant_objects as usize).rev() {
+        if bad_budget_indices.contains(&map.m_variant_objects.get()[i].variant_quota_index) {
+            map.m_variant_objects.get_mut().remove(i);
+            map.m_variant_objects.get_mut().push(s_variant_object_datum::default());
+            map.m_number_of_variant_objects -= 1;
+            removed_objects_count += 1;
+        }
+    }
+
+    // Realign indexes after removing from quotas.
+    for bad_index in bad_budget_indices {
+        for object_index in 0..map.m_number_of_variant_objects as usize {
+            if map.m_variant_objects.get()[object_index].variant_quota_index > *bad_index {
+                map.m_variant_objects.get_mut()[object_index].variant_quota_index -= 1;
+            }
+        }
+    }
+
+    removed_objects_count
+}
+
 fn get_checksum_for_map(map_id: u32) -> Result<u32, Box<dyn Error>> {
     match map_id {
         030 => Ok(0xA9494AE8), // Last Resort
@@ -0,0 +1,101 @@
+use std::fs::create_dir_all;
+use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::game_variant_conversion::{convert_mcc_game_variant_to_tu1, convert_tu1_game_variant_to_mcc};
+use blf_lib::blf::BlfFileBuilder;
+use blf_lib::blf::chunks::search_for_chunk_in_file;
+use blf_lib::blf::versions::haloreach::v12065_11_08_24_1738_tu1actual as tu1;
+use blf_lib::blf::versions::haloreach_mcc::v_untracked_25_08_16_1352 as mcc;
+use blf_lib::result::BLFLibResult;
+use crate::build_path;
+use crate::console::console_task;
+use crate::io::get_files_in_folder;
+use crate::title_storage::check_file_exists;
+
+pub fn convert_reach_game_variants(variants_folder: String, converted_variants_folder: String, to_mcc: bool) {
+    let mut task = console_task::start(if to_mcc {
+        "Converting TU1 game variants to MCC"
+    } else {
+        "Converting MCC game variants to TU1"
+    });
+
+    if !check_file_exists(&variants_folder) {
+        task.fail_with_error("The game variants folder could not be found.");
+        return;
+    }
+
+    create_dir_all(&converted_variants_folder).unwrap();
+
+    let variant_file_names = get_files_in_folder(&variants_folder).unwrap_or_else(|err|{
+        task.fail_with_error(err);
+        panic!()
+    });
+
+    for variant_file_name in variant_file_names {
+        let variant_file_path = build_path!(
+            &variants_folder,
+            &variant_file_name
+        );
+        let converted_variant_path = build_path!(
+            &converted_variants_folder,
+            &variant_file_name
+        );
+
+        let converted = if to_mcc {
+            convert_tu1_game_variant_file(&variant_file_path, &converted_variant_path)
+        } else {
+            convert_mcc_game_variant_file(&variant_file_path, &converted_variant_path)
+        };
+
+        match converted {
+            Ok(true) => task.add_message(format!("Converted game variant {variant_file_name}")),
+            Ok(false) => task.add_warning(format!("Skipped {variant_file_name} because it has no game variant.")),
+            Err(err) => task.add_error(format!("Skipped game variant {variant_file_name}: {err}")),
+        }
+    }
+
+    task.complete();
+}
+
+fn convert_tu1_game_variant_file(variant_file_path: &String, converted_variant_path: &String) -> BLFLibResult<bool> {
+    let game_variant = if let Some(chunk) = search_for_chunk_in_file::<tu1::s_blf_chunk_game_variant>(variant_file_path)? {
+        chunk.game_variant
+    } else if let Some(chunk) = search_for_chunk_in_file::<tu1::s_blf_chunk_matchmaking_game_variant>(variant_file_path)? {
+        chunk.game_variant
+    } else {
+        return Ok(false);
+    };
+
+    let game_variant = convert_tu1_game_variant_to_mcc(&game_variant)?;
+
+    let mut blf_file = BlfFileBuilder::new();
+    blf_file.add_chunk(mcc::s_blf_chunk_start_of_file::default());
+    blf_file.add_chunk(mcc::s_blf_chunk_content_header {
+        metadata: game_variant.get_metadata()?.clone(),
+        ..Default::default()
+    });
+    blf_file.add_chunk(mcc::s_blf_chunk_game_variant::create(game_variant));
+    blf_file.add_chunk(mcc::s_blf_chunk_end_of_file::default());
+    blf_file.write_file(converted_variant_path)?;
+
+    Ok(true)
+}
+
+fn convert_mcc_game_variant_file(variant_file_path: &String, converted_variant_path: &String) -> BLFLibResult<bool> {
+    let game_variant = if let Some(chunk) = search_for_chunk_in_file::<mcc::s_blf_chunk_game_variant>(variant_file_path)? {
+        chunk.game_variant
+    } else if let Some(chunk) = search_for_chunk_in_file::<mcc::s_blf_chunk_matchmaking_game_variant>(variant_file_path)? {
+        chunk.game_variant
+    } else {
+        return Ok(false);
+    };
+
+    let game_variant = convert_mcc_game_variant_to_tu1(&game_variant)?;
+
+    let mut blf_file = BlfFileBuilder::new();
+    blf_file.add_chunk(tu1::s_blf_chunk_start_of_file::default());
+    blf_file.add_chunk(tu1::s_blf_chunk_content_header::create_for_game_variant(&game_variant)?);
+    blf_file.add_chunk(tu1::s_blf_chunk_game_variant::create(game_variant));
+    blf_file.add_chunk(tu1::s_blf_chunk_end_of_file::default());
+    blf_file.write_file(converted_variant_path)?;
+
+    Ok(true)
+}
@@ -0,0 +1,50 @@
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_variant::c_game_variant;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_decompiler::decompile_megalo_script;
+use blf_lib::blf::reader::BlfFileReader;
+use blf_lib::blf::versions::haloreach::v12065_11_08_24_1738_tu1actual::{s_blf_chunk_game_variant, s_blf_chunk_matchmaking_game_variant};
+use blf_lib::blf::chunks::BlfChunk;
+
+pub fn decompile_script(variant_path: String) {
+    let mut reader = BlfFileReader::open(&variant_path).unwrap_or_else(|err| {
+        eprintln!("Could not read {variant_path}: {err}");
+        std::process::exit(1);
+    });
+
+    let mut game_variant: Option<c_game_variant> = None;
+    while let Some(next) = reader.next() {
+        let (header, offset) = next.unwrap_or_else(|err| {
+            eprintln!("Could not read {variant_path}: {err}");
+            std::process::exit(1);
+        });
+
+        let read_result = if header.signature == s_blf_chunk_game_variant::get_signature() && header.version == s_blf_chunk_game_variant::get_version() {
+            reader.read_chunk::<s_blf_chunk_game_variant>(&header, offset).map(|chunk| chunk.game_variant)
+        } else if header.signature == s_blf_chunk_matchmaking_game_variant::get_signature() && header.version == s_blf_chunk_matchmaking_game_variant::get_version() {
+            reader.read_chunk::<s_blf_chunk_matchmaking_game_variant>(&header, offset).map(|chunk| chunk.game_variant)
+        } else {
+            continue;
+        };
+
+        game_variant = Some(read_result.unwrap_or_else(|err| {
+            eprintln!("Could not read the game variant: {err}");
+            std::process::exit(1);
+        }));
+        break;
+    }
+
+    let Some(game_variant) = game_variant else {
+        eprintln!("No Halo: Reach game variant was found in {variant_path}.");
+        std::process::exit(1);
+    };
+
+    let custom_variant = game_variant.m_custom_variant.as_ref()
+        .or(game_variant.m_sandbox_variant.as_ref().map(|sandbox_variant| &sandbox_variant.m_custom_variant));
+
+    match custom_variant {
+        Some(custom_variant) => print!("{}", decompile_megalo_script(custom_variant)),
+        None => {
+            eprintln!("{variant_path} is a {:?} variant, which has no script.", game_variant.m_game_engine);
+            std::process::exit(1);
+        }
+    }
+}
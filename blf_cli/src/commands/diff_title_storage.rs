@@ -0,0 +1,243 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use colored::Colorize;
+use csv::ReaderBuilder;
+use serde_json::Value;
+use tempdir::TempDir;
+use blf_lib::result::BLFLibResult;
+use crate::build_path;
+use crate::io::{get_directories_in_folder, get_files_in_folder};
+use crate::title_storage::get_title_converter;
+
+// Title storage is compared as config, so every title with a TitleConverter can be diffed the same way.
+// JSON is compared field by field, game set CSVs row by row and text files line by line.
+
+pub fn diff_title_storage(
+    old_path: String,
+    new_path: String,
+    title: String,
+    version: String,
+    config: bool,
+) {
+    let mut title_converter = get_title_converter(&title, &version)
+        .unwrap_or_else(|| panic!("No title converter was found for the provided title and version: {title}, {version}"));
+
+    // The temp dirs need to outlive the diff.
+    let temp_dirs = (!config).then(|| {
+        (TempDir::new("blf_cli").unwrap(), TempDir::new("blf_cli").unwrap())
+    });
+
+    let (old_config_path, new_config_path) = match &temp_dirs {
+        Some((old_temp_dir, new_temp_dir)) => {
+            let old_config_path = old_temp_dir.path().to_str().unwrap().to_string();
+            let new_config_path = new_temp_dir.path().to_str().unwrap().to_string();
+
+            // build_config reports its own errors, a build that wrote nothing would otherwise diff as an empty tree.
+            for (blfs_path, config_path) in [(&old_path, &old_config_path), (&new_path, &new_config_path)] {
+                title_converter.build_config(blfs_path, config_path);
+
+                if fs::read_dir(config_path).map(|mut entries| entries.next().is_none()).unwrap_or(true) {
+                    eprintln!("{}", format!("Failed to build a config from {blfs_path}, nothing was written.").on_red());
+                    std::process::exit(1);
+                }
+            }
+            println!();
+
+            (old_config_path, new_config_path)
+        }
+        None => (old_path.clone(), new_path.clone()),
+    };
+
+    println!("{}", format!("Comparing {old_path} to {new_path}").bold());
+
+    let mut change_count = 0;
+    if let Err(error) = diff_folder(&old_config_path, &new_config_path, "", &mut change_count) {
+        eprintln!("{}", format!("Failed to compare title storage: {error}").on_red());
+        std::process::exit(1);
+    }
+
+    if change_count == 0 {
+        println!("No changes.");
+    }
+    else {
+        println!("{change_count} change(s).");
+    }
+}
+
+fn print_added(path: &str, change_count: &mut usize) {
+    println!("{}", format!("+ {path}").green());
+    *change_count += 1;
+}
+
+fn print_removed(path: &str, change_count: &mut usize) {
+    println!("{}", format!("- {path}").red());
+    *change_count += 1;
+}
+
+fn print_changed(path: &str, description: impl Into<String>, change_count: &mut usize) {
+    println!("{} {}", format!("~ {path}").yellow(), description.into());
+    *change_count += 1;
+}
+
+// Folders only in one tree, such as added or removed hoppers, are reported once rather than per file.
+fn diff_folder(old_folder: &String, new_folder: &String, relative_path: &str, change_count: &mut usize) -> BLFLibResult {
+    let old_directories = BTreeSet::from_iter(get_directories_in_folder(old_folder)?);
+    let new_directories = BTreeSet::from_iter(get_directories_in_folder(new_folder)?);
+
+    for directory in old_directories.union(&new_directories) {
+        let path = format!("{relative_path}{directory}/");
+
+        match (old_directories.contains(directory), new_directories.contains(directory)) {
+            (true, false) => print_removed(&path, change_count),
+            (false, true) => print_added(&path, change_count),
+            _ => diff_folder(&build_path!(old_folder, directory), &build_path!(new_folder, directory), &path, change_count)?,
+        }
+    }
+
+    let old_files = BTreeSet::from_iter(get_files_in_folder(old_folder)?);
+    let new_files = BTreeSet::from_iter(get_files_in_folder(new_folder)?);
+
+    for file in old_files.union(&new_files) {
+        let path = format!("{relative_path}{file}");
+
+        match (old_files.contains(file), new_files.contains(file)) {
+            (true, false) => print_removed(&path, change_count),
+            (false, true) => print_added(&path, change_count),
+            _ => diff_file(&build_path!(old_folder, file), &build_path!(new_folder, file), &path, change_count)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn diff_file(old_file: &String, new_file: &String, path: &str, change_count: &mut usize) -> BLFLibResult {
+    let old_data = fs::read(old_file)?;
+    let new_data = fs::read(new_file)?;
+
+    if old_data == new_data {
+        return Ok(());
+    }
+
+    if path.ends_with(".json") {
+        diff_json("", &serde_json::from_slice(&old_data)?, &serde_json::from_slice(&new_data)?, path, change_count);
+    }
+    else if path.ends_with(".csv") {
+        diff_csv(&old_data, &new_data, path, change_count)?;
+    }
+    else if path.ends_with(".txt") {
+        diff_text(&String::from_utf8(old_data)?, &String::from_utf8(new_data)?, path, change_count);
+    }
+    else {
+        print_changed(path, "(binary file changed)", change_count);
+    }
+
+    Ok(())
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => format!("\"{string}\""),
+        Value::Null => String::from("(none)"),
+        _ => value.to_string(),
+    }
+}
+
+fn diff_json(field: &str, old_value: &Value, new_value: &Value, path: &str, change_count: &mut usize) {
+    match (old_value, new_value) {
+        (Value::Object(old_fields), Value::Object(new_fields)) => {
+            let keys = BTreeSet::from_iter(old_fields.keys().chain(new_fields.keys()));
+
+            for key in keys {
+                diff_json(
+                    &if field.is_empty() { key.clone() } else { format!("{field}.{key}") },
+                    old_fields.get(key).unwrap_or(&Value::Null),
+                    new_fields.get(key).unwrap_or(&Value::Null),
+                    path,
+                    change_count,
+                );
+            }
+        }
+        (Value::Array(old_elements), Value::Array(new_elements)) => {
+            for index in 0..old_elements.len().max(new_elements.len()) {
+                diff_json(
+                    &format!("{field}[{index}]"),
+                    old_elements.get(index).unwrap_or(&Value::Null),
+                    new_elements.get(index).unwrap_or(&Value::Null),
+                    path,
+                    change_count,
+                );
+            }
+        }
+        _ if old_value != new_value => {
+            print_changed(path, format!("{field}: {} -> {}", value_to_string(old_value), value_to_string(new_value)), change_count);
+        }
+        _ => {}
+    }
+}
+
+// Game set rows are matched by their first two columns, the map and game variant.
+fn read_csv_rows(data: &[u8]) -> BLFLibResult<(Vec<String>, BTreeMap<String, Vec<String>>)> {
+    let mut reader = ReaderBuilder::new().from_reader(data);
+    let headers = reader.headers()?.iter().map(String::from).collect::<Vec<_>>();
+    let mut rows = BTreeMap::<String, Vec<String>>::new();
+
+    for record in reader.records() {
+        let record = record?.iter().map(String::from).collect::<Vec<_>>();
+        let key = record.iter().take(2).cloned().collect::<Vec<_>>().join(", ");
+
+        // Keep duplicate rows apart.
+        let mut unique_key = key.clone();
+        let mut duplicate_index = 1;
+        while rows.contains_key(&unique_key) {
+            duplicate_index += 1;
+            unique_key = format!("{key} #{duplicate_index}");
+        }
+
+        rows.insert(unique_key, record);
+    }
+
+    Ok((headers, rows))
+}
+
+fn diff_csv(old_data: &[u8], new_data: &[u8], path: &str, change_count: &mut usize) -> BLFLibResult {
+    let (headers, old_rows) = read_csv_rows(old_data)?;
+    let (_, new_rows) = read_csv_rows(new_data)?;
+
+    for key in BTreeSet::from_iter(old_rows.keys().chain(new_rows.keys())) {
+        match (old_rows.get(key), new_rows.get(key)) {
+            (Some(_), None) => print_removed(&format!("{path} ({key})"), change_count),
+            (None, Some(_)) => print_added(&format!("{path} ({key})"), change_count),
+            (Some(old_row), Some(new_row)) => {
+                for (column, header) in headers.iter().enumerate() {
+                    let old_cell = old_row.get(column).map(String::as_str).unwrap_or_default();
+                    let new_cell = new_row.get(column).map(String::as_str).unwrap_or_default();
+
+                    if old_cell != new_cell {
+                        print_changed(path, format!("({key}) {header}: {old_cell} -> {new_cell}"), change_count);
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn diff_text(old_text: &str, new_text: &str, path: &str, change_count: &mut usize) {
+    let old_lines = old_text.lines().collect::<BTreeSet<_>>();
+    let new_lines = new_text.lines().collect::<BTreeSet<_>>();
+
+    for line in old_text.lines().filter(|line| !new_lines.contains(line)) {
+        print_removed(&format!("{path}: \"{line}\""), change_count);
+    }
+
+    for line in new_text.lines().filter(|line| !old_lines.contains(line)) {
+        print_added(&format!("{path}: \"{line}\""), change_count);
+    }
+
+    // Lines which were only reordered.
+    if old_lines == new_lines {
+        print_changed(path, "(lines reordered)", change_count);
+    }
+}
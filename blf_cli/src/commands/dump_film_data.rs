@@ -1,5 +1,7 @@
+use std::fs::{write, File};
+use std::io::BufReader;
 use blf_lib::blf::chunks::search_for_chunk_in_file;
-use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_content_header, s_blf_chunk_saved_film_header};
+use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_content_header, s_blf_chunk_saved_film_data, s_blf_chunk_saved_film_header};
 use blf_lib::io::write_json_file;
 use crate::build_path;
 use crate::console::console_task;
@@ -28,7 +30,18 @@ pub fn dump_film_data(
             let flmh = flmh.unwrap();
             let chdr = chdr.unwrap();
             write_json_file(&flmh, format!("{file_path}.json")).unwrap();
+
             task.add_message(format!("{} - {}", file_name, chdr.metadata.name.get_string()));
+
+            // The update stream isn't decoded yet, so it's dumped as-is.
+            let update_stream = File::open(&file_path)
+                .map_err(|err| err.to_string())
+                .and_then(|file| s_blf_chunk_saved_film_data::read_update_stream(BufReader::new(file)).map_err(|err| err.to_string()))
+                .and_then(|update_stream| write(format!("{file_path}.film_data.bin"), update_stream).map_err(|err| err.to_string()));
+
+            if let Err(err) = update_stream {
+                task.add_warning(format!("Failed to read film data for {file_name}: {err}"));
+            }
         }
     });
 
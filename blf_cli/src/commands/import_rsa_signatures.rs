@@ -1,6 +1,6 @@
 use std::fs::File;
 use std::io::Write;
-use blf_lib::blam::common::cache::cache_files::s_cache_file_header_v11;
+use blf_lib::blam::common::cache::cache_files::s_cache_file_header;
 use blf_lib::blf::chunks::find_chunk_in_file;
 use blf_lib::blf::versions::halo3::k_title_halo3;
 use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_scenario;
@@ -66,24 +66,36 @@ pub fn import_rsa_signatures(
                 format!("{map_file_name}.map")
             );
 
-            let cache_file = s_cache_file_header_v11::read(map_file_path);
-            if cache_file.is_err() {
-                task.add_error(format!("Failed to read {map_file_name}.map"));
+            let cache_file = match s_cache_file_header::read_file(&map_file_path) {
+                Ok(cache_file) => cache_file,
+                Err(err) => {
+                    task.add_error(format!("Failed to read {map_file_name}.map: {err}"));
+                    continue;
+                }
+            };
+
+            // Cache files store the scenario's full tag path, compare scenario names.
+            let scenario_name = |scenario_path: &str| scenario_path.rsplit('\\').next().unwrap_or_default().to_string();
+            if cache_file.scenario_path.as_ref().is_some_and(|scenario_path| scenario_name(scenario_path) != scenario_name(&map_file_name)) {
+                task.add_error(format!("{map_file_name}.map is for a different scenario: {}", cache_file.scenario_path.unwrap_or_default()));
                 continue;
             }
 
-            let cache_file = cache_file.unwrap();
+            let (Some(rsa_signature), Some(scenario_checksum)) = (&cache_file.rsa_signature, cache_file.get_scenario_checksum()) else {
+                task.add_error(format!("{map_file_name}.map ({}) has no RSA signature.", cache_file.build_string));
+                continue;
+            };
 
             let output_file_path = build_path!(
                 &rsa_signatures_config_path,
                 format!("{map_id}_{map_file_name}")
             );
 
-            task.add_message(format!("{map_id}_{map_file_name}"));
+            task.add_message(format!("{map_id}_{map_file_name} ({scenario_checksum:08X})"));
 
             create_parent_folders(&output_file_path).unwrap();
             let mut output_file = File::create(output_file_path).unwrap();
-            output_file.write_all(cache_file.rsa_signature.get()).unwrap();
+            output_file.write_all(rsa_signature).unwrap();
         }
     } else {
         task.add_error("Unsupported title and version.");
@@ -0,0 +1,108 @@
+use std::fs;
+use blf_lib::blf::chunks::find_and_validate_eof;
+use blf_lib::blf::chunks::s_blf_chunk_raw::s_blf_chunk_raw;
+use blf_lib::blf::chunks::DynTitleAndBuild;
+use blf_lib::blf::detection::detect_build;
+use blf_lib::blf::registry::{chunk_to_json, find_registration_for_chunk, get_build_registrations, parse_chunk, read_headers, s_blf_build_registration};
+use blf_lib::blf::s_blf_header;
+use blf_lib::io::bitstream::{format_bitstream_trace, trace_bitstream};
+
+pub fn inspect(
+    blf_path: String,
+    title: Option<String>,
+    version: Option<String>,
+    json: bool,
+    trace: bool,
+) {
+    let buffer = fs::read(&blf_path).unwrap_or_else(|err| {
+        eprintln!("Could not read {blf_path}: {err}");
+        std::process::exit(1);
+    });
+
+    let build: Option<&s_blf_build_registration> = match (&title, &version) {
+        (Some(title), Some(version)) => Some(
+            get_build_registrations()
+                .into_iter()
+                .find(|build| build.title == title.as_str() && build.build_string == version.as_str())
+                .unwrap_or_else(|| {
+                    eprintln!("No build was found for the provided title and version: {title}, {version}");
+                    std::process::exit(1);
+                })
+        ),
+        (None, None) => detect_build(&buffer),
+        _ => {
+            eprintln!("Please provide both a title and a version, or neither.");
+            std::process::exit(1);
+        }
+    };
+
+    println!("File: {blf_path} ({} bytes)", buffer.len());
+    match build {
+        Some(build) => println!("Build: {} {}{}", build.title(), build.build_string(), if title.is_none() { " (best guess)" } else { "" }),
+        None => println!("Build: unknown"),
+    }
+    match find_and_validate_eof(&buffer) {
+        Ok(_) => println!("_eof: valid"),
+        Err(err) => println!("_eof: invalid ({err})"),
+    }
+    println!();
+
+    let headers = read_headers(&buffer);
+    let read_size = headers.last()
+        .map(|(offset, header)| offset + header.chunk_size as usize)
+        .unwrap_or_default();
+
+    println!("{:<10} {:<6} {:<7} {:<10} {}", "Offset", "Chunk", "Version", "Size", "Type");
+    for (offset, header) in &headers {
+        let read_chunk = || build.map(|build| parse_chunk(&buffer, *offset, header, build));
+        let (chunk, trace_entries) = if trace { trace_bitstream(read_chunk) } else { (read_chunk(), Vec::new()) };
+
+        let type_name = match &chunk {
+            None => String::from("-"),
+            Some(Err(err)) => format!("failed to read: {err}"),
+            Some(Ok(chunk)) => match find_registration_for_chunk(chunk.as_ref()) {
+                Some(registration) => registration.module_path.to_string(),
+                None => String::from("unknown"),
+            },
+        };
+
+        println!(
+            "{:<10} {:<6} {:<7} {:<10} {}",
+            format!("0x{:X}", offset),
+            header.signature.to_string(),
+            header.version.to_string(),
+            format!("0x{:X}", header.chunk_size),
+            type_name,
+        );
+
+        // Offsets are relative to the data each bitstream was created with, which is usually the chunk body.
+        if !trace_entries.is_empty() {
+            let body = &buffer[offset + s_blf_header::size()..offset + header.chunk_size as usize];
+            print!("{}", format_bitstream_trace(body, &trace_entries));
+        }
+
+        if !json {
+            continue;
+        }
+
+        match &chunk {
+            Some(Ok(chunk)) => match chunk_to_json(chunk.as_ref()) {
+                Some(Ok(value)) => println!("{}", serde_json::to_string_pretty(&value).unwrap()),
+                Some(Err(err)) => println!("Failed to serialize chunk: {err}"),
+                None => if let Some(raw) = chunk.as_any().downcast_ref::<s_blf_chunk_raw>() {
+                    println!("{} bytes of unknown data", raw.data.len());
+                },
+            },
+            _ => {},
+        }
+    }
+
+    if read_size < buffer.len() {
+        println!();
+        println!("{} trailing bytes after 0x{:X} were not read.", buffer.len() - read_size, read_size);
+    }
+
+    if headers.is_empty() {
+        println!("No chunks found, is this a BLF file? (expected {} byte chunk headers)", s_blf_header::size());
+    }
+}
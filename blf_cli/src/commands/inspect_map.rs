@@ -0,0 +1,28 @@
+use blf_lib::blam::common::cache::cache_files::s_cache_file_header;
+
+pub fn inspect_map(
+    map_path: String,
+    json: bool,
+) {
+    let header = s_cache_file_header::read_file(&map_path).unwrap_or_else(|err| {
+        eprintln!("Could not read {map_path}: {err}");
+        std::process::exit(1);
+    });
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&header).unwrap());
+        return;
+    }
+
+    let unknown = || String::from("unknown");
+
+    println!("File: {map_path}");
+    println!("Version: {} ({:?})", header.version, header.byte_order);
+    println!("Layout: {}", header.layout.unwrap_or("unmapped"));
+    println!("Build: {}", header.build_string);
+    println!("File length: {} bytes", header.file_length);
+    println!("Map type: {}", header.map_type.map(|map_type| format!("{map_type:?}")).unwrap_or_else(unknown));
+    println!("Map name: {}", header.map_name.clone().unwrap_or_else(unknown));
+    println!("Scenario: {}", header.scenario_path.clone().unwrap_or_else(unknown));
+    println!("Scenario checksum: {}", header.get_scenario_checksum().map(|checksum| format!("{checksum:08X}")).unwrap_or_else(unknown));
+}
@@ -0,0 +1,236 @@
+use std::fs;
+use std::fs::exists;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use colored::Colorize;
+use blf_lib::blf::chunks::{BlfChunk, ReadableBlfChunk, SerializableBlfChunk};
+use blf_lib::blf::{get_buffer_hash, s_blf_header};
+use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_online_file_manifest;
+use blf_lib::assert_ok;
+use blf_lib::result::BLFLibResult;
+use crate::io::{build_path, get_directories_in_folder, get_files_in_folder};
+use crate::title_storage::get_title_converter;
+
+// Serves a built title storage folder to local clients, such as an emulator or curl, in place of a real web server.
+// Manifests are rehashed on every request, so files edited by hand are never rejected for a stale hash.
+
+const k_manifest_file_name_prefix: &str = "manifest_";
+// Manifest cache keys are relative to the title storage folder, eg "/title/default_hoppers/network_configuration_135.bin"
+const k_manifest_cache_key_prefix: &str = "/title/";
+const k_config_poll_interval: Duration = Duration::from_secs(1);
+
+pub fn serve(
+    blf_path: String,
+    port: u16,
+    url_prefix: String,
+    config_path: Option<String>,
+    title: Option<String>,
+    version: Option<String>,
+) {
+    if let (Some(config_path), Some(title), Some(version)) = (config_path, title, version) {
+        let mut title_converter = get_title_converter(&title, &version)
+            .unwrap_or_else(|| panic!("No title converter was found for the provided title and version: {title}, {version}"));
+
+        title_converter.build_blfs(&config_path, &blf_path);
+
+        let blf_path = blf_path.clone();
+        thread::spawn(move || watch_config(config_path, blf_path, title, version));
+    }
+
+    let url_prefix = url_prefix.trim_end_matches('/').to_string();
+
+    // Only local clients are served.
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|error| {
+        eprintln!("{}", format!("Failed to listen on port {port}: {error}").on_red());
+        std::process::exit(1);
+    });
+
+    println!("{}", format!("Serving {blf_path} at http://127.0.0.1:{port}{url_prefix}/").bold());
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        let blf_path = blf_path.clone();
+        let url_prefix = url_prefix.clone();
+        thread::spawn(move || {
+            if let Err(error) = handle_request(stream, &blf_path, &url_prefix) {
+                eprintln!("{}", format!("Failed to handle request: {error}").red());
+            }
+        });
+    }
+}
+
+fn handle_request(mut stream: TcpStream, blf_path: &str, url_prefix: &str) -> BLFLibResult {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Headers aren't used, but are read so that the connection isn't reset while the client is still sending.
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut request_line = request_line.split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default().split('?').next().unwrap_or_default();
+
+    let (status, body) = match method {
+        "GET" | "HEAD" => match read_requested_file(blf_path, url_prefix, path) {
+            Ok(Some(data)) => ("200 OK", data),
+            Ok(None) => ("404 Not Found", Vec::new()),
+            Err(error) => {
+                eprintln!("{}", format!("Failed to read {path}: {error}").red());
+                ("500 Internal Server Error", Vec::new())
+            }
+        },
+        _ => ("405 Method Not Allowed", Vec::new()),
+    };
+
+    let log_line = format!("{method} {path} {status}");
+    if status.starts_with('2') {
+        println!("{log_line}");
+    }
+    else {
+        println!("{}", log_line.yellow());
+    }
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+
+    if method != "HEAD" {
+        stream.write_all(&body)?;
+    }
+
+    Ok(())
+}
+
+// Returns None if the path is outside of the served folder or the file doesn't exist.
+fn read_requested_file(blf_path: &str, url_prefix: &str, path: &str) -> BLFLibResult<Option<Vec<u8>>> {
+    let Some(file_path) = path.strip_prefix(url_prefix).and_then(|path| get_file_path(blf_path, path)) else {
+        return Ok(None);
+    };
+
+    if !exists(&file_path)? || !fs::metadata(&file_path)?.is_file() {
+        return Ok(None);
+    }
+
+    let data = fs::read(&file_path)?;
+
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    if file_name.starts_with(k_manifest_file_name_prefix) {
+        return Ok(Some(rehash_manifest(blf_path, data)?));
+    }
+
+    Ok(Some(data))
+}
+
+// Paths are only resolved from plain file and folder names, so requests can't leave the served folder.
+fn get_file_path(blf_path: &str, relative_path: &str) -> Option<String> {
+    let names = relative_path.strip_prefix('/')?.split('/').collect::<Vec<_>>();
+
+    if names.iter().any(|name| name.is_empty() || *name == "." || *name == ".." || name.contains('\\')) {
+        return None;
+    }
+
+    Some(build_path(
+        std::iter::once(blf_path).chain(names).map(String::from).collect()
+    ))
+}
+
+fn rehash_manifest(blf_path: &str, mut data: Vec<u8>) -> BLFLibResult<Vec<u8>> {
+    for offset in 0..data.len().saturating_sub(s_blf_header::size()) {
+        let header = s_blf_header::decode(&data[offset..offset + s_blf_header::size()])?;
+        let chunk_end = offset + header.chunk_size as usize;
+
+        if header.signature != s_blf_chunk_online_file_manifest::get_signature()
+            || header.version != s_blf_chunk_online_file_manifest::get_version()
+            || (header.chunk_size as usize) < s_blf_header::size()
+            || chunk_end > data.len()
+        {
+            continue;
+        }
+
+        let mut manifest = s_blf_chunk_online_file_manifest::read(
+            data[offset + s_blf_header::size()..chunk_end].to_vec(),
+            Some(header),
+            &Vec::new(),
+        )?;
+
+        manifest.update_file_hashes(|cache_key| {
+            let Some(file_path) = cache_key.strip_prefix(k_manifest_cache_key_prefix)
+                .and_then(|relative_path| get_file_path(blf_path, &format!("/{relative_path}")))
+            else {
+                return Ok(None);
+            };
+
+            if !exists(&file_path)? {
+                return Ok(None);
+            }
+
+            Ok(Some(get_buffer_hash(&fs::read(&file_path)?)?))
+        })?;
+
+        // Only the hashes change, so the manifest is rewritten in place.
+        let rehashed_manifest = manifest.write(&Vec::new())?;
+        assert_ok!(rehashed_manifest.len() == chunk_end - offset, "The rehashed manifest is a different size to the original.");
+        data[offset..chunk_end].copy_from_slice(&rehashed_manifest);
+        break;
+    }
+
+    Ok(data)
+}
+
+fn watch_config(config_path: String, blf_path: String, title: String, version: String) {
+    let mut config_files = get_config_files(&config_path);
+
+    loop {
+        thread::sleep(k_config_poll_interval);
+
+        let latest_config_files = get_config_files(&config_path);
+        if latest_config_files == config_files {
+            continue;
+        }
+        config_files = latest_config_files;
+
+        println!("{}", "Config changed, rebuilding...".bold());
+
+        // A bad config shouldn't stop the server, the previous build is served until the next change.
+        let rebuilt = catch_unwind(AssertUnwindSafe(|| {
+            let mut title_converter = get_title_converter(&title, &version).unwrap();
+            title_converter.build_blfs(&config_path, &blf_path);
+        }));
+
+        if rebuilt.is_err() {
+            println!("{}", "Failed to rebuild title storage, serving the previous build.".bright_white().on_red());
+        }
+    }
+}
+
+// Every file in the config folder and when it was last modified, so that added and removed files are noticed too.
+fn get_config_files(path: &String) -> Vec<(String, Option<SystemTime>)> {
+    let mut config_files = get_files_in_folder(path).unwrap_or_default().into_iter()
+        .map(|file_name| {
+            let file_path = build_path(vec![path.clone(), file_name]);
+            let modified = fs::metadata(&file_path).and_then(|metadata| metadata.modified()).ok();
+            (file_path, modified)
+        })
+        .collect::<Vec<_>>();
+
+    for directory in get_directories_in_folder(path).unwrap_or_default() {
+        config_files.extend(get_config_files(&build_path(vec![path.clone(), directory])));
+    }
+
+    config_files.sort();
+    config_files
+}
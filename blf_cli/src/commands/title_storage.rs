@@ -16,6 +16,24 @@ pub enum TitleStorageSubcommands {
         title: String,
         version: String,
     },
+    /// Checks a title storage config folder for problems before building, without writing any BLFs.
+    #[command(arg_required_else_help = true)]
+    Validate {
+        config_input_path: String,
+        title: String,
+        version: String,
+    },
+    /// Lists what changed between two title storage folders, such as hoppers, game sets, variants and MOTDs.
+    #[command(arg_required_else_help = true)]
+    Diff {
+        old_path: String,
+        new_path: String,
+        title: String,
+        version: String,
+        /// Compare two config folders, rather than two built BLF folders.
+        #[arg(long)]
+        config: bool,
+    },
     #[command(arg_required_else_help = true)]
     BuildConfig {
         blf_input_path: String,
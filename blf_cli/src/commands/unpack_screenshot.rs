@@ -1,46 +1,45 @@
 use std::fs;
 use std::path::Path;
-use blf_lib::blf::chunks::search_for_chunk_in_file;
-use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_content_header, s_blf_chunk_screenshot_camera, s_blf_chunk_screenshot_data};
+use blf_lib::blf::content::read_screenshot;
 
 pub fn unpack_screenshot(
     screenshot_path: String,
     output_path: Option<String>,
 ) {
-    let header: s_blf_chunk_content_header = search_for_chunk_in_file(&screenshot_path)
-        .unwrap()
-        .expect("Could not find Screenshot header.");
-    let camera: s_blf_chunk_screenshot_camera = search_for_chunk_in_file(&screenshot_path)
-        .unwrap()
-        .expect("Could not find Screenshot camera.");
-    let data: s_blf_chunk_screenshot_data = search_for_chunk_in_file(&screenshot_path)
-        .unwrap()
-        .expect("Could not find Screenshot data.");
+    let screenshot = fs::read(&screenshot_path)
+        .map_err(|err| err.into())
+        .and_then(|data| read_screenshot(&data))
+        .unwrap_or_else(|err| {
+            eprintln!("Could not read screenshot: {err}");
+            std::process::exit(1);
+        });
+    let summary = &screenshot.summary;
 
-    println!("{} by {} ({:X})", header.metadata.name.get_string(), header.metadata.author.get_string().unwrap(), header.metadata.author_id);
-    println!("Taken on {}", header.metadata.date);
-    println!("Description: \"{}\"", header.metadata.description.get_string().unwrap());
+    println!("{} by {} ({:X})", summary.name, summary.author, summary.author_xuid);
+    println!("Taken on {}", summary.date);
+    println!("Description: \"{}\"", summary.description);
     println!("--- File Data ---");
-    println!("Halo Version: {}", header.build_number);
-    println!("Map ID: {}", header.metadata.map_id);
-    println!("Game ID: {}", header.metadata.game_id);
-    println!("Unique ID: {}", header.metadata.unique_id);
-    if header.metadata.hopper_id != -1 { println!("Hopper ID: {}", header.metadata.hopper_id); }
+    println!("Title: {}", summary.title);
+    println!("Halo Version: {}", summary.build_number);
+    println!("Map ID: {}", summary.map_id);
+    println!("Game ID: {}", summary.game_id);
+    println!("Unique ID: {}", summary.unique_id);
+    if let Some(hopper_id) = summary.hopper_id { println!("Hopper ID: {}", hopper_id); }
     println!("--- Image Data ---");
-    println!("JPEG length: {}", camera.jpeg_data_length);
-    println!("Camera Position: {}, {}, {}", camera.camera.camera.position.x, camera.camera.camera.position.y, camera.camera.camera.position.z);
-    println!("Tick: game {}, film {}", camera.game_tick, camera.film_tick);
+    println!("JPEG length: {}", screenshot.jpeg_data.len());
+    if let Some(position) = screenshot.camera_position { println!("Camera Position: {}, {}, {}", position.x, position.y, position.z); }
+    if let (Some(game_tick), Some(film_tick)) = (screenshot.game_tick, screenshot.film_tick) { println!("Tick: game {}, film {}", game_tick, film_tick); }
     println!();
 
     let output_path = output_path.unwrap_or(
         Path::new(&screenshot_path)
             .parent().unwrap()
-            .join(format!("{}.jpg", header.metadata.unique_id))
+            .join(format!("{}.jpg", summary.unique_id))
             .to_str().unwrap()
             .to_string()
     );
 
-    let unpacked = fs::write(&output_path, data.jpeg_data);
+    let unpacked = fs::write(&output_path, &screenshot.jpeg_data);
 
     if unpacked.is_err() {
         eprintln!("Could not unpack screenshot.");
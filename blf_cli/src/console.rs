@@ -133,6 +133,10 @@ impl console_task {
         self.errors.push(message.into());
     }
 
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
     fn log_warning(message: &String) {
         println!("  ⚠ {}", message.bold().black().on_bright_yellow());
     }
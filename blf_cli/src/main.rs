@@ -11,10 +11,18 @@ use clap::{command, Parser};
 use crate::commands::Commands;
 use crate::commands::Commands::{ConvertH3MCCMapVariants, TitleStorage};
 use crate::commands::convert_halo3mcc_map_variants::convert_halo3mcc_map_variants;
+use crate::commands::convert_reach_game_variants::convert_reach_game_variants;
+use crate::commands::diff_title_storage::diff_title_storage;
 use crate::commands::dump_film_data::dump_film_data;
 use crate::commands::import_rsa_signatures::import_rsa_signatures;
+use crate::commands::inspect::inspect;
+use crate::commands::inspect_map::inspect_map;
+use crate::commands::content_header::{resync_content_header, validate_content_header};
+use crate::commands::decompile_script::decompile_script;
+use crate::commands::compile_script::compile_script;
 use crate::commands::import_variant::import_variant;
 use crate::commands::export_variant::export_variant;
+use crate::commands::serve::serve;
 use crate::commands::title_storage::TitleStorageSubcommands;
 use crate::commands::unpack_screenshot::unpack_screenshot;
 
@@ -49,6 +57,20 @@ fn main() {
                     &blf_output_path
                 );
             },
+            TitleStorageSubcommands::Validate { config_input_path, title, version } => {
+                let mut title_converter =
+                    title_storage::get_title_converter(&title, &version)
+                        .unwrap_or_else(|| panic!("No title converter was found for the provided title and version: {title}, {version}"));
+
+                match title_converter.validate_config(&config_input_path) {
+                    Some(true) => {}
+                    Some(false) => std::process::exit(1),
+                    None => println!("Validation is not yet supported for {title} ({version}), the config was not checked."),
+                }
+            },
+            TitleStorageSubcommands::Diff { old_path, new_path, title, version, config } => {
+                diff_title_storage(old_path, new_path, title, version, config);
+            },
             TitleStorageSubcommands::BuildConfig { blf_input_path, config_output_path, title, version } => {
                 let mut title_converter =
                     title_storage::get_title_converter(title, version)
@@ -69,8 +91,11 @@ fn main() {
                 export_variant(variant_json_path, destination_path, title, version);
             }
         },
-        ConvertH3MCCMapVariants { mcc_maps_folder, converted_maps_folder} => {
-            convert_halo3mcc_map_variants(mcc_maps_folder, converted_maps_folder);
+        ConvertH3MCCMapVariants { map_variants_folder, converted_maps_folder, to_mcc, mcc_maps_folder } => {
+            convert_halo3mcc_map_variants(map_variants_folder, converted_maps_folder, to_mcc, mcc_maps_folder);
+        }
+        Commands::ConvertReachGameVariants { variants_folder, converted_variants_folder, to_mcc } => {
+            convert_reach_game_variants(variants_folder, converted_variants_folder, to_mcc);
         }
         Commands::UnpackScreenshot { screenshot_path, output_path } => {
             unpack_screenshot(screenshot_path, output_path);
@@ -78,5 +103,26 @@ fn main() {
         Commands::DumpFilmData { film_folder} => {
             dump_film_data(film_folder);
         }
+        Commands::Inspect { blf_path, title, version, json, trace } => {
+            inspect(blf_path, title, version, json, trace);
+        }
+        Commands::InspectMap { map_path, json } => {
+            inspect_map(map_path, json);
+        }
+        Commands::ValidateContentHeader { blf_path } => {
+            validate_content_header(blf_path);
+        }
+        Commands::ResyncContentHeader { blf_path, output_path, private_key } => {
+            resync_content_header(blf_path, output_path, private_key);
+        }
+        Commands::Serve { blf_path, port, url_prefix, config, title, version } => {
+            serve(blf_path, port, url_prefix, config, title, version);
+        }
+        Commands::DecompileScript { variant_path } => {
+            decompile_script(variant_path);
+        }
+        Commands::CompileScript { variant_path, script_path, output_path } => {
+            compile_script(variant_path, script_path, output_path);
+        }
     }
 }
\ No newline at end of file
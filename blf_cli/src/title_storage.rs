@@ -13,6 +13,7 @@ use crate::title_storage::halo3::v11729_07_08_10_0021_main::v11729_07_08_10_0021
 use crate::title_storage::halo3::v11856_07_08_20_2332_release::v11856_07_08_20_2332_release;
 use crate::title_storage::halo3::v12070_08_09_05_2031_halo3_ship::v12070_08_09_05_2031_halo3_ship;
 use crate::title_storage::halo3odst::v13895_09_04_27_2201_atlas_release::v13895_09_04_27_2201_atlas_release;
+use crate::title_storage::halo4::v20810_12_09_22_1647_main::v20810_12_09_22_1647_main;
 use crate::title_storage::haloonline::v1_106708_cert_ms23___release::v1_106708_cert_ms23___release;
 use crate::title_storage::haloreach::v09449_10_03_25_1545_omaha_beta::v09449_10_03_25_1545_omaha_beta;
 use crate::title_storage::haloreach::v09730_10_04_09_1309_omaha_delta::v09730_10_04_09_1309_omaha_delta;
@@ -23,12 +24,20 @@ use crate::title_storage::haloreach::v08516_10_02_19_1607_omaha_alpha::v08516_10
 pub mod halo3;
 pub mod halo3odst;
 pub mod haloreach;
+pub mod halo4;
 pub mod ares;
 pub mod haloonline;
+pub mod build_cache;
 
 pub trait TitleConverter: DynTitleAndBuild {
     fn build_blfs(&mut self, config_path: &String, blfs_path: &String);
     fn build_config(&mut self, blfs_path: &String, config_path: &String);
+
+    /// Checks a config folder for problems which would fail or break a build, without writing any BLFs.
+    /// Returns whether the config is valid, or None if validation isn't supported for this build.
+    fn validate_config(&mut self, config_path: &String) -> Option<bool> {
+        None
+    }
 }
 
 #[macro_export]
@@ -47,6 +56,7 @@ fn get_title_converters() -> Vec<Box<dyn TitleConverter>> {
         Box::new(v13895_09_04_27_2201_atlas_release::default()),    // Halo 3: ODST
         Box::new(v12065_11_08_24_1738_tu1actual::default()),        // Halo: Reach (TU1)
         Box::new(v11860_10_07_24_0147_omaha_release::default()),    // Halo: Reach
+        Box::new(v20810_12_09_22_1647_main::default()),             // Halo 4
 
         // Pre-Releases
         Box::new(v11856_07_08_20_2332_release::default()),          // Halo 3 (Epsilon Refresh)
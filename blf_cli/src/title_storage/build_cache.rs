@@ -0,0 +1,82 @@
+use std::env::{current_exe, temp_dir};
+use std::fs;
+use std::fs::{create_dir_all, exists};
+use std::sync::OnceLock;
+use blf_lib::blf::get_buffer_hash;
+use blf_lib::result::BLFLibResult;
+use crate::build_path;
+use crate::io::create_parent_folders;
+
+// Encoding variants is the slowest part of a title storage build, so encoded variants are cached between builds.
+// Each output is keyed by a hash of everything it's built from, and is copied byte for byte,
+// so files built from the cache (and the manifests hashing them) are identical to a clean build.
+// Only game and map variants are cached, and only by the Halo 3 (12070) and Reach (12065) converters.
+// Every other file, including images and game set CSVs, is rebuilt from its config each time.
+
+pub const k_build_cache_folder_name: &str = "blf_cli_build_cache";
+
+#[derive(Clone)]
+pub struct c_build_cache {
+    folder: String,
+}
+
+impl c_build_cache {
+    /// Opens the cache for one kind of output, such as "game_variants", for a build.
+    pub fn open(build_string: &str, kind: &str) -> BLFLibResult<Self> {
+        // Any other blf_cli binary may encode differently, even with the same version number, so each gets its own cache.
+        let folder = build_path!(
+            temp_dir().to_string_lossy().to_string(),
+            k_build_cache_folder_name,
+            env!("CARGO_PKG_VERSION"),
+            get_executable_fingerprint()?,
+            build_string,
+            kind
+        );
+
+        create_dir_all(&folder)?;
+
+        Ok(Self { folder })
+    }
+
+    fn get_cached_file_path(&self, input: &[u8]) -> BLFLibResult<String> {
+        let hash = get_buffer_hash(input)?;
+        let hash = hash.data.get().iter().map(|byte| format!("{byte:02X}")).collect::<String>();
+
+        Ok(build_path!(&self.folder, format!("{hash}.bin")))
+    }
+
+    /// Copies the output cached for this input to output_path, or runs build to write it and caches the result.
+    /// Returns true if the cached output was used.
+    pub fn build(&self, input: &[u8], output_path: &String, build: impl FnOnce() -> BLFLibResult) -> BLFLibResult<bool> {
+        let cached_file_path = self.get_cached_file_path(input)?;
+
+        if exists(&cached_file_path)? {
+            create_parent_folders(output_path)?;
+            fs::copy(&cached_file_path, output_path)?;
+            return Ok(true);
+        }
+
+        build()?;
+
+        // Other threads may be reading the cache, so the file is only moved into place once it's complete.
+        let partial_file_path = format!("{cached_file_path}.{}.{:?}", std::process::id(), std::thread::current().id());
+        fs::copy(output_path, &partial_file_path)?;
+        fs::rename(&partial_file_path, &cached_file_path)?;
+
+        Ok(false)
+    }
+}
+
+/// Hashes the running executable, so that rebuilding blf_cli with a changed encoder invalidates the cache.
+fn get_executable_fingerprint() -> BLFLibResult<String> {
+    static executable_fingerprint: OnceLock<String> = OnceLock::new();
+
+    if let Some(fingerprint) = executable_fingerprint.get() {
+        return Ok(fingerprint.clone());
+    }
+
+    let hash = get_buffer_hash(&fs::read(current_exe()?)?)?;
+    let fingerprint = hash.data.get().iter().take(8).map(|byte| format!("{byte:02X}")).collect::<String>();
+
+    Ok(executable_fingerprint.get_or_init(|| fingerprint).clone())
+}
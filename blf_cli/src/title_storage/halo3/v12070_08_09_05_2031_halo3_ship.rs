@@ -1,7 +1,7 @@
 pub mod variant_importer;
 pub mod variant_exporter;
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::error::Error;
 use std::fs;
 use std::fs::{create_dir_all, exists, remove_file, File};
@@ -9,12 +9,14 @@ use std::io::{Read, Write};
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::SystemTime;
 use colored::Colorize;
 use csv::{ReaderBuilder, WriterBuilder};
 use crate::io::{create_parent_folders, get_directories_in_folder, get_files_in_folder, read_text_file_lines, write_text_file, write_text_file_lines, FILE_SEPARATOR};
 use crate::{build_path, debug_log, title_converter, やった};
 use crate::title_storage::{check_file_exists, validate_jpeg, TitleConverter};
+use crate::title_storage::build_cache::c_build_cache;
 use lazy_static::lazy_static;
 use blf_lib::blam::halo3::v12070_08_09_05_2031_halo3_ship::cseries::language::{get_language_string, k_language_suffix_chinese_traditional, k_language_suffix_english, k_language_suffix_french, k_language_suffix_german, k_language_suffix_italian, k_language_suffix_japanese, k_language_suffix_korean, k_language_suffix_mexican, k_language_suffix_portuguese, k_language_suffix_spanish};
 use blf_lib::blf::{get_blf_file_hash, BlfFile, BlfFileBuilder};
@@ -32,7 +34,7 @@ use blf_lib::blam::halo3::v12070_08_09_05_2031_halo3_ship::game::game_engine_var
 use blf_lib::blam::halo3::v12070_08_09_05_2031_halo3_ship::saved_games::scenario_map_variant::c_map_variant;
 use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_hopper_configuration_table;
 use blf_lib::blf::versions::halo3::v11855_07_08_20_2317_halo3_ship::{s_blf_chunk_banhammer_messages, s_blf_chunk_online_file_manifest};
-use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_end_of_file, s_blf_chunk_game_set_entry, s_blf_chunk_map_manifest, s_blf_chunk_start_of_file};
+use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{k_hopper_maximum_hopper_count, k_maximum_game_sets, s_blf_chunk_end_of_file, s_blf_chunk_game_set_entry, s_blf_chunk_map_manifest, s_blf_chunk_start_of_file};
 use blf_lib::io::{read_file_to_string, read_json_file, write_json_file};
 use blf_lib::result::{BLFLibError, BLFLibResult};
 use blf_lib::types::string::StaticString;
@@ -578,6 +580,61 @@ impl TitleConverter for v12070_08_09_05_2031_halo3_ship {
             }
         }
     }
+
+    fn validate_config(&mut self, config_path: &String) -> Option<bool> {
+        println!("{}", format!("Validating Title Storage config in {config_path}").bold());
+
+        let hopper_directories = get_directories_in_folder(config_path).unwrap_or_else(|err|{
+            println!("{}", err);
+            panic!()
+        });
+
+        let mut valid = true;
+
+        for hopper_directory in hopper_directories {
+            println!("{} {}...", "Validating".bold(), hopper_directory.bold().bright_white());
+
+            let hoppers_config_path = build_path!(
+                config_path,
+                &hopper_directory
+            );
+
+            let result = || -> BLFLibResult<bool> {
+                let mut hoppers_valid = true;
+
+                if hopper_directory.len() > hopper_directory_name_max_length {
+                    println!("{}", format!(
+                        "Hoppers folder \"{hopper_directory}\" is too long and will be skipped when building. ({} > {} characters)",
+                        hopper_directory.len(),
+                        hopper_directory_name_max_length
+                    ).on_red());
+                    hoppers_valid = false;
+                }
+
+                let mut map_variants = BTreeSet::<String>::new();
+                let mut game_variants = BTreeSet::<String>::new();
+
+                hoppers_valid &= Self::validate_config_banhammer_messages_and_matchmaking_tips(&hoppers_config_path)?;
+                hoppers_valid &= Self::validate_config_motds(&hoppers_config_path)?;
+                hoppers_valid &= Self::validate_config_hoppers(&hoppers_config_path, &mut map_variants, &mut game_variants)?;
+                hoppers_valid &= Self::validate_config_map_variants(&hoppers_config_path, &map_variants)?;
+                hoppers_valid &= Self::validate_config_game_variants(&hoppers_config_path, &game_variants)?;
+
+                Ok(hoppers_valid)
+            }();
+
+            match result {
+                Ok(hoppers_valid) => valid &= hoppers_valid,
+                Err(error) => {
+                    println!("{}", "Failed to validate title storage for hoppers".bright_white().on_red());
+                    println!("{}", error.to_string().on_red());
+                    valid = false;
+                }
+            }
+        }
+
+        Some(valid)
+    }
 }
 
 impl v12070_08_09_05_2031_halo3_ship {
@@ -1118,6 +1175,248 @@ impl v12070_08_09_05_2031_halo3_ship {
         やった!(task)
     }
 
+    fn validate_config_banhammer_messages_and_matchmaking_tips(hoppers_config_path: &String) -> BLFLibResult<bool> {
+        let mut task = console_task::start("Validating Banhammer Messages and Matchmaking Tips");
+
+        for language_code in k_language_suffixes {
+            let banhammer_messages_path = title_storage_config::banhammer_messages_file_path(hoppers_config_path, language_code);
+            if exists(&banhammer_messages_path)? {
+                if let Err(error) = s_blf_chunk_banhammer_messages::create(read_text_file_lines(banhammer_messages_path)?) {
+                    task.add_error(format!("{} banhammer messages are invalid: {error}", get_language_string(language_code)));
+                }
+            }
+
+            let matchmaking_tips_path = title_storage_config::matchmaking_tips_file_path(hoppers_config_path, language_code);
+            if exists(&matchmaking_tips_path)? {
+                if let Err(error) = s_blf_chunk_matchmaking_tips::create(read_text_file_lines(matchmaking_tips_path)?) {
+                    task.add_error(format!("{} matchmaking tips are invalid: {error}", get_language_string(language_code)));
+                }
+            }
+        }
+
+        let valid = !task.has_errors();
+        task.complete();
+        Ok(valid)
+    }
+
+    fn validate_config_motds(hoppers_config_path: &String) -> BLFLibResult<bool> {
+        let mut task = console_task::start("Validating MOTDs");
+
+        for blue in [false, true] {
+            let motd_name = if blue { "Mythic MOTD" } else { "MOTD" };
+
+            for language_code in k_language_suffixes {
+                if exists(title_storage_config::motd_file_path(hoppers_config_path, language_code, blue))? {
+                    let image_valid = validate_jpeg(
+                        title_storage_config::motd_image_file_path(hoppers_config_path, language_code, blue),
+                        title_storage_output::motd_image_width,
+                        title_storage_output::motd_image_height,
+                        Some(title_storage_output::motd_image_max_size)
+                    );
+
+                    if let Err(error) = image_valid {
+                        task.add_error(format!("{} {motd_name} has an invalid Image: {error}", get_language_string(language_code)));
+                    }
+                }
+
+                let motd_popup_path = title_storage_config::motd_popup_file_path(hoppers_config_path, language_code, blue);
+                if exists(&motd_popup_path)? {
+                    if let Err(error) = read_json_file::<s_blf_chunk_message_of_the_day_popup>(&motd_popup_path) {
+                        task.add_error(format!("{} {motd_name} Popup is invalid: {error}", get_language_string(language_code)));
+                    }
+
+                    let image_valid = validate_jpeg(
+                        title_storage_config::motd_popup_image_file_path(hoppers_config_path, language_code, blue),
+                        title_storage_output::motd_popup_image_width,
+                        title_storage_output::motd_popup_image_height,
+                        Some(title_storage_output::motd_popup_image_max_size)
+                    );
+
+                    if let Err(error) = image_valid {
+                        task.add_error(format!("{} {motd_name} Popup has an invalid Image: {error}", get_language_string(language_code)));
+                    }
+                }
+            }
+        }
+
+        let valid = !task.has_errors();
+        task.complete();
+        Ok(valid)
+    }
+
+    fn validate_config_hoppers(
+        hoppers_config_path: &String,
+        map_variants: &mut BTreeSet<String>,
+        game_variants: &mut BTreeSet<String>,
+    ) -> BLFLibResult<bool> {
+        let mut task = console_task::start("Validating Hoppers");
+
+        let active_hopper_folders = read_text_file_lines(title_storage_config::active_hoppers_file_path(hoppers_config_path))?;
+
+        if active_hopper_folders.len() > k_hopper_maximum_hopper_count {
+            task.add_error(format!(
+                "There are too many active hoppers. ({} > {k_hopper_maximum_hopper_count})",
+                active_hopper_folders.len()
+            ));
+        }
+
+        let mut hopper_folders_by_id = HashMap::<u16, &String>::new();
+
+        for hopper_folder in &active_hopper_folders {
+            let hopper_id = match get_hopper_id_from_hopper_folder_name(hopper_folder) {
+                Ok(hopper_id) => hopper_id,
+                Err(error) => {
+                    task.add_error(error.to_string());
+                    continue;
+                }
+            };
+
+            if let Some(other_hopper_folder) = hopper_folders_by_id.insert(hopper_id, hopper_folder) {
+                task.add_error(format!("Hoppers {other_hopper_folder} and {hopper_folder} share the identifier {hopper_id}."));
+            }
+
+            let configuration_path = title_storage_config::matchmaking_hopper_configuration_file_path(hoppers_config_path, hopper_folder);
+            if !exists(&configuration_path)? {
+                task.add_error(format!("Couldn't find a configuration file for hopper {hopper_folder}!"));
+            }
+            else {
+                match read_json_file::<title_storage_config::matchmaking_hopper>(&configuration_path) {
+                    Err(error) => task.add_error(format!("Hopper {hopper_folder} has an invalid configuration: {error}")),
+                    Ok(hopper) => {
+                        if hopper.configuration.hopper_identifier != hopper_id {
+                            task.add_error(format!(
+                                "Hopper {hopper_folder} is configured with the identifier {}, which doesn't match its folder name.",
+                                hopper.configuration.hopper_identifier
+                            ));
+                        }
+
+                        for (language_code, description) in &hopper.descriptions {
+                            if let Err(error) = s_blf_chunk_hopper_description_table::default().add_description((hopper_id, description)) {
+                                task.add_error(format!("Hopper {hopper_folder} has an invalid {} description: {error}", get_language_string(language_code)));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let game_set_path = title_storage_config::game_set_file_path(hoppers_config_path, hopper_folder);
+            if !exists(&game_set_path)? {
+                task.add_error(format!("No game set was found for hopper {hopper_folder}"));
+                continue;
+            }
+
+            let mut reader = ReaderBuilder::new().from_path(&game_set_path)?;
+            let mut game_set_entry_count = 0;
+            for (row_index, row) in reader.deserialize::<title_storage_config::game_set_config_row>().enumerate() {
+                let row = match row {
+                    Ok(row) => row,
+                    Err(error) => {
+                        task.add_error(format!("Hopper {hopper_folder} has an invalid game set row {}: {error}", row_index + 1));
+                        continue;
+                    }
+                };
+
+                game_set_entry_count += 1;
+
+                let game_set_entry = StaticString::from_string(&row.map_variant_file_name).and_then(|map_variant_file_name| Ok(s_blf_chunk_game_set_entry {
+                    map_variant_file_name,
+                    game_variant_file_name: StaticString::from_string(&row.game_variant_file_name)?,
+                    ..Default::default()
+                }));
+
+                if let Err(error) = game_set_entry {
+                    task.add_error(format!("Hopper {hopper_folder} has an invalid game set row {}: {error}", row_index + 1));
+                }
+
+                if exists(title_storage_config::map_variant_file_path(hoppers_config_path, &row.map_variant_file_name))? {
+                    map_variants.insert(row.map_variant_file_name.clone());
+                }
+                else {
+                    task.add_error(format!("Hopper {hopper_folder} uses map variant \"{}\" which could not be found.", row.map_variant_file_name));
+                }
+
+                if exists(title_storage_config::game_variant_file_path(hoppers_config_path, &row.game_variant_file_name))? {
+                    game_variants.insert(row.game_variant_file_name.clone());
+                }
+                else {
+                    task.add_error(format!("Hopper {hopper_folder} uses game variant \"{}\" which could not be found.", row.game_variant_file_name));
+                }
+            }
+
+            if game_set_entry_count > k_maximum_game_sets {
+                task.add_error(format!("Hopper {hopper_folder} has too many game set entries. ({game_set_entry_count} > {k_maximum_game_sets})"));
+            }
+        }
+
+        if let Err(error) = read_json_file::<title_storage_config::matchmaking_hopper_categories>(
+            title_storage_config::matchmaking_hopper_categories_file_path(hoppers_config_path)
+        ) {
+            task.add_error(format!("Hopper categories are invalid: {error}"));
+        }
+
+        let valid = !task.has_errors();
+        task.complete();
+        Ok(valid)
+    }
+
+    fn validate_config_map_variants(hoppers_config_path: &String, map_variants: &BTreeSet<String>) -> BLFLibResult<bool> {
+        let mut task = console_task::start("Validating Map Variants");
+
+        let scenario_crc32s = Self::get_scenario_rsa_crc32s(hoppers_config_path);
+        if scenario_crc32s.is_empty() {
+            task.add_error("No RSA signatures were found");
+        }
+
+        for map_variant_file_name in map_variants {
+            let map_variant = read_json_file::<c_map_variant>(title_storage_config::map_variant_file_path(
+                hoppers_config_path,
+                map_variant_file_name,
+            ));
+
+            let map_variant = match map_variant {
+                Ok(map_variant) => map_variant,
+                Err(error) => {
+                    task.add_error(format!("Map variant \"{map_variant_file_name}\" is invalid: {error}"));
+                    continue;
+                }
+            };
+
+            match scenario_crc32s.get(&map_variant.m_map_id) {
+                None => task.add_error(format!(
+                    "Map variant \"{map_variant_file_name}\" is on map {}, which has no RSA signature.",
+                    map_variant.m_map_id
+                )),
+                Some(expected_scenario_crc) if *expected_scenario_crc != map_variant.m_original_map_rsa_signature_hash => task.add_warning(format!(
+                    "Map variant \"{map_variant_file_name}\" has a bad checksum which will be corrected when building. (got {:08X}, expected {:08X})",
+                    map_variant.m_original_map_rsa_signature_hash,
+                    expected_scenario_crc
+                )),
+                _ => {}
+            }
+        }
+
+        let valid = !task.has_errors();
+        task.complete();
+        Ok(valid)
+    }
+
+    fn validate_config_game_variants(hoppers_config_path: &String, game_variants: &BTreeSet<String>) -> BLFLibResult<bool> {
+        let mut task = console_task::start("Validating Game Variants");
+
+        for game_variant_file_name in game_variants {
+            if let Err(error) = read_json_file::<c_game_variant>(title_storage_config::game_variant_file_path(
+                hoppers_config_path,
+                game_variant_file_name,
+            )) {
+                task.add_error(format!("Game variant \"{game_variant_file_name}\" is invalid: {error}"));
+            }
+        }
+
+        let valid = !task.has_errors();
+        task.complete();
+        Ok(valid)
+    }
+
     fn build_blf_banhammer_messages(hoppers_config_folder: &String, hoppers_blf_folder: &String) -> BLFLibResult {
         let mut task = console_task::start("Building Banhammer Messages");
 
@@ -1464,6 +1763,8 @@ impl v12070_08_09_05_2031_halo3_ship {
             .build()
             .unwrap();
 
+        let build_cache = c_build_cache::open(k_build_string_halo3_ship_12070, title_storage_config::game_variants_folder_name).unwrap();
+        let cached_variant_count = Arc::new(AtomicUsize::new(0));
         let task = Arc::new(Mutex::new(task));
         let json_queue = Arc::new(Mutex::new(VecDeque::from(json_queue)));
         let shared_variant_hashes = Arc::new(Mutex::new(HashMap::new()));
@@ -1475,6 +1776,8 @@ impl v12070_08_09_05_2031_halo3_ship {
             for n in 0..cpu_cores {
                 let shared_variant_hashes = Arc::clone(&shared_variant_hashes);
                 let game_variants_temp_build_path = game_variants_temp_build_path.clone();
+                let build_cache = build_cache.clone();
+                let cached_variant_count = Arc::clone(&cached_variant_count);
                 let task = Arc::clone(&task);
                 let json_queue = Arc::clone(&json_queue);
 
@@ -1495,15 +1798,20 @@ impl v12070_08_09_05_2031_halo3_ship {
                                 )
                             );
 
-                            let game_variant: c_game_variant = serde_json::from_str(&json).unwrap();
+                            let cached = build_cache.build(json.as_bytes(), &game_variant_blf_path, || {
+                                let game_variant: c_game_variant = serde_json::from_str(&json)?;
+
+                                BlfFileBuilder::new()
+                                    .add_chunk(s_blf_chunk_start_of_file::new("game var"))
+                                    .add_chunk(s_blf_chunk_author::for_build::<v12070_08_09_05_2031_halo3_ship>())
+                                    .add_chunk(s_blf_chunk_packed_game_variant::create(game_variant))
+                                    .add_chunk(s_blf_chunk_end_of_file::default())
+                                    .write_file(&game_variant_blf_path)
+                            }).unwrap();
 
-                            BlfFileBuilder::new()
-                                .add_chunk(s_blf_chunk_start_of_file::new("game var"))
-                                .add_chunk(s_blf_chunk_author::for_build::<v12070_08_09_05_2031_halo3_ship>())
-                                .add_chunk(s_blf_chunk_packed_game_variant::create(game_variant))
-                                .add_chunk(s_blf_chunk_end_of_file::default())
-                                .write_file(&game_variant_blf_path)
-                                .unwrap();
+                            if cached {
+                                cached_variant_count.fetch_add(1, Ordering::Relaxed);
+                            }
 
                             let hash = get_blf_file_hash(game_variant_blf_path).unwrap();
                             let mut hashes = shared_variant_hashes.lock().await;
@@ -1523,7 +1831,11 @@ impl v12070_08_09_05_2031_halo3_ship {
             variant_hashes.extend(final_hashes.clone());
 
             let mut task = task.lock().await;
-            task.add_message(format!("Built {} variants.", variant_hashes.len()));
+            task.add_message(format!(
+                "Built {} variants, {} were unchanged.",
+                variant_hashes.len(),
+                cached_variant_count.load(Ordering::Relaxed)
+            ));
             task.complete();
         });
     }
@@ -1616,6 +1928,8 @@ impl v12070_08_09_05_2031_halo3_ship {
             .build()
             .unwrap();
 
+        let build_cache = c_build_cache::open(k_build_string_halo3_ship_12070, title_storage_config::map_variants_folder_name).unwrap();
+        let cached_variant_count = Arc::new(AtomicUsize::new(0));
         let task = Arc::new(Mutex::new(task));
         let json_queue = Arc::new(Mutex::new(VecDeque::from(json_queue)));
         let shared_variant_hashes = Arc::new(Mutex::new(HashMap::new()));
@@ -1629,6 +1943,8 @@ impl v12070_08_09_05_2031_halo3_ship {
                 let shared_variant_hashes = Arc::clone(&shared_variant_hashes);
                 let shared_variant_map_ids = Arc::clone(&shared_variant_map_ids);
                 let map_variants_temp_build_path = map_variants_temp_build_path.clone();
+                let build_cache = build_cache.clone();
+                let cached_variant_count = Arc::clone(&cached_variant_count);
                 let scenario_crc32s = Arc::clone(&scenario_crc32s);
                 let task = Arc::clone(&task);
                 let json_queue = Arc::clone(&json_queue);
@@ -1670,13 +1986,22 @@ impl v12070_08_09_05_2031_halo3_ship {
                             let mut map_ids = shared_variant_map_ids.lock().await;
                             map_ids.insert(map_variant_file_name.clone(), map_variant_json.m_map_id);
 
-                            BlfFileBuilder::new()
-                                .add_chunk(s_blf_chunk_start_of_file::default())
-                                .add_chunk(s_blf_chunk_packed_map_variant {
-                                    map_variant: map_variant_json
-                                })
-                                .add_chunk(s_blf_chunk_end_of_file::default())
-                                .write_file(&map_variant_blf_path).unwrap();
+                            // The corrected checksum is part of the output, so it's part of the cache key.
+                            let cache_key = [json.as_bytes(), &map_variant_json.m_original_map_rsa_signature_hash.to_be_bytes()].concat();
+
+                            let cached = build_cache.build(&cache_key, &map_variant_blf_path, || {
+                                BlfFileBuilder::new()
+                                    .add_chunk(s_blf_chunk_start_of_file::default())
+                                    .add_chunk(s_blf_chunk_packed_map_variant {
+                                        map_variant: map_variant_json
+                                    })
+                                    .add_chunk(s_blf_chunk_end_of_file::default())
+                                    .write_file(&map_variant_blf_path)
+                            }).unwrap();
+
+                            if cached {
+                                cached_variant_count.fetch_add(1, Ordering::Relaxed);
+                            }
 
                             let hash = get_blf_file_hash(map_variant_blf_path).unwrap();
                             let mut hashes = shared_variant_hashes.lock().await;
@@ -1699,7 +2024,11 @@ impl v12070_08_09_05_2031_halo3_ship {
             variant_map_ids.extend(final_map_ids.clone());
 
             let mut task = task.lock().await;
-            task.add_message(format!("Built {} variants.", variant_hashes.len()));
+            task.add_message(format!(
+                "Built {} variants, {} were unchanged.",
+                variant_hashes.len(),
+                cached_variant_count.load(Ordering::Relaxed)
+            ));
             task.complete();
         });
     }
@@ -23,82 +23,93 @@ mod cold_storage;
 mod heretic;
 mod citadel;
 
-pub fn get_h3_index_for_mcc_object(map_id: u32, mcc_object_index: u32) -> Option<u32> {
+use bimap::BiMap;
+
+// Each map's objects, 360 object indexes on the left and MCC's on the right.
+fn get_objects_map(map_id: u32) -> &'static BiMap<u32, u32> {
     match map_id {
         30 => {
-            last_resort::last_resort_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*last_resort::last_resort_objects_map
         }
         300 => {
-            construct::construct_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*construct::construct_objects_map
         }
         310 => {
-            deadlock::deadlock_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*deadlock::deadlock_objects_map
         }
         320 => {
-            guardian::guardian_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*guardian::guardian_objects_map
         }
         330 => {
-            isolation::isolation_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*isolation::isolation_objects_map
         }
         340 => {
-            valhalla::valhalla_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*valhalla::valhalla_objects_map
         }
         350 => {
-            epitaph::epitaph_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*epitaph::epitaph_objects_map
         }
         360 => {
-            snowbound::snowbound_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*snowbound::snowbound_objects_map
         }
         380 => {
-            narrows::narrows_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*narrows::narrows_objects_map
         }
         390 => {
-            the_pit::the_pit_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*the_pit::the_pit_objects_map
         }
         400 => {
-            sandtrap::sandtrap_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*sandtrap::sandtrap_objects_map
         }
         410 => {
-            standoff::standoff_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*standoff::standoff_objects_map
         }
         440 => {
-            longshore::longshore_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*longshore::longshore_objects_map
         }
         470 => {
-            avalanche::avalanche_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*avalanche::avalanche_objects_map
         }
         480 => {
-            foundry::foundry_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*foundry::foundry_objects_map
         }
         490 => {
-            assembly::assembly_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*assembly::assembly_objects_map
         }
         500 => {
-            orbital::orbital_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*orbital::orbital_objects_map
         }
         520 => {
-            blackout::blackout_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*blackout::blackout_objects_map
         }
         580 => {
-            rats_nest::rats_nest_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*rats_nest::rats_nest_objects_map
         }
         590 => {
-            ghost_town::ghost_town_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*ghost_town::ghost_town_objects_map
         }
         600 => {
-            cold_storage::cold_storage_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*cold_storage::cold_storage_objects_map
         }
         720 => {
-            heretic::heretic_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*heretic::heretic_objects_map
         }
         730 => {
-            sandbox::sandbox_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*sandbox::sandbox_objects_map
         },
         740 => {
-            citadel::citadel_objects_map.get_by_right(&mcc_object_index).cloned()
+            &*citadel::citadel_objects_map
         },
         _ => {
             panic!("No MCC object mapping for Map {}", map_id);
         }
     }
-}
\ No newline at end of file
+}
+
+pub fn get_h3_index_for_mcc_object(map_id: u32, mcc_object_index: u32) -> Option<u32> {
+    get_objects_map(map_id).get_by_right(&mcc_object_index).cloned()
+}
+
+pub fn get_mcc_index_for_h3_object(map_id: u32, h3_object_index: u32) -> Option<u32> {
+    get_objects_map(map_id).get_by_left(&h3_object_index).cloned()
+}
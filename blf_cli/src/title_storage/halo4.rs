@@ -0,0 +1 @@
+pub mod v20810_12_09_22_1647_main;
@@ -0,0 +1,422 @@
+use std::error::Error;
+use std::fs::{exists, File};
+use std::io::Read;
+use std::time::SystemTime;
+use colored::Colorize;
+use crate::io::{get_directories_in_folder, get_files_in_folder, read_text_file_lines, write_text_file_lines};
+use crate::{build_path, title_converter, やった};
+use crate::title_storage::TitleConverter;
+use blf_lib::blam::halo3::v12070_08_09_05_2031_halo3_ship::cseries::language::{get_language_string, k_language_suffix_chinese_traditional, k_language_suffix_english, k_language_suffix_french, k_language_suffix_german, k_language_suffix_italian, k_language_suffix_japanese, k_language_suffix_korean, k_language_suffix_mexican, k_language_suffix_portuguese, k_language_suffix_spanish};
+use blf_lib::blf::{get_blf_file_hash, BlfFileBuilder};
+use blf_lib::blf::chunks::find_chunk_in_file;
+use crate::console::console_task;
+use blf_lib::blf::versions::halo4::v20810_12_09_22_1647_main::{s_blf_chunk_author, s_blf_chunk_banhammer_messages, s_blf_chunk_end_of_file, s_blf_chunk_map_manifest, s_blf_chunk_matchmaking_tips, s_blf_chunk_online_file_manifest, s_blf_chunk_start_of_file};
+use blf_lib::result::{BLFLibError, BLFLibResult};
+
+title_converter! (
+    #[Title("Halo 4")]
+    #[Build("20810.12.09.22.1647.main")]
+    pub struct v20810_12_09_22_1647_main {}
+);
+
+// Only files whose chunks share Halo 3's layouts are converted. Hoppers, game sets, variants
+// and network configuration are skipped until Halo 4's own chunk layouts are mapped.
+
+pub const k_language_suffixes: [&str; 10] = [
+    k_language_suffix_english,
+    k_language_suffix_japanese,
+    k_language_suffix_german,
+    k_language_suffix_french,
+    k_language_suffix_spanish,
+    k_language_suffix_mexican,
+    k_language_suffix_italian,
+    k_language_suffix_korean,
+    k_language_suffix_chinese_traditional,
+    // k_language_suffix_chinese_simplified,
+    k_language_suffix_portuguese,
+    // k_language_suffix_polish,
+];
+
+mod title_storage_output {
+    use blf_lib::blf::chunks::BlfChunk;
+    use blf_lib::blf::versions::halo4::v20810_12_09_22_1647_main::s_blf_chunk_online_file_manifest;
+    use crate::build_path;
+
+    // applies to the root folder, eg "default_hoppers"
+    pub const hopper_directory_name_max_length: usize = 64;
+
+    // Root
+    pub fn manifest_file_name() -> String {
+        format!("manifest_{:0>3}.bin", s_blf_chunk_online_file_manifest::get_version().major)
+    }
+
+    pub fn manifest_file_path(hoppers_path: &String) -> String {
+        build_path!(
+            hoppers_path,
+            manifest_file_name()
+        )
+    }
+
+    // Languages
+    // eg. default_hoppers/en/
+    pub const rsa_manifest_file_name: &str = "rsa_manifest.bin";
+    pub fn rsa_manifest_file_path(hoppers_path: &String, language_code: &str) -> String {
+        build_path!(
+            hoppers_path,
+            language_code,
+            rsa_manifest_file_name
+        )
+    }
+    pub const banhammer_messages_file_name: &str = "matchmaking_banhammer_messages.bin";
+    pub fn banhammer_messages_file_path(hoppers_path: &String, language_code: &str) -> String {
+        build_path!(
+            hoppers_path,
+            language_code,
+            banhammer_messages_file_name
+        )
+    }
+
+    pub const matchmaking_tips_file_name: &str = "matchmaking_tips.bin";
+    pub fn matchmaking_tips_file_path(hoppers_path: &String, language_code: &str) -> String {
+        build_path!(
+            hoppers_path,
+            language_code,
+            matchmaking_tips_file_name
+        )
+    }
+}
+
+mod title_storage_config {
+    use crate::build_path;
+
+    pub const banhammer_messages_folder_name: &str = "banhammer_messages";
+    pub fn banhammer_messages_file_path(config_folder: &String, language_code: &str) -> String {
+        build_path!(
+            config_folder,
+            banhammer_messages_folder_name,
+            format!("{language_code}.txt")
+        )
+    }
+
+    pub const rsa_signatures_folder_name: &str = "rsa_signatures";
+    pub fn rsa_signatures_folder_path(config_folder: &String, language_code: &str) -> String {
+        build_path!(
+            config_folder,
+            rsa_signatures_folder_name,
+            language_code
+        )
+    }
+
+    pub const matchmaking_tips_folder_name: &str = "matchmaking_tips";
+    pub fn matchmaking_tips_file_path(config_folder: &String, language_code: &str) -> String {
+        build_path!(
+            config_folder,
+            matchmaking_tips_folder_name,
+            format!("{language_code}.txt")
+        )
+    }
+}
+
+impl TitleConverter for v20810_12_09_22_1647_main {
+    fn build_blfs(&mut self, config_path: &String, blfs_path: &String) {
+        let start_time = SystemTime::now();
+
+        println!("{}", format!("Writing Title Storage BLFs to {blfs_path}").bold());
+
+        let hopper_directories = get_directories_in_folder(config_path).unwrap_or_else(|err|{
+            println!("{}", err);
+            panic!()
+        });
+
+        for hopper_directory in hopper_directories {
+            let result = || -> Result<(), Box<dyn Error>> {
+                if hopper_directory.len() > title_storage_output::hopper_directory_name_max_length {
+                    return Err(Box::from(format!(
+                        "Hoppers folder \"{hopper_directory}\" is too long and will be skipped. ({} > {} characters)",
+                        hopper_directory.len(),
+                        title_storage_output::hopper_directory_name_max_length
+                    )))
+                }
+
+                let hopper_config_path = build_path!(
+                    config_path,
+                    &hopper_directory
+                );
+
+                let hopper_blfs_path = build_path!(
+                    blfs_path,
+                    &hopper_directory
+                );
+
+                println!("{} {}...", "Converting".bold(), hopper_directory.bold().bright_white());
+
+                Self::build_blf_banhammer_messages(&hopper_config_path, &hopper_blfs_path)?;
+                Self::build_blf_matchmaking_tips(&hopper_config_path, &hopper_blfs_path)?;
+                Self::build_blf_map_manifests(&hopper_config_path, &hopper_blfs_path)?;
+                Self::build_blf_manifest(&hopper_blfs_path)?;
+
+                Ok(())
+            }();
+
+            if result.is_err() {
+                println!("{}", "Failed to build title storage for hoppers".bright_white().on_red());
+                println!("{}", result.err().unwrap().to_string().on_red());
+            }
+        }
+
+        let seconds = start_time.elapsed().unwrap().as_secs_f32();
+        println!("Finished conversion in {seconds:.2} seconds.");
+    }
+
+    fn build_config(&mut self, blfs_path: &String, config_path: &String) {
+        println!("{} {}", "Writing Title Storage config to ".bold(), config_path.bold());
+
+        let hopper_directories = get_directories_in_folder(blfs_path).unwrap_or_else(|err|{
+            println!("{}", err);
+            panic!();
+        });
+
+        for hopper_directory in hopper_directories {
+            let result = || -> Result<(), Box<dyn Error>> {
+                let hoppers_config_path = build_path!(
+                    config_path,
+                    &hopper_directory
+                );
+
+                let hoppers_blf_path = build_path!(
+                    blfs_path,
+                    &hopper_directory
+                );
+
+                println!("{} {}...", "Converting".bold(), hopper_directory.bold().bright_white());
+                Self::build_config_banhammer_messages(&hoppers_blf_path, &hoppers_config_path)?;
+                Self::build_config_matchmaking_tips(&hoppers_blf_path, &hoppers_config_path)?;
+                Ok(())
+            }();
+
+            if result.is_err() {
+                println!("{}", "Failed to build title storage config for hoppers".bright_white().on_red());
+                println!("{}", result.err().unwrap().to_string().on_red());
+            }
+        }
+    }
+}
+
+impl v20810_12_09_22_1647_main {
+    fn build_config_banhammer_messages(hoppers_blf_path: &String, hoppers_config_path: &String) -> Result<(), Box<dyn Error>> {
+        let mut task = console_task::start("Converting Banhammer Messages");
+
+        for language_code in k_language_suffixes {
+            let blf_file_path = title_storage_output::banhammer_messages_file_path(
+                hoppers_blf_path,
+                language_code
+            );
+
+            if !exists(&blf_file_path)? {
+                task.add_warning(format!(
+                    "No {} banhammer messages are present.",
+                    get_language_string(language_code),
+                ));
+
+                continue;
+            }
+
+            let bhms = find_chunk_in_file::<s_blf_chunk_banhammer_messages>(blf_file_path)?;
+            write_text_file_lines(
+                title_storage_config::banhammer_messages_file_path(
+                    hoppers_config_path,
+                    language_code
+                ),
+                &bhms.get_messages()?
+            )?;
+        }
+
+        やった!(task)
+    }
+
+    fn build_config_matchmaking_tips(hoppers_blf_path: &String, hoppers_config_path: &String) -> BLFLibResult {
+        let mut task = console_task::start("Converting Matchmaking Tips");
+
+        for language_code in k_language_suffixes {
+            let blf_file_path = title_storage_output::matchmaking_tips_file_path(
+                hoppers_blf_path,
+                language_code
+            );
+
+            if !exists(&blf_file_path)? {
+                task.add_warning(format!(
+                    "No {} matchmaking tips are present.",
+                    get_language_string(language_code),
+                ));
+
+                continue;
+            }
+
+            let mmtp = find_chunk_in_file::<s_blf_chunk_matchmaking_tips>(blf_file_path)?;
+
+            write_text_file_lines(
+                title_storage_config::matchmaking_tips_file_path(
+                    hoppers_config_path,
+                    language_code
+                ),
+                &mmtp.tips.iter().map(|tip|tip.get_string()).collect::<Result<Vec<String>, BLFLibError>>()?
+            )?
+        }
+
+        やった!(task)
+    }
+
+    fn build_blf_banhammer_messages(hoppers_config_folder: &String, hoppers_blf_folder: &String) -> BLFLibResult {
+        let mut task = console_task::start("Building Banhammer Messages");
+
+        for language_code in k_language_suffixes {
+            let config_path = title_storage_config::banhammer_messages_file_path(hoppers_config_folder, language_code);
+
+            if !exists(&config_path)? {
+                task.add_warning(format!("{} banhammer messages are missing.", get_language_string(language_code)));
+                continue;
+            }
+
+            let matchmaking_banhammer_messages = read_text_file_lines(
+                config_path,
+            )?;
+
+            let bhms = s_blf_chunk_banhammer_messages::create(matchmaking_banhammer_messages)?;
+
+            BlfFileBuilder::new()
+                .add_chunk(s_blf_chunk_start_of_file::default())
+                .add_chunk(s_blf_chunk_author::for_build::<v20810_12_09_22_1647_main>())
+                .add_chunk(bhms)
+                .add_chunk(s_blf_chunk_end_of_file::default())
+                .write_file(title_storage_output::banhammer_messages_file_path(hoppers_blf_folder, language_code))?;
+        }
+
+        やった!(task)
+    }
+
+    fn build_blf_matchmaking_tips(hoppers_config_folder: &String, hoppers_blf_folder: &String) -> BLFLibResult {
+        let mut task = console_task::start("Building Matchmaking Tips");
+
+        for language_code in k_language_suffixes {
+            let config_path = title_storage_config::matchmaking_tips_file_path(hoppers_config_folder, language_code);
+
+            if !exists(&config_path)? {
+                task.add_warning(format!("{} matchmaking tips are missing.", get_language_string(language_code)));
+                continue;
+            }
+
+            let matchmaking_tips = read_text_file_lines(config_path)?;
+
+            BlfFileBuilder::new()
+                .add_chunk(s_blf_chunk_start_of_file::default())
+                .add_chunk(s_blf_chunk_author::for_build::<v20810_12_09_22_1647_main>())
+                .add_chunk(s_blf_chunk_matchmaking_tips::create(matchmaking_tips)?)
+                .add_chunk(s_blf_chunk_end_of_file::default())
+                .write_file(title_storage_output::matchmaking_tips_file_path(
+                    hoppers_blf_folder,
+                    language_code
+                ))?
+        }
+
+        やった!(task)
+    }
+
+    fn build_blf_map_manifests(hoppers_config_path: &String, hoppers_blf_path: &String) -> Result<(), Box<dyn Error>>
+    {
+        let mut task = console_task::start("Building Map Manifests");
+
+        for language_code in k_language_suffixes {
+            let rsa_folder = title_storage_config::rsa_signatures_folder_path(
+                hoppers_config_path,
+                language_code,
+            );
+
+            let mut rsa_files = Vec::<String>::new();
+
+            if exists(&rsa_folder)? {
+                rsa_files = get_files_in_folder(&rsa_folder)?;
+            }
+
+            if rsa_files.is_empty() {
+                task.add_error(format!("No {} RSA signatures were found", get_language_string(language_code)))
+            }
+
+            let mut map_manifest = s_blf_chunk_map_manifest::default();
+
+            for rsa_file_name in rsa_files {
+                let rsa_file_path = build_path!(&rsa_folder, &rsa_file_name);
+                let mut rsa_file = File::open(&rsa_file_path)?;
+                let mut rsa_signature = Vec::<u8>::with_capacity(0x100);
+                rsa_file.read_to_end(&mut rsa_signature)?;
+
+                map_manifest.add_rsa_signature(rsa_signature.as_slice())?;
+            }
+
+            BlfFileBuilder::new()
+                .add_chunk(s_blf_chunk_start_of_file::new("rsa manifest"))
+                .add_chunk(s_blf_chunk_author::for_build::<v20810_12_09_22_1647_main>())
+                .add_chunk(map_manifest)
+                .add_chunk(s_blf_chunk_end_of_file::default())
+                .write_file(title_storage_output::rsa_manifest_file_path(
+                    hoppers_blf_path,
+                    language_code
+                ))?;
+        }
+
+        やった!(task)
+    }
+
+    fn build_blf_manifest(
+        hoppers_blfs_path: &String,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut task = console_task::start("Building Manifest File");
+
+        let mut manifest_chunk = s_blf_chunk_online_file_manifest::default();
+
+        let mut add_hash_if_file_exists = |manifest_path: String, file_path: String| -> BLFLibResult {
+            if exists(&file_path)? {
+                manifest_chunk.add_file_hash(
+                    manifest_path,
+                    get_blf_file_hash(file_path)?,
+                )?;
+            }
+            Ok(())
+        };
+
+        for language_code in k_language_suffixes {
+            add_hash_if_file_exists(
+                format!(
+                    "/{language_code}/{}",
+                    title_storage_output::rsa_manifest_file_name
+                ),
+                title_storage_output::rsa_manifest_file_path(hoppers_blfs_path, language_code)
+            )?;
+
+            add_hash_if_file_exists(
+                format!(
+                    "/{language_code}/{}",
+                    title_storage_output::banhammer_messages_file_name
+                ),
+                title_storage_output::banhammer_messages_file_path(hoppers_blfs_path, language_code)
+            )?;
+
+            add_hash_if_file_exists(
+                format!(
+                    "/{language_code}/{}",
+                    title_storage_output::matchmaking_tips_file_name
+                ),
+                title_storage_output::matchmaking_tips_file_path(hoppers_blfs_path, language_code)
+            )?;
+        }
+
+        BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::default())
+            .add_chunk(manifest_chunk)
+            .add_chunk(s_blf_chunk_end_of_file::default())
+            .write_file(title_storage_output::manifest_file_path(
+                hoppers_blfs_path,
+            ))?;
+
+        やった!(task)
+    }
+}
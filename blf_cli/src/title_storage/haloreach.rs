@@ -1,4 +1,6 @@
 pub mod variant_io;
+pub mod map_checksums;
+pub mod config_validation;
 pub mod v12065_11_08_24_1738_tu1actual;
 pub mod v09730_10_04_09_1309_omaha_delta;
 pub mod v09449_10_03_25_1545_omaha_beta;
@@ -0,0 +1,290 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs::exists;
+use colored::Colorize;
+use serde::de::DeserializeOwned;
+use blf_lib::blam::common::cache::map_checksums::c_map_checksums;
+use blf_lib::blam::halo3::v12070_08_09_05_2031_halo3_ship::cseries::language::get_language_string;
+use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_banhammer_messages, s_blf_chunk_matchmaking_tips};
+use blf_lib::io::read_json_file;
+use blf_lib::result::BLFLibResult;
+use crate::build_path;
+use crate::console::console_task;
+use crate::io::{get_directories_in_folder, read_text_file_lines};
+use crate::title_storage::haloreach::map_checksums::{apply_map_variant_checksums, read_rsa_signature_checksums};
+use crate::title_storage::validate_jpeg;
+
+/// Every Reach build shares the same config layout, only the chunk types, limits and a few file names differ.
+/// Implemented by each Reach converter so that one validator can check all of them.
+pub trait ReachTitleStorageConfig {
+    type matchmaking_hopper: DeserializeOwned;
+    type matchmaking_hopper_categories: DeserializeOwned;
+    type game_set: DeserializeOwned;
+    type game_variant: DeserializeOwned;
+    type map_variant: DeserializeOwned;
+
+    const language_suffixes: &'static [&'static str];
+    const hopper_directory_name_max_length: usize;
+    const maximum_hopper_count: usize;
+    const maximum_game_set_entries: usize;
+    const hopper_image_width: usize;
+    const hopper_image_height: usize;
+
+    fn banhammer_messages_file_path(hoppers_config_path: &String, language_code: &str) -> String;
+    /// Matchmaking tips are duplicated for CEA in later builds.
+    fn matchmaking_tips_file_paths(hoppers_config_path: &String, language_code: &str) -> Vec<String>;
+    fn rsa_signatures_folder_path(hoppers_config_path: &String) -> String;
+    fn active_hoppers_file_path(hoppers_config_path: &String) -> String;
+    fn matchmaking_hopper_configuration_file_path(hoppers_config_path: &String, hopper_folder: &String) -> String;
+    fn matchmaking_hopper_image_file_path(hoppers_config_path: &String, hopper_folder: &String) -> String;
+    fn matchmaking_hopper_categories_file_path(hoppers_config_path: &String) -> String;
+    fn game_set_file_path(hoppers_config_path: &String, hopper_folder: &String) -> String;
+    fn game_variant_file_path(hoppers_config_path: &String, game_variant_file_name: &String) -> String;
+    fn map_variant_file_path(hoppers_config_path: &String, map_variant_file_name: &String) -> String;
+    fn get_hopper_id_from_hopper_folder_name(hopper_folder: &String) -> BLFLibResult<u16>;
+
+    fn hopper_identifier(hopper: &Self::matchmaking_hopper) -> u16;
+    /// Returns the map and game variant file names of each entry, either can be empty.
+    fn game_set_entries(game_set: &Self::game_set) -> BLFLibResult<Vec<(String, String)>>;
+    /// Returns the map id, scenario crc and palette crc of a map variant.
+    fn map_variant_checksums(map_variant: &Self::map_variant) -> (u32, u32, u32);
+}
+
+/// Checks a Reach config folder for problems which would fail or break a build, without writing any BLFs.
+/// Every problem is listed, returns whether the config is valid.
+pub fn validate_config<T: ReachTitleStorageConfig>(config_path: &String) -> bool {
+    println!("{}", format!("Validating Title Storage config in {config_path}").bold());
+
+    let hopper_directories = get_directories_in_folder(config_path).unwrap_or_else(|err|{
+        println!("{}", err);
+        panic!()
+    });
+
+    let mut valid = true;
+
+    for hopper_directory in hopper_directories {
+        println!("{} {}...", "Validating".bold(), hopper_directory.bold().bright_white());
+
+        let hoppers_config_path = build_path!(
+            config_path,
+            &hopper_directory
+        );
+
+        let result = || -> BLFLibResult<bool> {
+            let mut hoppers_valid = true;
+
+            if hopper_directory.len() > T::hopper_directory_name_max_length {
+                println!("{}", format!(
+                    "Hoppers folder \"{hopper_directory}\" is too long and will be skipped when building. ({} > {} characters)",
+                    hopper_directory.len(),
+                    T::hopper_directory_name_max_length
+                ).on_red());
+                hoppers_valid = false;
+            }
+
+            let mut map_variants = BTreeSet::<String>::new();
+            let mut game_variants = BTreeSet::<String>::new();
+
+            hoppers_valid &= validate_config_banhammer_messages_and_matchmaking_tips::<T>(&hoppers_config_path)?;
+            hoppers_valid &= validate_config_hoppers::<T>(&hoppers_config_path, &mut map_variants, &mut game_variants)?;
+            hoppers_valid &= validate_config_map_variants::<T>(&hoppers_config_path, &map_variants)?;
+            hoppers_valid &= validate_config_game_variants::<T>(&hoppers_config_path, &game_variants)?;
+
+            Ok(hoppers_valid)
+        }();
+
+        match result {
+            Ok(hoppers_valid) => valid &= hoppers_valid,
+            Err(error) => {
+                println!("{}", "Failed to validate title storage for hoppers".bright_white().on_red());
+                println!("{}", error.to_string().on_red());
+                valid = false;
+            }
+        }
+    }
+
+    valid
+}
+
+fn validate_config_banhammer_messages_and_matchmaking_tips<T: ReachTitleStorageConfig>(hoppers_config_path: &String) -> BLFLibResult<bool> {
+    let mut task = console_task::start("Validating Banhammer Messages and Matchmaking Tips");
+
+    for &language_code in T::language_suffixes {
+        let banhammer_messages_path = T::banhammer_messages_file_path(hoppers_config_path, language_code);
+        if exists(&banhammer_messages_path)? {
+            if let Err(error) = s_blf_chunk_banhammer_messages::create(read_text_file_lines(banhammer_messages_path)?) {
+                task.add_error(format!("{} banhammer messages are invalid: {error}", get_language_string(language_code)));
+            }
+        }
+
+        for matchmaking_tips_path in T::matchmaking_tips_file_paths(hoppers_config_path, language_code) {
+            if exists(&matchmaking_tips_path)? {
+                if let Err(error) = s_blf_chunk_matchmaking_tips::create(read_text_file_lines(matchmaking_tips_path.clone())?) {
+                    task.add_error(format!("{} matchmaking tips in {matchmaking_tips_path} are invalid: {error}", get_language_string(language_code)));
+                }
+            }
+        }
+    }
+
+    let valid = !task.has_errors();
+    task.complete();
+    Ok(valid)
+}
+
+fn validate_config_hoppers<T: ReachTitleStorageConfig>(
+    hoppers_config_path: &String,
+    map_variants: &mut BTreeSet<String>,
+    game_variants: &mut BTreeSet<String>,
+) -> BLFLibResult<bool> {
+    let mut task = console_task::start("Validating Hoppers");
+
+    let active_hopper_folders = read_text_file_lines(T::active_hoppers_file_path(hoppers_config_path))?;
+
+    if active_hopper_folders.len() > T::maximum_hopper_count {
+        task.add_error(format!(
+            "There are too many active hoppers. ({} > {})",
+            active_hopper_folders.len(),
+            T::maximum_hopper_count
+        ));
+    }
+
+    let mut hopper_folders_by_id = HashMap::<u16, &String>::new();
+
+    for hopper_folder in &active_hopper_folders {
+        let hopper_id = match T::get_hopper_id_from_hopper_folder_name(hopper_folder) {
+            Ok(hopper_id) => hopper_id,
+            Err(error) => {
+                task.add_error(error.to_string());
+                continue;
+            }
+        };
+
+        if let Some(other_hopper_folder) = hopper_folders_by_id.insert(hopper_id, hopper_folder) {
+            task.add_error(format!("Hoppers {other_hopper_folder} and {hopper_folder} share the identifier {hopper_id}."));
+        }
+
+        let configuration_path = T::matchmaking_hopper_configuration_file_path(hoppers_config_path, hopper_folder);
+        if !exists(&configuration_path)? {
+            task.add_error(format!("Couldn't find a configuration file for hopper {hopper_folder}!"));
+        }
+        else {
+            match read_json_file::<T::matchmaking_hopper>(&configuration_path) {
+                Err(error) => task.add_error(format!("Hopper {hopper_folder} has an invalid configuration: {error}")),
+                Ok(hopper) if T::hopper_identifier(&hopper) != hopper_id => task.add_error(format!(
+                    "Hopper {hopper_folder} is configured with the identifier {}, which doesn't match its folder name.",
+                    T::hopper_identifier(&hopper)
+                )),
+                _ => {}
+            }
+        }
+
+        let hopper_image_path = T::matchmaking_hopper_image_file_path(hoppers_config_path, hopper_folder);
+        if !exists(&hopper_image_path)? {
+            task.add_warning(format!("No image was found for hopper {hopper_folder}"));
+        }
+        else if let Err(error) = validate_jpeg(&hopper_image_path, T::hopper_image_width, T::hopper_image_height, None) {
+            task.add_warning(format!("Hopper {hopper_folder} has an invalid image and it will be skipped: {error}"));
+        }
+
+        let game_set_path = T::game_set_file_path(hoppers_config_path, hopper_folder);
+        if !exists(&game_set_path)? {
+            task.add_error(format!("No game set was found for hopper {hopper_folder}"));
+            continue;
+        }
+
+        let game_set_entries = read_json_file::<T::game_set>(&game_set_path).and_then(|game_set| T::game_set_entries(&game_set));
+        let game_set_entries = match game_set_entries {
+            Ok(game_set_entries) => game_set_entries,
+            Err(error) => {
+                task.add_error(format!("Hopper {hopper_folder} has an invalid game set: {error}"));
+                continue;
+            }
+        };
+
+        if game_set_entries.len() > T::maximum_game_set_entries {
+            task.add_error(format!(
+                "Hopper {hopper_folder} has too many game set entries. ({} > {})",
+                game_set_entries.len(),
+                T::maximum_game_set_entries
+            ));
+        }
+
+        for (map_variant_file_name, game_variant_file_name) in game_set_entries {
+            if !map_variant_file_name.is_empty() {
+                if exists(T::map_variant_file_path(hoppers_config_path, &map_variant_file_name))? {
+                    map_variants.insert(map_variant_file_name);
+                }
+                else {
+                    task.add_error(format!("Hopper {hopper_folder} uses map variant \"{map_variant_file_name}\" which could not be found."));
+                }
+            }
+
+            if !game_variant_file_name.is_empty() {
+                if exists(T::game_variant_file_path(hoppers_config_path, &game_variant_file_name))? {
+                    game_variants.insert(game_variant_file_name);
+                }
+                else {
+                    task.add_error(format!("Hopper {hopper_folder} uses game variant \"{game_variant_file_name}\" which could not be found."));
+                }
+            }
+        }
+    }
+
+    if let Err(error) = read_json_file::<T::matchmaking_hopper_categories>(
+        T::matchmaking_hopper_categories_file_path(hoppers_config_path)
+    ) {
+        task.add_error(format!("Hopper categories are invalid: {error}"));
+    }
+
+    let valid = !task.has_errors();
+    task.complete();
+    Ok(valid)
+}
+
+fn validate_config_map_variants<T: ReachTitleStorageConfig>(hoppers_config_path: &String, map_variants: &BTreeSet<String>) -> BLFLibResult<bool> {
+    let mut task = console_task::start("Validating Map Variants");
+
+    let mut map_checksums = c_map_checksums::default();
+    read_rsa_signature_checksums(&T::rsa_signatures_folder_path(hoppers_config_path), &mut map_checksums)?;
+
+    let mut map_variant_checksums = Vec::<(&String, (u32, u32, u32))>::new();
+    for map_variant_file_name in map_variants {
+        match read_json_file::<T::map_variant>(T::map_variant_file_path(hoppers_config_path, map_variant_file_name)) {
+            Ok(map_variant) => {
+                let checksums = T::map_variant_checksums(&map_variant);
+                map_checksums.add_palette_crc(checksums.0, checksums.2);
+                map_variant_checksums.push((map_variant_file_name, checksums));
+            }
+            Err(error) => task.add_error(format!("Map variant \"{map_variant_file_name}\" is invalid: {error}")),
+        }
+    }
+
+    for (map_variant_file_name, (map_id, mut scenario_crc, palette_crc)) in map_variant_checksums {
+        // A missing signature fails the variant, anything else is corrected or reported when building.
+        if map_checksums.get_scenario_crc(map_id).is_none() {
+            task.add_error(format!("Map variant \"{map_variant_file_name}\" is on map {map_id}, which has no RSA signature."));
+            continue;
+        }
+
+        for warning in apply_map_variant_checksums(map_variant_file_name, &map_checksums, map_id, &mut scenario_crc, palette_crc) {
+            task.add_warning(warning);
+        }
+    }
+
+    let valid = !task.has_errors();
+    task.complete();
+    Ok(valid)
+}
+
+fn validate_config_game_variants<T: ReachTitleStorageConfig>(hoppers_config_path: &String, game_variants: &BTreeSet<String>) -> BLFLibResult<bool> {
+    let mut task = console_task::start("Validating Game Variants");
+
+    for game_variant_file_name in game_variants {
+        if let Err(error) = read_json_file::<T::game_variant>(T::game_variant_file_path(hoppers_config_path, game_variant_file_name)) {
+            task.add_error(format!("Game variant \"{game_variant_file_name}\" is invalid: {error}"));
+        }
+    }
+
+    let valid = !task.has_errors();
+    task.complete();
+    Ok(valid)
+}
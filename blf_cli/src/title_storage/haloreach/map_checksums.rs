@@ -0,0 +1,65 @@
+use std::fs;
+use std::fs::exists;
+use std::str::FromStr;
+use lazy_static::lazy_static;
+use regex::Regex;
+use blf_lib::blam::common::cache::map_checksums::c_map_checksums;
+use blf_lib::result::BLFLibResult;
+use crate::build_path;
+use crate::io::get_files_in_folder;
+
+lazy_static! {
+    static ref rsa_signature_file_map_id_regex: Regex = Regex::new(r"^[0-9]{1,}").unwrap();
+}
+
+/// Adds the scenario crc of every RSA signature in the folder, signatures are named "{map_id}_{map_name}".
+pub fn read_rsa_signature_checksums(rsa_folder: &String, map_checksums: &mut c_map_checksums) -> BLFLibResult {
+    if !exists(rsa_folder)? {
+        return Ok(());
+    }
+
+    for rsa_file_name in get_files_in_folder(rsa_folder)? {
+        let Some(map_id) = rsa_signature_file_map_id_regex.find(&rsa_file_name) else {
+            continue;
+        };
+
+        let rsa_signature = fs::read(build_path!(rsa_folder, &rsa_file_name))?;
+        map_checksums.add_rsa_signature(u32::from_str(map_id.as_str())?, &rsa_signature);
+    }
+
+    Ok(())
+}
+
+/// Fills in a map variant's scenario crc, and checks its palette crc against other variants of the same map.
+/// Palette crcs are only reported, they can't be computed without the scenario's tags so they're never changed.
+/// Returns an error message for each crc which couldn't be checked, had to be corrected or looks wrong.
+pub fn apply_map_variant_checksums(
+    map_variant_file_name: &String,
+    map_checksums: &c_map_checksums,
+    map_id: u32,
+    scenario_crc: &mut u32,
+    palette_crc: u32,
+) -> Vec<String> {
+    let mut errors = Vec::<String>::new();
+
+    match map_checksums.get_scenario_crc(map_id) {
+        None => errors.push(format!("Map Variant \"{map_variant_file_name}\" could not be validated due to missing RSA signature!")),
+        Some(expected_scenario_crc) if expected_scenario_crc != *scenario_crc => {
+            if *scenario_crc != 0 {
+                errors.push(format!("Map Variant \"{map_variant_file_name}\" has a bad checksum and may not load properly! (got {:08X}, expected {:08X})", scenario_crc, expected_scenario_crc));
+            }
+            *scenario_crc = expected_scenario_crc;
+        }
+        _ => {}
+    }
+
+    match map_checksums.get_palette_crc(map_id) {
+        _ if palette_crc == 0 => errors.push(format!("Map Variant \"{map_variant_file_name}\" has no palette crc and may not load properly!")),
+        Some(common_palette_crc) if common_palette_crc != palette_crc => {
+            errors.push(format!("Map Variant \"{map_variant_file_name}\" has a different palette crc to most variants of map {map_id} and may not load properly! (got {:08X}, most variants have {:08X})", palette_crc, common_palette_crc));
+        }
+        _ => {}
+    }
+
+    errors
+}
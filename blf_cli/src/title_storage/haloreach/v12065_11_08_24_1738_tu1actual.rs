@@ -8,11 +8,15 @@ use std::fs::{create_dir_all, exists, remove_file, File};
 use std::io::Read;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::SystemTime;
 use colored::Colorize;
 use crate::io::{create_parent_folders, get_directories_in_folder, get_files_in_folder, read_text_file_lines, write_text_file_lines};
 use crate::{build_path, debug_log, title_converter, やった};
 use crate::title_storage::{validate_jpeg, TitleConverter};
+use crate::title_storage::build_cache::c_build_cache;
+use crate::title_storage::haloreach::config_validation::{validate_config, ReachTitleStorageConfig};
+use crate::title_storage::haloreach::map_checksums::{apply_map_variant_checksums, read_rsa_signature_checksums};
 use lazy_static::lazy_static;
 use blf_lib::blam::halo3::v12070_08_09_05_2031_halo3_ship::cseries::language::{get_language_string, k_language_suffix_chinese_traditional, k_language_suffix_english, k_language_suffix_french, k_language_suffix_german, k_language_suffix_italian, k_language_suffix_japanese, k_language_suffix_korean, k_language_suffix_mexican, k_language_suffix_portuguese, k_language_suffix_spanish};
 use blf_lib::blf::{get_blf_file_hash, BlfFile, BlfFileBuilder};
@@ -23,10 +27,11 @@ use tempdir::TempDir;
 use tokio::runtime;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
+use blf_lib::blam::common::cache::map_checksums::c_map_checksums;
 use blf_lib::blam::common::memory::secure_signature::s_network_http_request_hash;
 use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_variant::c_game_variant;
 use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::saved_games::scenario_map_variant::c_map_variant;
-use blf_lib::blf::versions::haloreach::v12065_11_08_24_1738_tu1actual::{s_blf_chunk_author, s_blf_chunk_banhammer_messages, s_blf_chunk_end_of_file, s_blf_chunk_game_set, s_blf_chunk_hopper_configuration_table, s_blf_chunk_hopper_description_table, s_blf_chunk_map_manifest, s_blf_chunk_map_variant, s_blf_chunk_matchmaking_game_variant, s_blf_chunk_matchmaking_tips, s_blf_chunk_megalo_categories, s_blf_chunk_nag_message, s_blf_chunk_network_configuration, s_blf_chunk_online_file_manifest, s_blf_chunk_predefined_queries, s_blf_chunk_start_of_file};
+use blf_lib::blf::versions::haloreach::v12065_11_08_24_1738_tu1actual::{k_hopper_maximum_hopper_count, k_maximum_game_entries, s_blf_chunk_author, s_blf_chunk_banhammer_messages, s_blf_chunk_end_of_file, s_blf_chunk_game_set, s_blf_chunk_hopper_configuration_table, s_blf_chunk_hopper_description_table, s_blf_chunk_map_manifest, s_blf_chunk_map_variant, s_blf_chunk_matchmaking_game_variant, s_blf_chunk_matchmaking_tips, s_blf_chunk_megalo_categories, s_blf_chunk_nag_message, s_blf_chunk_network_configuration, s_blf_chunk_online_file_manifest, s_blf_chunk_predefined_queries, s_blf_chunk_start_of_file};
 use blf_lib::blf::versions::haloreach::v12065_11_08_24_1738_tu1actual::s_blf_chunk_dlc_map_manifest;
 use blf_lib::io::{read_json_file, write_json_file};
 use blf_lib::OPTION_TO_RESULT;
@@ -624,6 +629,86 @@ impl TitleConverter for v12065_11_08_24_1738_tu1actual {
             }
         }
     }
+
+    fn validate_config(&mut self, config_path: &String) -> Option<bool> {
+        Some(validate_config::<Self>(config_path))
+    }
+}
+
+impl ReachTitleStorageConfig for v12065_11_08_24_1738_tu1actual {
+    type matchmaking_hopper = title_storage_config::matchmaking_hopper;
+    type matchmaking_hopper_categories = title_storage_config::matchmaking_hopper_categories;
+    type game_set = s_blf_chunk_game_set;
+    type game_variant = c_game_variant;
+    type map_variant = c_map_variant;
+
+    const language_suffixes: &'static [&'static str] = &k_language_suffixes;
+    const hopper_directory_name_max_length: usize = title_storage_output::hopper_directory_name_max_length;
+    const maximum_hopper_count: usize = k_hopper_maximum_hopper_count;
+    const maximum_game_set_entries: usize = k_maximum_game_entries;
+    const hopper_image_width: usize = hopper_image_width;
+    const hopper_image_height: usize = hopper_image_height;
+
+    fn banhammer_messages_file_path(hoppers_config_path: &String, language_code: &str) -> String {
+        title_storage_config::banhammer_messages_file_path(hoppers_config_path, language_code)
+    }
+
+    fn matchmaking_tips_file_paths(hoppers_config_path: &String, language_code: &str) -> Vec<String> {
+        [false, true].into_iter()
+            .map(|tangerine| title_storage_config::matchmaking_tips_file_path(hoppers_config_path, language_code, tangerine))
+            .collect()
+    }
+
+    fn rsa_signatures_folder_path(hoppers_config_path: &String) -> String {
+        title_storage_config::rsa_signatures_folder_path(hoppers_config_path, k_language_suffix_english)
+    }
+
+    fn active_hoppers_file_path(hoppers_config_path: &String) -> String {
+        title_storage_config::active_hoppers_file_path(hoppers_config_path)
+    }
+
+    fn matchmaking_hopper_configuration_file_path(hoppers_config_path: &String, hopper_folder: &String) -> String {
+        title_storage_config::matchmaking_hopper_configuration_file_path(hoppers_config_path, hopper_folder)
+    }
+
+    fn matchmaking_hopper_image_file_path(hoppers_config_path: &String, hopper_folder: &String) -> String {
+        title_storage_config::matchmaking_hopper_image_file_path(hoppers_config_path, hopper_folder)
+    }
+
+    fn matchmaking_hopper_categories_file_path(hoppers_config_path: &String) -> String {
+        title_storage_config::matchmaking_hopper_categories_file_path(hoppers_config_path)
+    }
+
+    fn game_set_file_path(hoppers_config_path: &String, hopper_folder: &String) -> String {
+        title_storage_config::game_set_file_path(hoppers_config_path, hopper_folder)
+    }
+
+    fn game_variant_file_path(hoppers_config_path: &String, game_variant_file_name: &String) -> String {
+        title_storage_config::game_variant_file_path(hoppers_config_path, game_variant_file_name)
+    }
+
+    fn map_variant_file_path(hoppers_config_path: &String, map_variant_file_name: &String) -> String {
+        title_storage_config::map_variant_file_path(hoppers_config_path, map_variant_file_name)
+    }
+
+    fn get_hopper_id_from_hopper_folder_name(hopper_folder: &String) -> BLFLibResult<u16> {
+        get_hopper_id_from_hopper_folder_name(hopper_folder)
+    }
+
+    fn hopper_identifier(hopper: &Self::matchmaking_hopper) -> u16 {
+        hopper.configuration.identifier
+    }
+
+    fn game_set_entries(game_set: &Self::game_set) -> BLFLibResult<Vec<(String, String)>> {
+        game_set.entries.iter().map(|entry| Ok((
+            entry.map_variant_file.file_name.get_string()?,
+            entry.game_variant_file.file_name.get_string()?,
+        ))).collect()
+    }
+
+    fn map_variant_checksums(map_variant: &Self::map_variant) -> (u32, u32, u32) {
+        (map_variant.m_map_id, map_variant.m_original_map_rsa_signature_hash, map_variant.m_scenario_palette_crc)
+    }
 }
 
 impl v12065_11_08_24_1738_tu1actual {
@@ -1786,6 +1871,8 @@ impl v12065_11_08_24_1738_tu1actual {
             .build()
             .unwrap();
 
+        let build_cache = c_build_cache::open(k_build_string_tu1actual_12065, title_storage_config::game_variants_folder_name).unwrap();
+        let cached_variant_count = Arc::new(AtomicUsize::new(0));
         let task = Arc::new(Mutex::new(task));
         let json_queue = Arc::new(Mutex::new(VecDeque::from(json_queue)));
         let shared_variant_hashes = Arc::new(Mutex::new(HashMap::new()));
@@ -1797,6 +1884,8 @@ impl v12065_11_08_24_1738_tu1actual {
             for n in 0..cpu_cores {
                 let shared_variant_hashes = Arc::clone(&shared_variant_hashes);
                 let game_variants_temp_build_path = game_variants_temp_build_path.clone();
+                let build_cache = build_cache.clone();
+                let cached_variant_count = Arc::clone(&cached_variant_count);
                 let task = Arc::clone(&task);
                 let json_queue = Arc::clone(&json_queue);
 
@@ -1817,15 +1906,20 @@ impl v12065_11_08_24_1738_tu1actual {
                                 )
                             );
 
-                            let game_variant: c_game_variant = serde_json::from_str(&json).unwrap();
+                            let cached = build_cache.build(json.as_bytes(), &game_variant_blf_path, || {
+                                let game_variant: c_game_variant = serde_json::from_str(&json)?;
+
+                                BlfFileBuilder::new()
+                                    .add_chunk(s_blf_chunk_start_of_file::new("game var"))
+                                    .add_chunk(s_blf_chunk_author::for_build::<v12065_11_08_24_1738_tu1actual>())
+                                    .add_chunk(s_blf_chunk_matchmaking_game_variant { game_variant})
+                                    .add_chunk(s_blf_chunk_end_of_file::default())
+                                    .write_file(&game_variant_blf_path)
+                            }).unwrap();
 
-                            BlfFileBuilder::new()
-                                .add_chunk(s_blf_chunk_start_of_file::new("game var"))
-                                .add_chunk(s_blf_chunk_author::for_build::<v12065_11_08_24_1738_tu1actual>())
-                                .add_chunk(s_blf_chunk_matchmaking_game_variant { game_variant})
-                                .add_chunk(s_blf_chunk_end_of_file::default())
-                                .write_file(&game_variant_blf_path)
-                                .unwrap();
+                            if cached {
+                                cached_variant_count.fetch_add(1, Ordering::Relaxed);
+                            }
 
                             let hash = get_blf_file_hash(game_variant_blf_path).unwrap();
                             let mut hashes = shared_variant_hashes.lock().await;
@@ -1845,7 +1939,11 @@ impl v12065_11_08_24_1738_tu1actual {
             variant_hashes.extend(final_hashes.clone());
 
             let mut task = task.lock().await;
-            task.add_message(format!("Built {} variants.", variant_hashes.len()));
+            task.add_message(format!(
+                "Built {} variants, {} were unchanged.",
+                variant_hashes.len(),
+                cached_variant_count.load(Ordering::Relaxed)
+            ));
             task.complete();
         });
 
@@ -1876,7 +1974,13 @@ impl v12065_11_08_24_1738_tu1actual {
         ).collect();
         let map_variants_to_convert: HashSet<String> = HashSet::from_iter(map_variants_to_convert.iter().cloned());
 
-        let mut json_queue: Vec<(String, String)> = Vec::new();
+        let mut map_checksums = c_map_checksums::default();
+        read_rsa_signature_checksums(
+            &title_storage_config::rsa_signatures_folder_path(hoppers_config_path, k_language_suffix_english),
+            &mut map_checksums,
+        )?;
+
+        let mut json_queue: Vec<(String, c_map_variant)> = Vec::new();
         for map_variant in map_variants_to_convert {
             let map_variant_json_path = title_storage_config::map_variant_file_path(
                 hoppers_config_path,
@@ -1891,6 +1995,10 @@ impl v12065_11_08_24_1738_tu1actual {
             let mut file = File::open(&map_variant_json_path).unwrap();
             let mut map_variant_json = String::new();
             file.read_to_string(&mut map_variant_json).unwrap();
+            let map_variant_json: c_map_variant = serde_json::from_str(&map_variant_json)?;
+
+            // Variants of the same map share a palette crc, collect them so that outliers can be reported.
+            map_checksums.add_palette_crc(map_variant_json.m_map_id, map_variant_json.m_scenario_palette_crc);
 
             json_queue.push((map_variant, map_variant_json));
         }
@@ -1900,8 +2008,11 @@ impl v12065_11_08_24_1738_tu1actual {
             .build()
             .unwrap();
 
+        let build_cache = c_build_cache::open(k_build_string_tu1actual_12065, title_storage_config::map_variants_folder_name).unwrap();
+        let cached_variant_count = Arc::new(AtomicUsize::new(0));
         let task = Arc::new(Mutex::new(task));
         let json_queue = Arc::new(Mutex::new(VecDeque::from(json_queue)));
+        let map_checksums = Arc::new(map_checksums);
         let shared_variant_hashes = Arc::new(Mutex::new(HashMap::new()));
 
         let cpu_cores = num_cpus::get();
@@ -1911,14 +2022,17 @@ impl v12065_11_08_24_1738_tu1actual {
             for n in 0..cpu_cores {
                 let shared_variant_hashes = Arc::clone(&shared_variant_hashes);
                 let map_variants_temp_build_path = map_variants_temp_build_path.clone();
+                let build_cache = build_cache.clone();
+                let cached_variant_count = Arc::clone(&cached_variant_count);
                 let task = Arc::clone(&task);
                 let json_queue = Arc::clone(&json_queue);
+                let map_checksums = Arc::clone(&map_checksums);
 
                 thread_handles.push(rt.spawn(async move {
                     loop {
                         let mut json_queue = json_queue.lock().await;
 
-                        if let Some((map_variant_file_name, json)) = json_queue.pop_front() {
+                        if let Some((map_variant_file_name, mut map_variant_json)) = json_queue.pop_front() {
                             let remaining = json_queue.len();
                             drop(json_queue);
 
@@ -1931,31 +2045,37 @@ impl v12065_11_08_24_1738_tu1actual {
                                 )
                             );
 
-                            let mut map_variant_json: c_map_variant = serde_json::from_str(&json).unwrap();
-
-                            // Check the scenario crc
-                            // TODO: Map and pallette crcs.
-                            // let expected_scenario_crc = scenario_crc32s.get(&map_variant_json.m_map_id);
-                            // if expected_scenario_crc.is_none() {
-                            //     let mut task = task.lock().await;
-                            //     task.add_error(format!("Map Variant {map_variant_file_name} could not be validated due to missing RSA signature!"))
-                            // }
-                            // else {
-                            //     let expected_scenario_crc = expected_scenario_crc.unwrap();
-                            //     if expected_scenario_crc != &map_variant_json.m_original_map_rsa_signature_hash {
-                            //         let mut task = task.lock().await;
-                            //         task.add_error(format!("Map Variant \"{map_variant_file_name}\" has a bad checksum and may not load properly! (got {:08X}, expected {:08X})", &map_variant_json.m_original_map_rsa_signature_hash, expected_scenario_crc));
-                            //         map_variant_json.m_original_map_rsa_signature_hash = *expected_scenario_crc;
-                            //     }
-                            // }
-
-                            BlfFileBuilder::new()
-                                .add_chunk(s_blf_chunk_start_of_file::default())
-                                .add_chunk(s_blf_chunk_map_variant {
-                                    map_variant: map_variant_json
-                                })
-                                .add_chunk(s_blf_chunk_end_of_file::default())
-                                .write_file(&map_variant_blf_path).unwrap();
+                            // Check the scenario and palette crcs
+                            let errors = apply_map_variant_checksums(
+                                &map_variant_file_name,
+                                &map_checksums,
+                                map_variant_json.m_map_id,
+                                &mut map_variant_json.m_original_map_rsa_signature_hash,
+                                map_variant_json.m_scenario_palette_crc,
+                            );
+                            if !errors.is_empty() {
+                                let mut task = task.lock().await;
+                                for error in errors {
+                                    task.add_error(error);
+                                }
+                            }
+
+                            // Keyed after the checksums are applied, as they're part of the output.
+                            let cache_key = serde_json::to_vec(&map_variant_json).unwrap();
+
+                            let cached = build_cache.build(&cache_key, &map_variant_blf_path, || {
+                                BlfFileBuilder::new()
+                                    .add_chunk(s_blf_chunk_start_of_file::default())
+                                    .add_chunk(s_blf_chunk_map_variant {
+                                        map_variant: map_variant_json
+                                    })
+                                    .add_chunk(s_blf_chunk_end_of_file::default())
+                                    .write_file(&map_variant_blf_path)
+                            }).unwrap();
+
+                            if cached {
+                                cached_variant_count.fetch_add(1, Ordering::Relaxed);
+                            }
 
                             let hash = get_blf_file_hash(map_variant_blf_path).unwrap();
                             let mut hashes = shared_variant_hashes.lock().await;
@@ -1975,7 +2095,11 @@ impl v12065_11_08_24_1738_tu1actual {
             variant_hashes.extend(final_hashes.clone());
 
             let mut task = task.lock().await;
-            task.add_message(format!("Built {} variants.", variant_hashes.len()));
+            task.add_message(format!(
+                "Built {} variants, {} were unchanged.",
+                variant_hashes.len(),
+                cached_variant_count.load(Ordering::Relaxed)
+            ));
             task.complete();
         });
 
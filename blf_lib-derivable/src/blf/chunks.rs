@@ -41,6 +41,7 @@ pub trait SerializableBlfChunk: DynamicBlfChunk + Any + Send + Sync {
     }
 
     fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
 impl<T: DynamicBlfChunk + BinRead + BinWrite + Clone + Any + BlfChunkHooks + Send + Sync> SerializableBlfChunk for T
@@ -65,6 +66,10 @@ impl<T: DynamicBlfChunk + BinRead + BinWrite + Clone + Any + BlfChunkHooks + Sen
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
 }
 
 pub trait ReadableBlfChunk: BlfChunk + Sized + SerializableBlfChunk + Default {
@@ -90,10 +95,16 @@ pub trait TitleAndBuild {
     fn get_title() -> &'static str;
 
     fn get_build_string() -> &'static str;
+
+    /// The module the build is declared in, eg blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship
+    /// Build strings aren't unique (see "untracked version"), so this is used to identify builds in the chunk registry.
+    fn get_module_path() -> &'static str;
 }
 
 pub trait DynTitleAndBuild {
     fn title(&self) -> String;
 
     fn build_string(&self) -> String;
+
+    fn module_path(&self) -> &'static str;
 }
@@ -1,28 +1,109 @@
 use std::fmt::{Debug, Display};
 use std::num::{ParseIntError, TryFromIntError};
+use crate::types::chunk_signature::chunk_signature;
+use crate::types::chunk_version::chunk_version;
+
+pub enum BLFLibError {
+    /// The requested chunk isn't in the file.
+    ChunkNotFound {
+        signature: chunk_signature,
+        version: Option<chunk_version>,
+    },
+    /// A different chunk was found where one was expected.
+    ChunkMismatch {
+        offset: u64,
+        expected_signature: chunk_signature,
+        expected_version: chunk_version,
+        found_signature: chunk_signature,
+        found_version: chunk_version,
+    },
+    /// An _eof failed validation, eg an invalid size, crc, sha1 or signature.
+    EndOfFileValidation {
+        reason: String,
+    },
+    /// A bitstream read past the end of its data.
+    BitstreamOverflow {
+        field: Option<String>,
+        bit_offset: usize,
+        size_in_bits: usize,
+        remaining_bits: usize,
+    },
+    /// A bitstream write went past the stream's maximum size.
+    BitstreamSizeExceeded {
+        field: Option<String>,
+        bit_offset: usize,
+        size_in_bits: usize,
+        maximum_size_bytes: usize,
+    },
+    Serde(serde_json::Error),
+    /// An error raised while reading or writing a chunk.
+    InChunk {
+        offset: u64,
+        signature: chunk_signature,
+        version: chunk_version,
+        source: Box<BLFLibError>,
+    },
+    Other(Box<dyn std::error::Error>),
+}
+
+impl BLFLibError {
+    pub fn end_of_file_validation(reason: impl Into<String>) -> BLFLibError {
+        BLFLibError::EndOfFileValidation { reason: reason.into() }
+    }
+
+    /// Names the bitstream field being read or written, if it isn't named already.
+    pub fn with_field(self, name: &str) -> BLFLibError {
+        match self {
+            BLFLibError::BitstreamOverflow { field: None, bit_offset, size_in_bits, remaining_bits } => BLFLibError::BitstreamOverflow {
+                field: Some(name.to_string()),
+                bit_offset,
+                size_in_bits,
+                remaining_bits,
+            },
+            BLFLibError::BitstreamSizeExceeded { field: None, bit_offset, size_in_bits, maximum_size_bytes } => BLFLibError::BitstreamSizeExceeded {
+                field: Some(name.to_string()),
+                bit_offset,
+                size_in_bits,
+                maximum_size_bytes,
+            },
+            err => err,
+        }
+    }
 
-pub struct BLFLibError(Box<dyn std::error::Error>);
+    /// Records which chunk was being read or written, if it isn't recorded already.
+    pub fn in_chunk(self, offset: u64, signature: chunk_signature, version: chunk_version) -> BLFLibError {
+        match self {
+            err @ BLFLibError::InChunk { .. } => err,
+            err => BLFLibError::InChunk {
+                offset,
+                signature,
+                version,
+                source: Box::new(err),
+            },
+        }
+    }
+}
 
 impl From<String> for BLFLibError {
     fn from(e: String) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<&str> for BLFLibError {
     fn from(e: &str) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<std::io::Error> for BLFLibError {
     fn from(e: std::io::Error) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 impl From<TryFromIntError> for BLFLibError {
     fn from(e: TryFromIntError) -> Self {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
@@ -34,61 +115,61 @@ impl From<std::convert::Infallible> for BLFLibError {
 
 impl From<ParseIntError> for BLFLibError {
     fn from(e: ParseIntError) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<std::array::TryFromSliceError> for BLFLibError {
     fn from(e: std::array::TryFromSliceError) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<std::string::FromUtf8Error> for BLFLibError {
     fn from(e: std::string::FromUtf8Error) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<std::string::FromUtf16Error> for BLFLibError {
     fn from(e: std::string::FromUtf16Error) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<Box<dyn std::error::Error>> for BLFLibError {
     fn from(err: Box<dyn std::error::Error>) -> Self {
-        BLFLibError(err)
+        BLFLibError::Other(err)
     }
 }
 
 impl From<std::fmt::Error> for BLFLibError {
     fn from(e: std::fmt::Error) -> Self {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<binrw::Error> for BLFLibError {
     fn from(e: binrw::Error) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<csv::Error> for BLFLibError {
     fn from(e: csv::Error) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
 impl From<serde_json::Error> for BLFLibError {
     fn from(e: serde_json::Error) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Serde(e)
     }
 }
 
 impl From<regex::Error> for BLFLibError {
     fn from(e: regex::Error) -> BLFLibError {
-        BLFLibError(e.into())
+        BLFLibError::Other(e.into())
     }
 }
 
@@ -96,7 +177,7 @@ impl From<BLFLibError> for binrw::Error {
     fn from(err: BLFLibError) -> Self {
         binrw::error::Error::Custom {
             pos: u64::MAX,
-            err: Box::new(err.0.to_string()),
+            err: Box::new(err.to_string()),
         }
     }
 }
@@ -104,19 +185,40 @@ impl From<BLFLibError> for binrw::Error {
 
 impl Display for BLFLibError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.0, f)
+        match self {
+            BLFLibError::ChunkNotFound { signature, version: Some(version) } => write!(f, "Chunk {signature} {version} not found"),
+            BLFLibError::ChunkNotFound { signature, version: None } => write!(f, "Chunk {signature} not found"),
+            BLFLibError::ChunkMismatch { offset, expected_signature, expected_version, found_signature, found_version } =>
+                write!(f, "Failed to read chunk {expected_signature} {expected_version}, found {found_signature} {found_version} instead at 0x{offset:X}!"),
+            BLFLibError::EndOfFileValidation { reason } => write!(f, "{reason}"),
+            BLFLibError::BitstreamOverflow { field, bit_offset, size_in_bits, remaining_bits } =>
+                write!(f, "Tried to read {size_in_bits} bits{} at bit {bit_offset} but the stream only has {remaining_bits} bits left!",
+                    field.as_ref().map(|field| format!(" for {field}")).unwrap_or_default()),
+            BLFLibError::BitstreamSizeExceeded { field, bit_offset, size_in_bits, maximum_size_bytes } =>
+                write!(f, "Tried to write {size_in_bits} bits{} at bit {bit_offset} but the stream exceeds {maximum_size_bytes} bytes!",
+                    field.as_ref().map(|field| format!(" for {field}")).unwrap_or_default()),
+            BLFLibError::Serde(err) => Display::fmt(err, f),
+            BLFLibError::InChunk { offset, signature, version, source } => write!(f, "{signature} {version} at 0x{offset:X}: {source}"),
+            BLFLibError::Other(err) => Display::fmt(err, f),
+        }
     }
 }
 
 impl Debug for BLFLibError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.0, f)
+        Display::fmt(self, f)
     }
 }
 
 impl std::error::Error for BLFLibError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        self.0.source()
+        match self {
+            BLFLibError::Serde(err) => Some(err),
+            BLFLibError::InChunk { source, .. } => Some(source.as_ref()),
+            BLFLibError::Other(err) => err.source(),
+            _ => None,
+        }
     }
 }
+
 pub type BLFLibResult<T = ()> = Result<T, BLFLibError>;
@@ -32,6 +32,11 @@ pub fn blf_chunk_macro(input: TokenStream) -> TokenStream {
     let bytes = signature_string.as_bytes();
     assert_eq!(bytes.len(), 4, "Signature provided with invalid byte length! {signature_string}");
 
+    // Matches chunk_version::new, registrations need to be built at compile time.
+    let version_int = (version_float * 10.0) as u32;
+    let version_major = (version_int / 10) as u16;
+    let version_minor = (version_int % 10) as u16;
+
     let test_size_tokens: TokenStream2 = if size_attribute.is_some() { test_size_macro(tokens.clone()).into() } else { quote! {} };
 
     match input.data {
@@ -57,6 +62,16 @@ pub fn blf_chunk_macro(input: TokenStream) -> TokenStream {
                         blf_lib::types::chunk_version::chunk_version::new(#version_float)
                     }
                 }
+                blf_lib::inventory::submit! {
+                    blf_lib::blf::registry::s_blf_chunk_registration::new(
+                        [#(#bytes), *],
+                        blf_lib::types::chunk_version::chunk_version { major: #version_major, minor: #version_minor },
+                        module_path!(),
+                        || Box::new(<#name as Default>::default()),
+                        std::any::TypeId::of::<#name>,
+                        blf_lib::blf::registry::serialize_chunk::<#name>,
+                    )
+                }
             }
         }
         _ => { panic!("#[derive(BlfChunk)] is only defined for structs!")}
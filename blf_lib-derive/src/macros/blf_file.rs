@@ -1,5 +1,31 @@
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, Data, DeriveInput};
+use syn::{parse_macro_input, Data, DeriveInput, GenericArgument, PathArguments, Type};
+
+enum BlfFileFieldKind {
+    Required,
+    Optional,
+    Repeated,
+}
+
+// Option<T> fields may be absent, Vec<T> fields collect every occurrence, anything else must be present once.
+fn get_field_kind(field_type: &Type) -> (BlfFileFieldKind, Type) {
+    if let Type::Path(type_path) = field_type {
+        if let Some(segment) = type_path.path.segments.last() {
+            if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if let Some(GenericArgument::Type(inner_type)) = arguments.args.first() {
+                    if segment.ident == "Option" {
+                        return (BlfFileFieldKind::Optional, inner_type.clone());
+                    }
+                    if segment.ident == "Vec" {
+                        return (BlfFileFieldKind::Repeated, inner_type.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    (BlfFileFieldKind::Required, field_type.clone())
+}
 
 pub fn blf_file_macro(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -7,35 +33,81 @@ pub fn blf_file_macro(input: TokenStream) -> TokenStream {
 
     match input.data {
         Data::Struct(body) => {
-            let writes = body.fields.iter().map(|field| {
+            let fields = body.fields.iter().map(|field| {
                 let field_name = format_ident!("{}", field.clone().ident.unwrap().to_string());
-
-                quote! {
-                    data.append(&mut blf_lib::blf::chunks::SerializableBlfChunk::write(&mut self.#field_name, &data)?);
+                let (kind, chunk_type) = get_field_kind(&field.ty);
+                (field_name, kind, chunk_type)
+            }).collect::<Vec<_>>();
+
+            let writes = fields.iter().map(|(field_name, kind, _)| {
+                match kind {
+                    BlfFileFieldKind::Required => quote! {
+                        data.append(&mut blf_lib::blf::chunks::SerializableBlfChunk::write(&mut self.#field_name, &data)?);
+                    },
+                    BlfFileFieldKind::Optional => quote! {
+                        if let Some(chunk) = self.#field_name.as_mut() {
+                            data.append(&mut blf_lib::blf::chunks::SerializableBlfChunk::write(chunk, &data)?);
+                        }
+                    },
+                    BlfFileFieldKind::Repeated => quote! {
+                        for chunk in self.#field_name.iter_mut() {
+                            data.append(&mut blf_lib::blf::chunks::SerializableBlfChunk::write(chunk, &data)?);
+                        }
+                    },
                 }
             });
 
-            let reads = body.fields.iter().map(|field| {
-                let field_name = format_ident!("{}", field.clone().ident.unwrap().to_string());
-
-                quote! {
-                    reader.read_exact(&mut header_bytes)?;
-                    header = blf_lib::blf::s_blf_header::decode(&header_bytes)?;
-
-                    if header.signature == blf_lib::blf::chunks::DynamicBlfChunk::signature(&blf_file.#field_name) && header.version == blf_lib::blf::chunks::DynamicBlfChunk::version(&blf_file.#field_name) {
-                        let mut body_bytes = vec![0u8; (header.chunk_size as usize) - blf_lib::blf::s_blf_header::size()];
-                        reader.read_exact(body_bytes.as_mut_slice())?;
+            let reads = fields.iter().enumerate().map(|(field_index, (field_name, kind, chunk_type))| {
+                let decode = match kind {
+                    BlfFileFieldKind::Required => quote! {
                         blf_lib::blf::chunks::SerializableBlfChunk::decode_body(&mut blf_file.#field_name, body_bytes.as_slice(), &previously_read)?;
+                    },
+                    BlfFileFieldKind::Optional => quote! {
+                        let mut chunk = <#chunk_type as Default>::default();
+                        blf_lib::blf::chunks::SerializableBlfChunk::decode_body(&mut chunk, body_bytes.as_slice(), &previously_read)?;
+                        blf_file.#field_name = Some(chunk);
+                    },
+                    BlfFileFieldKind::Repeated => quote! {
+                        let mut chunk = <#chunk_type as Default>::default();
+                        blf_lib::blf::chunks::SerializableBlfChunk::decode_body(&mut chunk, body_bytes.as_slice(), &previously_read)?;
+                        blf_file.#field_name.push(chunk);
+                    },
+                };
+
+                // Single chunk fields take the first matching chunk, later duplicates are skipped.
+                let unread_check = match kind {
+                    BlfFileFieldKind::Repeated => quote! { true },
+                    _ => quote! { !fields_read[#field_index] },
+                };
 
-                        previously_read.extend_from_slice(&header_bytes);
-                        previously_read.extend_from_slice(&body_bytes);
-                    }
-                    else {
-                        return Err(format!("{} Chunk not found!", blf_lib::blf::chunks::DynamicBlfChunk::signature(&blf_file.#field_name).to_string()).into());
+                quote! {
+                    if !chunk_matched
+                        && #unread_check
+                        && header.signature == <#chunk_type as blf_lib::blf::chunks::BlfChunk>::get_signature()
+                        && header.version == <#chunk_type as blf_lib::blf::chunks::BlfChunk>::get_version()
+                    {
+                        #decode
+                        fields_read[#field_index] = true;
+                        chunk_matched = true;
                     }
                 }
             });
 
+            let required_checks = fields.iter().enumerate().filter_map(|(field_index, (_, kind, chunk_type))| {
+                match kind {
+                    BlfFileFieldKind::Required => Some(quote! {
+                        if !fields_read[#field_index] {
+                            return Err(blf_lib::result::BLFLibError::ChunkNotFound {
+                                signature: <#chunk_type as blf_lib::blf::chunks::BlfChunk>::get_signature(),
+                                version: Some(<#chunk_type as blf_lib::blf::chunks::BlfChunk>::get_version()),
+                            }.into());
+                        }
+                    }),
+                    _ => None,
+                }
+            });
+
+            let field_count = fields.len();
 
             (quote! {
                 impl blf_lib::blf::BlfFile for #name {
@@ -67,10 +139,42 @@ pub fn blf_file_macro(input: TokenStream) -> TokenStream {
                         let mut header_bytes = [0u8; blf_lib::blf::s_blf_header::size()];
                         let mut header: blf_lib::blf::s_blf_header;
                         let mut previously_read = Vec::<u8>::new();
+                        let mut fields_read = [false; #field_count];
 
                         let mut blf_file = Self::default();
 
-                        #(#reads)*
+                        // Chunks are matched to fields by signature and version in whatever order they appear.
+                        // Like find_and_validate_eof, reading stops at the _eof or at anything that doesn't look like a chunk,
+                        // some files have extra noise at the end.
+                        while std::io::Read::read_exact(reader, &mut header_bytes).is_ok() {
+                            header = blf_lib::blf::s_blf_header::decode(&header_bytes)?;
+
+                            if (header.chunk_size as usize) < blf_lib::blf::s_blf_header::size() {
+                                break;
+                            }
+
+                            // The body is read through take rather than into a buffer of chunk_size,
+                            // so noise claiming a huge chunk can't allocate more than the stream holds.
+                            let body_size = (header.chunk_size as usize) - blf_lib::blf::s_blf_header::size();
+                            let mut body_bytes = Vec::<u8>::new();
+                            if std::io::Read::read_to_end(&mut std::io::Read::take(&mut *reader, body_size as u64), &mut body_bytes).is_err()
+                                || body_bytes.len() < body_size
+                            {
+                                break;
+                            }
+
+                            let mut chunk_matched = false;
+                            #(#reads)*
+
+                            previously_read.extend_from_slice(&header_bytes);
+                            previously_read.extend_from_slice(&body_bytes);
+
+                            if header.signature == blf_lib::types::chunk_signature::chunk_signature::from_string("_eof") {
+                                break;
+                            }
+                        }
+
+                        #(#required_checks)*
 
                         Ok(blf_file)
                     }
@@ -79,4 +183,4 @@ pub fn blf_file_macro(input: TokenStream) -> TokenStream {
         }
         _ => panic!("Tried to apply BlfFile derive to a non-struct!")
     }
-}
\ No newline at end of file
+}
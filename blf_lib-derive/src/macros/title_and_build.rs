@@ -55,6 +55,10 @@ pub fn title_and_build_macro(input: TokenStream) -> TokenStream {
                     fn get_build_string() -> &'static str {
                         #build_string
                     }
+
+                    fn get_module_path() -> &'static str {
+                        module_path!()
+                    }
                 }
                 impl blf_lib::blf::chunks::DynTitleAndBuild for #name {
                     fn title(&self) -> String {
@@ -64,6 +68,13 @@ pub fn title_and_build_macro(input: TokenStream) -> TokenStream {
                     fn build_string(&self) -> String {
                         String::from(#build_string)
                     }
+
+                    fn module_path(&self) -> &'static str {
+                        module_path!()
+                    }
+                }
+                blf_lib::inventory::submit! {
+                    blf_lib::blf::registry::s_blf_build_registration::new(#title_string, #build_string, module_path!())
                 }
             }
         }
@@ -1 +1,2 @@
-pub mod cache_files;
\ No newline at end of file
+pub mod cache_files;
+pub mod map_checksums;
@@ -1,47 +1,298 @@
-﻿use std::fs::File;
-use std::io::{Cursor, Read};
-use binrw::{BinRead, BinReaderExt, BinWrite};
-use std::ffi::c_char;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use serde::Serialize;
+use blf_lib::assert_ok;
+use blf_lib::blam::common::memory::crc::crc32;
 use blf_lib_derivable::result::BLFLibResult;
-use blf_lib::types::array::StaticArray;
-use crate::types::string::StaticString;
-use crate::types::string::to_string;
-
-#[derive(Debug, BinRead, BinWrite)]
-#[brw(big)]
-pub struct s_cache_file_header_v11 {
-    head: [c_char; 4], // "head"
-    pub version: u32, // 11
-    pub size: u32,
-    unknown1: StaticArray<u8, 0x180>,
-    pub map_name: StaticString<0x20>,
-    pub map_language: u32,
-    unknown2: StaticArray<u8, 0x1BC>,
-    pub rsa_signature: StaticArray<u8, 0x100>,
-    unknown3: StaticArray<u8, 0x2B90>,
-    foot: [c_char; 4], // "foot"
-}
-
-impl s_cache_file_header_v11 {
-    pub fn read(path: String) -> BLFLibResult<Self> {
-        let mut input_file = File::open(path)?;
-        let mut buffer = [0u8; 0x3000];
-        input_file.read_exact(buffer.as_mut_slice()).expect("Failed to read cache file.");
-        let mut reader = Cursor::new(buffer);
-        let cache_file: s_cache_file_header_v11 = reader.read_ne()?;
-
-        if to_string(&cache_file.head)? != "head" {
-            return Err("Invalid cache file: Missing head.".into())
-        }
 
-        if to_string(&cache_file.foot)? != "foot" {
-            return Err("Invalid cache file: Missing foot.".into())
+pub const k_cache_file_header_signature: &[u8; 4] = b"head";
+pub const k_cache_file_footer_signature: &[u8; 4] = b"foot";
+
+// Every gen3+ cache file header shares this prefix, the build string is used to identify the engine.
+const k_cache_file_version_offset: usize = 0x4;
+const k_cache_file_length_offset: usize = 0x8;
+const k_cache_file_build_string_offset: usize = 0x11C;
+const k_cache_file_build_string_length: usize = 0x20;
+
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, FromPrimitive)]
+pub enum e_cache_file_map_type {
+    campaign = 0,
+    multiplayer = 1,
+    main_menu = 2,
+    shared = 3,
+    shared_campaign = 4,
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum e_cache_file_byte_order {
+    big_endian,
+    little_endian,
+}
+
+/// Where each header field lives for a cache file version.
+/// Fields which haven't been mapped for a version are None and read as None.
+#[derive(Debug, Clone, Copy)]
+pub struct s_cache_file_header_layout {
+    pub name: &'static str,
+    pub version: u32,
+    pub header_size: usize,
+    pub map_type_offset: Option<usize>,
+    pub map_name_offset: Option<usize>,
+    pub scenario_path_offset: Option<usize>,
+    pub rsa_signature_offset: Option<usize>,
+}
+
+const k_map_name_length: usize = 0x20;
+const k_scenario_path_length: usize = 0x100;
+const k_rsa_signature_length: usize = 0x100;
+
+/// Halo 3, its pre-release builds and ODST share a header, they're told apart by build string.
+pub const k_cache_file_header_layout_halo3: s_cache_file_header_layout = s_cache_file_header_layout {
+    name: "Halo 3",
+    version: 11,
+    header_size: 0x3000,
+    map_type_offset: Some(0x13C),
+    map_name_offset: Some(0x18C),
+    scenario_path_offset: Some(0x1B0),
+    rsa_signature_offset: Some(0x36C),
+};
+
+/// Reach's pre-release builds share this header.
+/// The RSA signature hasn't been located in Reach or Halo 4 headers yet.
+pub const k_cache_file_header_layout_haloreach: s_cache_file_header_layout = s_cache_file_header_layout {
+    name: "Halo: Reach",
+    version: 12,
+    header_size: 0xA000,
+    map_type_offset: Some(0x13C),
+    map_name_offset: Some(0x180),
+    scenario_path_offset: Some(0x1A4),
+    rsa_signature_offset: None,
+};
+
+pub const k_cache_file_header_layout_halo4: s_cache_file_header_layout = s_cache_file_header_layout {
+    name: "Halo 4",
+    version: 12,
+    header_size: 0x1E000,
+    map_type_offset: Some(0x13C),
+    map_name_offset: Some(0x1A4),
+    scenario_path_offset: Some(0x1C8),
+    rsa_signature_offset: None,
+};
+
+// Reach and Halo 4 share a version, they're told apart by where the header ends.
+const k_cache_file_header_layouts: [s_cache_file_header_layout; 3] = [
+    k_cache_file_header_layout_halo3,
+    k_cache_file_header_layout_haloreach,
+    k_cache_file_header_layout_halo4,
+];
+
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct s_cache_file_header {
+    pub version: u32,
+    pub byte_order: e_cache_file_byte_order,
+    pub file_length: u32,
+    pub build_string: String,
+    /// The layout the header was read with, None for versions which haven't been mapped.
+    pub layout: Option<&'static str>,
+    pub map_type: Option<e_cache_file_map_type>,
+    pub map_name: Option<String>,
+    pub scenario_path: Option<String>,
+    #[serde(skip)]
+    pub rsa_signature: Option<Vec<u8>>,
+}
+
+impl s_cache_file_header {
+    pub fn read_file(path: impl AsRef<std::path::Path>) -> BLFLibResult<Self> {
+        Self::read(&mut File::open(path)?)
+    }
+
+    pub fn read<R: Read + Seek>(reader: &mut R) -> BLFLibResult<Self> {
+        let file_size = reader.seek(SeekFrom::End(0))? as usize;
+        reader.seek(SeekFrom::Start(0))?;
+
+        let mut prefix = vec![0u8; (k_cache_file_build_string_offset + k_cache_file_build_string_length).min(file_size)];
+        reader.read_exact(&mut prefix)?;
+        assert_ok!(prefix.len() == k_cache_file_build_string_offset + k_cache_file_build_string_length, "Invalid cache file: The file is too small to hold a header.");
+
+        // Xbox 360 maps are big endian, PC maps are little endian and have their signatures reversed.
+        let byte_order = if &prefix[0..4] == k_cache_file_header_signature {
+            e_cache_file_byte_order::big_endian
+        } else if prefix[0..4].iter().rev().eq(k_cache_file_header_signature.iter()) {
+            e_cache_file_byte_order::little_endian
+        } else {
+            return Err("Invalid cache file: Missing head.".into());
+        };
+
+        let version = read_u32(&prefix, k_cache_file_version_offset, byte_order);
+        let file_length = read_u32(&prefix, k_cache_file_length_offset, byte_order);
+        let build_string = read_string(&prefix, k_cache_file_build_string_offset, k_cache_file_build_string_length);
+
+        let mut header = Self {
+            version,
+            byte_order,
+            file_length,
+            build_string,
+            layout: None,
+            map_type: None,
+            map_name: None,
+            scenario_path: None,
+            rsa_signature: None,
+        };
+
+        let layout = k_cache_file_header_layouts.iter()
+            .filter(|layout| layout.version == version && layout.header_size <= file_size)
+            .find(|layout| -> bool {
+                let mut footer = [0u8; 4];
+                reader.seek(SeekFrom::Start((layout.header_size - 4) as u64)).is_ok()
+                    && reader.read_exact(&mut footer).is_ok()
+                    && has_signature(&footer, k_cache_file_footer_signature, byte_order)
+            });
+
+        let Some(layout) = layout else {
+            return if k_cache_file_header_layouts.iter().any(|layout| layout.version == version) {
+                Err("Invalid cache file: Missing foot.".into())
+            } else {
+                Ok(header)
+            };
+        };
+
+        let mut buffer = vec![0u8; layout.header_size];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut buffer)?;
+
+        header.layout = Some(layout.name);
+        header.map_type = layout.map_type_offset.and_then(|offset| e_cache_file_map_type::from_u16(read_u16(&buffer, offset, byte_order)));
+        header.map_name = layout.map_name_offset.map(|offset| read_string(&buffer, offset, k_map_name_length));
+        header.scenario_path = layout.scenario_path_offset.map(|offset| read_string(&buffer, offset, k_scenario_path_length));
+        header.rsa_signature = layout.rsa_signature_offset.map(|offset| buffer[offset..offset + k_rsa_signature_length].to_vec());
+
+        Ok(header)
+    }
+
+    /// The checksum hoppers and map variants use to refer to this map.
+    pub fn get_scenario_checksum(&self) -> Option<u32> {
+        self.rsa_signature.as_ref().map(|rsa_signature| crc32(0xFFFFFFFF, rsa_signature))
+    }
+}
+
+fn has_signature(bytes: &[u8], signature: &[u8; 4], byte_order: e_cache_file_byte_order) -> bool {
+    match byte_order {
+        e_cache_file_byte_order::big_endian => bytes == signature,
+        e_cache_file_byte_order::little_endian => bytes.iter().rev().eq(signature.iter()),
+    }
+}
+
+fn read_u16(buffer: &[u8], offset: usize, byte_order: e_cache_file_byte_order) -> u16 {
+    let bytes = [buffer[offset], buffer[offset + 1]];
+    match byte_order {
+        e_cache_file_byte_order::big_endian => u16::from_be_bytes(bytes),
+        e_cache_file_byte_order::little_endian => u16::from_le_bytes(bytes),
+    }
+}
+
+fn read_u32(buffer: &[u8], offset: usize, byte_order: e_cache_file_byte_order) -> u32 {
+    let bytes = [buffer[offset], buffer[offset + 1], buffer[offset + 2], buffer[offset + 3]];
+    match byte_order {
+        e_cache_file_byte_order::big_endian => u32::from_be_bytes(bytes),
+        e_cache_file_byte_order::little_endian => u32::from_le_bytes(bytes),
+    }
+}
+
+fn read_string(buffer: &[u8], offset: usize, length: usize) -> String {
+    buffer[offset..offset + length].iter()
+        .take_while(|byte| **byte != 0)
+        .map(|byte| char::from(*byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use super::*;
+
+    fn cache_file_header(layout: &s_cache_file_header_layout, build_string: &str, map_name: &str, scenario_path: &str) -> Vec<u8> {
+        let mut buffer = vec![0u8; layout.header_size];
+        buffer[0..4].copy_from_slice(b"head");
+        buffer[4..8].copy_from_slice(&layout.version.to_be_bytes());
+        buffer[8..12].copy_from_slice(&(layout.header_size as u32).to_be_bytes());
+        buffer[0x11C..0x11C + build_string.len()].copy_from_slice(build_string.as_bytes());
+        buffer[0x13C..0x13E].copy_from_slice(&1u16.to_be_bytes());
+
+        let map_name_offset = layout.map_name_offset.unwrap();
+        buffer[map_name_offset..map_name_offset + map_name.len()].copy_from_slice(map_name.as_bytes());
+        let scenario_path_offset = layout.scenario_path_offset.unwrap();
+        buffer[scenario_path_offset..scenario_path_offset + scenario_path.len()].copy_from_slice(scenario_path.as_bytes());
+        if let Some(rsa_signature_offset) = layout.rsa_signature_offset {
+            buffer[rsa_signature_offset..rsa_signature_offset + k_rsa_signature_length].fill(0xAB);
         }
 
-        if cache_file.version != 11 {
-            return Err(format!("Invalid cache file: Expected version {}, got {}", 11, cache_file.version).into());
+        buffer[layout.header_size - 4..].copy_from_slice(b"foot");
+        buffer
+    }
+
+    fn halo3_header() -> Vec<u8> {
+        cache_file_header(&k_cache_file_header_layout_halo3, "12070.08.09.05.2031.halo3_ship", "guardian", "levels\\multi\\guardian\\guardian")
+    }
+
+    #[test]
+    fn reads_halo3_header() {
+        let header = s_cache_file_header::read(&mut Cursor::new(halo3_header())).unwrap();
+
+        assert_eq!(header.layout, Some("Halo 3"));
+        assert_eq!(header.build_string, "12070.08.09.05.2031.halo3_ship");
+        assert_eq!(header.map_type, Some(e_cache_file_map_type::multiplayer));
+        assert_eq!(header.map_name.as_deref(), Some("guardian"));
+        assert_eq!(header.scenario_path.as_deref(), Some("levels\\multi\\guardian\\guardian"));
+        assert_eq!(header.get_scenario_checksum(), Some(crc32(0xFFFFFFFF, &[0xAB; 0x100])));
+    }
+
+    #[test]
+    fn reads_halo3_prerelease_and_odst_headers() {
+        for build_string in ["11729.07.08.10.0021.main", "13895.09.04.27.2201.atlas_release"] {
+            let header = s_cache_file_header::read(&mut Cursor::new(
+                cache_file_header(&k_cache_file_header_layout_halo3, build_string, "citadel", "levels\\atlas\\citadel\\citadel")
+            )).unwrap();
+
+            assert_eq!(header.layout, Some("Halo 3"));
+            assert_eq!(header.build_string, build_string);
+            assert_eq!(header.map_name.as_deref(), Some("citadel"));
+            assert!(header.rsa_signature.is_some());
         }
+    }
+
+    #[test]
+    fn reads_haloreach_header() {
+        let header = s_cache_file_header::read(&mut Cursor::new(
+            cache_file_header(&k_cache_file_header_layout_haloreach, "11860.10.07.24.0147.omaha_relea", "20_sword_slayer", "levels\\multi\\20_sword_slayer\\20_sword_slayer")
+        )).unwrap();
 
-        Ok(cache_file)
+        assert_eq!(header.layout, Some("Halo: Reach"));
+        assert_eq!(header.map_type, Some(e_cache_file_map_type::multiplayer));
+        assert_eq!(header.map_name.as_deref(), Some("20_sword_slayer"));
+        assert_eq!(header.scenario_path.as_deref(), Some("levels\\multi\\20_sword_slayer\\20_sword_slayer"));
+        assert_eq!(header.get_scenario_checksum(), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn reads_halo4_header() {
+        let header = s_cache_file_header::read(&mut Cursor::new(
+            cache_file_header(&k_cache_file_header_layout_halo4, "20810.12.09.22.1647.main", "ca_warhouse", "levels\\multi\\ca_warhouse\\ca_warhouse")
+        )).unwrap();
+
+        assert_eq!(header.layout, Some("Halo 4"));
+        assert_eq!(header.map_type, Some(e_cache_file_map_type::multiplayer));
+        assert_eq!(header.map_name.as_deref(), Some("ca_warhouse"));
+        assert_eq!(header.scenario_path.as_deref(), Some("levels\\multi\\ca_warhouse\\ca_warhouse"));
+        assert_eq!(header.get_scenario_checksum(), None);
+    }
+
+    #[test]
+    fn rejects_missing_foot() {
+        let mut buffer = halo3_header();
+        buffer[0x2FFC..0x3000].fill(0);
+
+        assert!(s_cache_file_header::read(&mut Cursor::new(buffer)).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use blf_lib::blam::common::cache::cache_files::s_cache_file_header;
+use blf_lib::blam::common::memory::crc::crc_checksum_buffer;
+use blf_lib::OPTION_TO_RESULT;
+use blf_lib_derivable::result::BLFLibResult;
+
+/// Map variants refer to their map by the crc of its RSA signature.
+pub fn get_rsa_signature_crc(rsa_signature: &[u8]) -> u32 {
+    crc_checksum_buffer(0xFFFFFFFF, rsa_signature)
+}
+
+/// The checksums a map variant must carry to load on its map, keyed by map id.
+///
+/// Scenario crcs are computed from each map's RSA signature.
+/// Palette crcs are computed from the scenario's tags, which aren't read here,
+/// so they're collected from existing map variants and the most common value for each map is used to spot outliers.
+#[derive(Default, Debug, Clone)]
+pub struct c_map_checksums {
+    scenario_crcs: HashMap<u32, u32>,
+    palette_crc_counts: HashMap<u32, HashMap<u32, usize>>,
+}
+
+impl c_map_checksums {
+    pub fn add_rsa_signature(&mut self, map_id: u32, rsa_signature: &[u8]) -> u32 {
+        let scenario_crc = get_rsa_signature_crc(rsa_signature);
+        self.scenario_crcs.insert(map_id, scenario_crc);
+        scenario_crc
+    }
+
+    pub fn add_cache_file(&mut self, map_id: u32, header: &s_cache_file_header) -> BLFLibResult<u32> {
+        let rsa_signature = OPTION_TO_RESULT!(
+            header.rsa_signature.as_ref(),
+            format!("Cache file for map {map_id} ({}) has no RSA signature.", header.build_string)
+        )?;
+
+        Ok(self.add_rsa_signature(map_id, rsa_signature))
+    }
+
+    pub fn add_palette_crc(&mut self, map_id: u32, palette_crc: u32) {
+        if palette_crc == 0 {
+            return;
+        }
+
+        *self.palette_crc_counts.entry(map_id).or_default().entry(palette_crc).or_default() += 1;
+    }
+
+    pub fn get_scenario_crc(&self, map_id: u32) -> Option<u32> {
+        self.scenario_crcs.get(&map_id).copied()
+    }
+
+    pub fn get_palette_crc(&self, map_id: u32) -> Option<u32> {
+        // Ties go to the lowest crc so that builds are reproducible.
+        self.palette_crc_counts.get(&map_id)?.iter()
+            .max_by(|(crc_a, count_a), (crc_b, count_b)| count_a.cmp(count_b).then(crc_b.cmp(crc_a)))
+            .map(|(palette_crc, _)| *palette_crc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_most_common_palette_crc() {
+        let mut map_checksums = c_map_checksums::default();
+        map_checksums.add_palette_crc(1000, 0x11111111);
+        map_checksums.add_palette_crc(1000, 0x22222222);
+        map_checksums.add_palette_crc(1000, 0x22222222);
+        map_checksums.add_palette_crc(1000, 0);
+
+        assert_eq!(map_checksums.get_palette_crc(1000), Some(0x22222222));
+        assert_eq!(map_checksums.get_palette_crc(1001), None);
+    }
+
+    #[test]
+    fn scenario_crc_is_rsa_signature_crc() {
+        let mut map_checksums = c_map_checksums::default();
+        let rsa_signature = [0xABu8; 0x100];
+        map_checksums.add_rsa_signature(1000, &rsa_signature);
+
+        assert_eq!(map_checksums.get_scenario_crc(1000), Some(get_rsa_signature_crc(&rsa_signature)));
+    }
+}
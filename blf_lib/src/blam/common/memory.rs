@@ -1,3 +1,4 @@
 pub mod secure_signature;
 pub mod crc;
-pub mod data_compress;
\ No newline at end of file
+pub mod data_compress;
+pub mod rsa_signature;
\ No newline at end of file
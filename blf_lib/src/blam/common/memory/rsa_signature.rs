@@ -0,0 +1,77 @@
+use rsa::Pkcs1v15Sign;
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use sha1::Sha1;
+use blf_lib::blf::get_buffer_hash;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
+
+pub const k_rsa_signature_length: usize = 256;
+
+pub use rsa::{RsaPrivateKey, RsaPublicKey};
+
+/// Reads an RSA private key, from PEM or DER encoded PKCS#1 or PKCS#8.
+pub fn read_rsa_private_key(key: &[u8]) -> BLFLibResult<RsaPrivateKey> {
+    if let Ok(pem) = std::str::from_utf8(key) {
+        if let Ok(key) = RsaPrivateKey::from_pkcs8_pem(pem) { return Ok(key) }
+        if let Ok(key) = RsaPrivateKey::from_pkcs1_pem(pem) { return Ok(key) }
+    }
+
+    RsaPrivateKey::from_pkcs8_der(key)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_der(key))
+        .map_err(|err| format!("Failed to read RSA private key: {err}").into())
+}
+
+/// Reads an RSA public key, from PEM or DER encoded PKCS#1 or SubjectPublicKeyInfo.
+pub fn read_rsa_public_key(key: &[u8]) -> BLFLibResult<RsaPublicKey> {
+    if let Ok(pem) = std::str::from_utf8(key) {
+        if let Ok(key) = RsaPublicKey::from_public_key_pem(pem) { return Ok(key) }
+        if let Ok(key) = RsaPublicKey::from_pkcs1_pem(pem) { return Ok(key) }
+    }
+
+    RsaPublicKey::from_public_key_der(key)
+        .or_else(|_| RsaPublicKey::from_pkcs1_der(key))
+        .map_err(|err| format!("Failed to read RSA public key: {err}").into())
+}
+
+/// Signs the salted SHA-1 hash of the buffer (see get_buffer_hash) with PKCS#1 v1.5.
+/// Keys must be 2048 bits to fit in an _eof chunk.
+pub fn rsa_sign_buffer(data: &[u8], private_key: &RsaPrivateKey) -> BLFLibResult<[u8; k_rsa_signature_length]> {
+    let hash = get_buffer_hash(data)?;
+    let signature = private_key.sign(Pkcs1v15Sign::new::<Sha1>(), hash.data.get())
+        .map_err(|err| format!("Failed to sign buffer: {err}"))?;
+
+    signature.try_into()
+        .map_err(|signature: Vec<u8>| format!("Expected a {k_rsa_signature_length} byte RSA signature, got {} bytes. Is the key 2048 bits?", signature.len()).into())
+}
+
+pub fn rsa_verify_buffer(data: &[u8], signature: &[u8], public_key: &RsaPublicKey) -> BLFLibResult {
+    let hash = get_buffer_hash(data)?;
+    public_key.verify(Pkcs1v15Sign::new::<Sha1>(), hash.data.get(), signature)
+        .map_err(|_| BLFLibError::end_of_file_validation("_eof has an invalid RSA signature"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+    use crate::blf::BlfFileBuilder;
+    use crate::blf::chunks::verify_rsa_signature;
+    use crate::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_start_of_file;
+
+    #[test]
+    fn signed_files_verify_until_tampered() {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+        let private_key = read_rsa_private_key(private_key.to_pkcs8_pem(LineEnding::LF).unwrap().as_bytes()).unwrap();
+        let public_key = read_rsa_public_key(&RsaPublicKey::from(&private_key).to_public_key_der().unwrap().into_vec()).unwrap();
+
+        let mut data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("signed"))
+            .write_signed(&private_key)
+            .unwrap();
+
+        assert!(verify_rsa_signature(&data, &public_key).is_ok());
+
+        data[0x10] ^= 0xFF;
+        assert!(verify_rsa_signature(&data, &public_key).is_err());
+    }
+}
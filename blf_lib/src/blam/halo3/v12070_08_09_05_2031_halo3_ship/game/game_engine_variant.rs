@@ -216,10 +216,13 @@ impl BinRead for c_game_variant {
 
 impl c_game_variant {
     pub fn encode(&self, bitstream: &mut c_bitstream_writer) -> BLFLibResult {
+        bitstream.write_identifier("game-engine")?;
         bitstream.write_raw(self.m_game_engine, 4)?;
 
+        bitstream.write_identifier("base-variant")?;
         self.m_base_variant.encode(bitstream)?;
 
+        bitstream.write_identifier("engine-variant")?;
         match self.m_game_engine {
             e_game_engine::none => { }
             e_game_engine::ctf => { OPTION_TO_RESULT!(self.m_ctf_variant.as_ref(), "Can't write - CTF variant is None")?.encode(bitstream)?; }
@@ -7,6 +7,8 @@ use blf_lib::types::array::StaticArray;
 use blf_lib::types::net::Ipv4Addr;
 use blf_lib_derive::TestSize;
 
+pub const k_transport_secure_address_size_in_bits: usize = 0x24 * 8;
+
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, BinRead, BinWrite, Default, TestSize)]
 #[cfg_attr(feature = "napi", napi(object, namespace = "halo3_12070_08_09_05_2031_halo3_ship"))]
 #[Size(0x24)]
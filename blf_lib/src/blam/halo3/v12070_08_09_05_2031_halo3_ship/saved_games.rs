@@ -1,2 +1,2 @@
 pub mod saved_game_files;
-pub mod scenario_map_variant;
\ No newline at end of file
+pub mod scenario_map_variant;
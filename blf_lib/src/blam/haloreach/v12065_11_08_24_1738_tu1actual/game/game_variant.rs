@@ -2916,36 +2916,50 @@ impl s_custom_game_engine_definition {
     }
 
     pub fn encode(&self, bitstream: &mut c_bitstream_writer) -> BLFLibResult {
+        bitstream.write_identifier("condition-count")?;
         bitstream.write_integer(self.m_conditions.len() as u32, 10)?;
-        for condition in &self.m_conditions {
+        for (index, condition) in self.m_conditions.iter().enumerate() {
+            bitstream.write_identifier(&format!("conditions[{index}]"))?;
             condition.encode(bitstream)?;
         }
 
+        bitstream.write_identifier("action-count")?;
         bitstream.write_integer(self.m_actions.len() as u32, 11)?;
-        for action in &self.m_actions {
+        for (index, action) in self.m_actions.iter().enumerate() {
+            bitstream.write_identifier(&format!("actions[{index}]"))?;
             action.encode(bitstream)?;
         }
 
+        bitstream.write_identifier("trigger-count")?;
         bitstream.write_integer(self.m_triggers.len() as u32, 9)?;
-        for trigger in &self.m_triggers {
+        for (index, trigger) in self.m_triggers.iter().enumerate() {
+            bitstream.write_identifier(&format!("triggers[{index}]"))?;
             trigger.encode(bitstream)?;
         }
 
+        bitstream.write_identifier("statistic-count")?;
         bitstream.write_integer(self.m_statistics.len() as u32, 3)?;
-        for statistic in &self.m_statistics {
+        for (index, statistic) in self.m_statistics.iter().enumerate() {
+            bitstream.write_identifier(&format!("statistics[{index}]"))?;
             statistic.encode(bitstream)?;
         }
 
+        bitstream.write_identifier("global-variable-metadata")?;
         self.m_global_variable_metadata.encode(bitstream)?;
+        bitstream.write_identifier("player-variable-metadata")?;
         self.m_player_variable_metadata.encode(bitstream)?;
+        bitstream.write_identifier("object-variable-metadata")?;
         self.m_object_variable_metadata.encode(bitstream)?;
+        bitstream.write_identifier("team-variable-metadata")?;
         self.m_team_variable_metadata.encode(bitstream)?;
 
+        bitstream.write_identifier("hud-widgets")?;
         bitstream.write_integer(self.m_hud_widgets.len() as u32, 3)?;
         for widget in &self.m_hud_widgets {
             bitstream.write_enum(*widget)?;
         }
 
+        bitstream.write_identifier("trigger-indices")?;
         bitstream.write_integer((self.m_initialization_trigger_index + 1) as u32, 9)?;
         bitstream.write_integer((self.m_local_initialization_trigger_index + 1) as u32, 9)?;
         bitstream.write_integer((self.m_host_migration_trigger_index + 1) as u32, 9)?;
@@ -2954,10 +2968,13 @@ impl s_custom_game_engine_definition {
         bitstream.write_integer((self.m_local_trigger_index + 1) as u32, 9)?;
         bitstream.write_integer((self.m_pregame_trigger_index + 1) as u32, 9)?;
 
+        bitstream.write_identifier("objects-used")?;
         bitstream.write_big_flags(self.m_objects_used.get())?;
 
+        bitstream.write_identifier("object-filter-count")?;
         bitstream.write_integer(self.m_object_filters.len() as u32, 5)?;
-        for filter in &self.m_object_filters {
+        for (index, filter) in self.m_object_filters.iter().enumerate() {
+            bitstream.write_identifier(&format!("object-filters[{index}]"))?;
             filter.encode(bitstream)?;
         }
 
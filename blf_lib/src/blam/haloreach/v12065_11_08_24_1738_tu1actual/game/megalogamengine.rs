@@ -18,4 +18,6 @@ pub mod megalogamengine_statistics;
 pub mod megalogamengine_variable_metadata;
 pub mod megalogamengine_map_objects;
 pub mod megalogamengine_hud_widgets;
-pub mod megalogamengine_sounds;
\ No newline at end of file
+pub mod megalogamengine_sounds;
+pub mod megalogamengine_decompiler;
+pub mod megalogamengine_compiler;
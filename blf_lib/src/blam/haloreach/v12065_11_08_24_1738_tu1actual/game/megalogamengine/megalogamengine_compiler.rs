@@ -0,0 +1,3000 @@
+use std::collections::HashMap;
+use num_traits::FromPrimitive;
+use serde::de::DeserializeOwned;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_engine_team::e_multiplayer_team_designator;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_engine_timer::e_game_engine_timer_rate;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_variant::c_game_engine_custom_variant;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_actions::*;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_conditions::*;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_custom_timer_reference::{c_custom_timer_reference, e_custom_timer_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_custom_variable_reference::{c_custom_variable_reference, e_custom_variable_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_explicit_object::{c_explicit_object, e_explicit_object_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_explicit_player::{c_explicit_player, e_explicit_player_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_explicit_team::{c_explicit_team, e_explicit_team_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_hud_widgets::{e_megalo_widget_position, e_megalogamengine_hud_meter_input_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_object_reference::{c_object_reference, e_object_reference_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_object_type_reference::c_object_type_reference;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_player_reference::{c_player_reference, e_player_reference_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_sounds::e_megalo_sound;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_team_reference::{c_team_reference, e_team_reference_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_text::{c_dynamic_string, c_replaceable_token, e_replaceable_token_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_trigger::{c_trigger, e_trigger_execution_mode, e_trigger_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_variable_metadata::{e_megalo_variable_network_state, k_maximum_global_numeric_variables, k_maximum_global_object_variables, k_maximum_global_player_variables, k_maximum_global_team_variables, k_maximum_global_timer_variables, s_variable_metadata};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_variant_variable::{e_variable_type, s_variant_variable};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::saved_games::scenario_map_variant::e_boundary_shape;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
+
+// Script strings are matched and added in English.
+const k_script_string_language_index: usize = 0;
+
+const k_maximum_triggers: usize = 320;
+const k_maximum_conditions: usize = 512;
+const k_maximum_actions: usize = 1024;
+const k_maximum_hud_widgets: usize = 4;
+const k_maximum_object_types: usize = 2048;
+const k_maximum_dynamic_string_tokens: usize = 3;
+
+// Declarable variables per scope, in number, timer, team, player and object order.
+const k_global_variable_limits: [usize; 5] = [
+    k_maximum_global_numeric_variables,
+    k_maximum_global_timer_variables,
+    k_maximum_global_team_variables,
+    k_maximum_global_player_variables,
+    k_maximum_global_object_variables,
+];
+const k_player_variable_limits: [usize; 5] = [8, 4, 4, 4, 4];
+const k_object_variable_limits: [usize; 5] = [8, 4, 2, 4, 4];
+const k_team_variable_limits: [usize; 5] = [8, 4, 4, 4, 6];
+
+const k_assignment_operators: [&str; 11] = ["=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "~=", "abs="];
+const k_comparison_operators: [&str; 6] = ["<", ">", "==", "<=", ">=", "!="];
+
+// Longer symbols come first so they win over their prefixes.
+const k_symbols: [&str; 24] = [
+    "==", "!=", "<=", ">=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "~=",
+    "<", ">", "=", ".", ",", "(", ")", "[", "]", "|", ":",
+];
+
+/// Compiles a megalo script, as written by `decompile_megalo_script`, into a custom game variant.
+///
+/// The variant's conditions, actions, triggers, event triggers, variable declarations and HUD widgets are replaced.
+/// Strings which aren't in the script string table yet are added to it,
+/// and object types referenced by the script are marked as used.
+/// Everything else, including script options, statistics, traits and object filters, is referenced as-is.
+pub fn compile_megalo_script(source: &str, variant: &mut c_game_engine_custom_variant) -> BLFLibResult {
+    let mut lines = Vec::new();
+    for (line_index, line) in source.lines().enumerate() {
+        let tokens = tokenize(line).map_err(|error| at_line(line_index + 1, error))?;
+        if !tokens.is_empty() {
+            lines.push((line_index + 1, tokens));
+        }
+    }
+
+    let mut parser = c_megalo_parser { m_lines: lines, m_position: 0 };
+    let statements = parser.parse_script()?;
+
+    // The script is compiled into a copy, so the variant is left as it was if compilation fails.
+    let mut compiled_variant = variant.clone();
+    let mut compiler = c_megalo_compiler {
+        m_variant: &mut compiled_variant,
+        m_conditions: Vec::new(),
+        m_actions: Vec::new(),
+        m_triggers: Vec::new(),
+        m_event_triggers: HashMap::new(),
+        m_functions: HashMap::new(),
+        m_function_calls: Vec::new(),
+    };
+
+    compiler.compile(&statements)?;
+    *variant = compiled_variant;
+
+    Ok(())
+}
+
+fn at_line(line: usize, error: BLFLibError) -> BLFLibError {
+    BLFLibError::from(format!("line {line}: {error}"))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum e_megalo_token {
+    identifier(String),
+    number(i64),
+    string(String),
+    symbol(&'static str),
+}
+
+impl e_megalo_token {
+    fn is_identifier(&self, name: &str) -> bool {
+        matches!(self, e_megalo_token::identifier(identifier) if identifier == name)
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self, e_megalo_token::symbol(existing) if *existing == symbol)
+    }
+}
+
+fn tokenize(line: &str) -> BLFLibResult<Vec<e_megalo_token>> {
+    let characters: Vec<char> = line.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < characters.len() {
+        let character = characters[position];
+        let next = characters.get(position + 1).copied();
+
+        if character.is_whitespace() {
+            position += 1;
+        }
+        else if character == '-' && next == Some('-') {
+            break;
+        }
+        else if character == '"' {
+            let (string, length) = read_string_literal(&characters[position..])?;
+            tokens.push(e_megalo_token::string(string));
+            position += length;
+        }
+        else if character.is_ascii_digit() || (character == '-' && next.is_some_and(|next| next.is_ascii_digit())) {
+            let start = position;
+            position += 1;
+            while position < characters.len() && characters[position].is_ascii_digit() {
+                position += 1;
+            }
+            let number: String = characters[start..position].iter().collect();
+            tokens.push(e_megalo_token::number(number.parse().map_err(|_| format!("{number} is not a valid number"))?));
+        }
+        else if character.is_ascii_alphabetic() || character == '_' {
+            let start = position;
+            while position < characters.len() && (characters[position].is_ascii_alphanumeric() || characters[position] == '_') {
+                position += 1;
+            }
+            let identifier: String = characters[start..position].iter().collect();
+
+            // abs= is the only operator which starts with a letter.
+            if identifier == "abs" && characters.get(position) == Some(&'=') && characters.get(position + 1) != Some(&'=') {
+                tokens.push(e_megalo_token::symbol("abs="));
+                position += 1;
+            } else {
+                tokens.push(e_megalo_token::identifier(identifier));
+            }
+        }
+        else {
+            let remaining: String = characters[position..].iter().collect();
+            let symbol = k_symbols.iter()
+                .find(|symbol| remaining.starts_with(**symbol))
+                .ok_or_else(|| format!("unexpected character '{character}'"))?;
+            tokens.push(e_megalo_token::symbol(symbol));
+            position += symbol.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Reads a string literal escaped the way Rust debug formatting escapes it, returning the string and the characters read.
+fn read_string_literal(characters: &[char]) -> BLFLibResult<(String, usize)> {
+    let mut string = String::new();
+    let mut position = 1;
+
+    while position < characters.len() {
+        let character = characters[position];
+        position += 1;
+
+        match character {
+            '"' => return Ok((string, position)),
+            '\\' => {
+                let escaped = characters.get(position).copied().ok_or("unterminated string")?;
+                position += 1;
+                match escaped {
+                    'n' => string.push('\n'),
+                    'r' => string.push('\r'),
+                    't' => string.push('\t'),
+                    '0' => string.push('\0'),
+                    '\\' | '"' | '\'' => string.push(escaped),
+                    'u' => {
+                        let end = characters[position..].iter().position(|character| *character == '}')
+                            .ok_or("unterminated unicode escape")?;
+                        let digits: String = characters[position..position + end].iter().skip(1).collect();
+                        let code = u32::from_str_radix(&digits, 16).map_err(|_| format!("invalid unicode escape \\u{{{digits}}}"))?;
+                        string.push(char::from_u32(code).ok_or_else(|| format!("invalid unicode escape \\u{{{digits}}}"))?);
+                        position += end + 1;
+                    }
+                    _ => return Err(format!("unknown escape \\{escaped}").into()),
+                }
+            }
+            _ => string.push(character),
+        }
+    }
+
+    Err("unterminated string".into())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct s_megalo_path_segment {
+    m_name: String,
+    m_index: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct s_megalo_call {
+    m_target: Vec<s_megalo_path_segment>,
+    m_name: String,
+    m_arguments: Vec<e_megalo_expression>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum e_megalo_expression {
+    number(i64),
+    string(String),
+    path(Vec<s_megalo_path_segment>),
+    call(s_megalo_call),
+    flags(Vec<e_megalo_expression>),
+}
+
+fn describe_path(path: &[s_megalo_path_segment]) -> String {
+    path.iter()
+        .map(|segment| match segment.m_index {
+            Some(index) => format!("{}[{index}]", segment.m_name),
+            None => segment.m_name.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+fn describe(expression: &e_megalo_expression) -> String {
+    match expression {
+        e_megalo_expression::number(number) => number.to_string(),
+        e_megalo_expression::string(string) => format!("{string:?}"),
+        e_megalo_expression::path(path) => describe_path(path),
+        e_megalo_expression::call(call) => {
+            let arguments: Vec<String> = call.m_arguments.iter().map(describe).collect();
+            if call.m_target.is_empty() {
+                format!("{}({})", call.m_name, arguments.join(", "))
+            } else {
+                format!("{}.{}({})", describe_path(&call.m_target), call.m_name, arguments.join(", "))
+            }
+        }
+        e_megalo_expression::flags(flags) => flags.iter().map(describe).collect::<Vec<String>>().join(" | "),
+    }
+}
+
+struct c_megalo_token_reader<'a> {
+    m_tokens: &'a [e_megalo_token],
+    m_position: usize,
+}
+
+impl<'a> c_megalo_token_reader<'a> {
+    fn new(tokens: &'a [e_megalo_token]) -> Self {
+        Self { m_tokens: tokens, m_position: 0 }
+    }
+
+    fn peek(&self) -> Option<&'a e_megalo_token> {
+        self.m_tokens.get(self.m_position)
+    }
+
+    fn next(&mut self) -> BLFLibResult<&'a e_megalo_token> {
+        let token = self.m_tokens.get(self.m_position).ok_or("unexpected end of line")?;
+        self.m_position += 1;
+        Ok(token)
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.m_position >= self.m_tokens.len()
+    }
+
+    fn try_symbol(&mut self, symbol: &str) -> bool {
+        if self.peek().is_some_and(|token| token.is_symbol(symbol)) {
+            self.m_position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn try_identifier(&mut self, name: &str) -> bool {
+        if self.peek().is_some_and(|token| token.is_identifier(name)) {
+            self.m_position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> BLFLibResult {
+        match self.next()? {
+            token if token.is_symbol(symbol) => Ok(()),
+            token => Err(format!("expected '{symbol}' but found {}", describe_token(token)).into()),
+        }
+    }
+
+    fn expect_identifier(&mut self) -> BLFLibResult<&'a str> {
+        match self.next()? {
+            e_megalo_token::identifier(identifier) => Ok(identifier),
+            token => Err(format!("expected a name but found {}", describe_token(token)).into()),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> BLFLibResult {
+        match self.next()? {
+            token if token.is_identifier(keyword) => Ok(()),
+            token => Err(format!("expected '{keyword}' but found {}", describe_token(token)).into()),
+        }
+    }
+
+    fn expect_end(&self) -> BLFLibResult {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(format!("unexpected {}", describe_token(token)).into()),
+        }
+    }
+
+    fn expression(&mut self) -> BLFLibResult<e_megalo_expression> {
+        let expression = self.primary()?;
+        if !self.peek().is_some_and(|token| token.is_symbol("|")) {
+            return Ok(expression);
+        }
+
+        let mut flags = vec![expression];
+        while self.try_symbol("|") {
+            flags.push(self.primary()?);
+        }
+        Ok(e_megalo_expression::flags(flags))
+    }
+
+    fn primary(&mut self) -> BLFLibResult<e_megalo_expression> {
+        let mut path = Vec::new();
+
+        loop {
+            match self.next()? {
+                e_megalo_token::number(number) if path.is_empty() => return Ok(e_megalo_expression::number(*number)),
+                e_megalo_token::string(string) if path.is_empty() => return Ok(e_megalo_expression::string(string.clone())),
+                e_megalo_token::identifier(name) => {
+                    let mut segment = s_megalo_path_segment { m_name: name.clone(), m_index: None };
+                    if self.try_symbol("[") {
+                        match self.next()? {
+                            e_megalo_token::number(index) => segment.m_index = Some(*index),
+                            token => return Err(format!("expected an index but found {}", describe_token(token)).into()),
+                        }
+                        self.expect_symbol("]")?;
+                    }
+                    else if self.try_symbol("(") {
+                        let mut arguments = Vec::new();
+                        if !self.try_symbol(")") {
+                            loop {
+                                arguments.push(self.expression()?);
+                                if self.try_symbol(")") {
+                                    break;
+                                }
+                                self.expect_symbol(",")?;
+                            }
+                        }
+                        return Ok(e_megalo_expression::call(s_megalo_call { m_target: path, m_name: segment.m_name, m_arguments: arguments }));
+                    }
+                    path.push(segment);
+                }
+                token => return Err(format!("unexpected {}", describe_token(token)).into()),
+            }
+
+            if !self.try_symbol(".") {
+                return Ok(e_megalo_expression::path(path));
+            }
+        }
+    }
+}
+
+fn describe_token(token: &e_megalo_token) -> String {
+    match token {
+        e_megalo_token::identifier(identifier) => format!("'{identifier}'"),
+        e_megalo_token::number(number) => format!("'{number}'"),
+        e_megalo_token::string(string) => format!("{string:?}"),
+        e_megalo_token::symbol(symbol) => format!("'{symbol}'"),
+    }
+}
+
+fn parse_expression(tokens: &[e_megalo_token]) -> BLFLibResult<e_megalo_expression> {
+    let mut reader = c_megalo_token_reader::new(tokens);
+    let expression = reader.expression()?;
+    reader.expect_end()?;
+    Ok(expression)
+}
+
+// Finds the first token at the outermost bracket depth which matches.
+fn find_top_level(tokens: &[e_megalo_token], matches: impl Fn(&e_megalo_token) -> bool) -> Option<usize> {
+    let mut depth = 0i32;
+    for (position, token) in tokens.iter().enumerate() {
+        if token.is_symbol("(") || token.is_symbol("[") {
+            depth += 1;
+        }
+        else if token.is_symbol(")") || token.is_symbol("]") {
+            depth -= 1;
+        }
+        else if depth == 0 && matches(token) {
+            return Some(position);
+        }
+    }
+    None
+}
+
+fn split_top_level<'a>(tokens: &'a [e_megalo_token], keyword: &str) -> BLFLibResult<Vec<&'a [e_megalo_token]>> {
+    let mut parts = Vec::new();
+    let mut remaining = tokens;
+
+    while let Some(position) = find_top_level(remaining, |token| token.is_identifier(keyword)) {
+        parts.push(&remaining[..position]);
+        remaining = &remaining[position + 1..];
+    }
+    parts.push(remaining);
+
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(format!("'{keyword}' is missing an operand").into());
+    }
+
+    Ok(parts)
+}
+
+// Strips parentheses which wrap the whole expression.
+fn strip_parentheses(tokens: &[e_megalo_token]) -> &[e_megalo_token] {
+    let wrapped = tokens.len() >= 2
+        && tokens[0].is_symbol("(")
+        && tokens[tokens.len() - 1].is_symbol(")")
+        && balanced(&tokens[1..tokens.len() - 1]);
+
+    if wrapped { &tokens[1..tokens.len() - 1] } else { tokens }
+}
+
+fn balanced(tokens: &[e_megalo_token]) -> bool {
+    let mut depth = 0i32;
+    for token in tokens {
+        if token.is_symbol("(") || token.is_symbol("[") {
+            depth += 1;
+        }
+        else if token.is_symbol(")") || token.is_symbol("]") {
+            depth -= 1;
+            if depth < 0 {
+                return false;
+            }
+        }
+    }
+    depth == 0
+}
+
+#[derive(Clone, Debug)]
+struct s_megalo_block {
+    m_trigger_type: e_trigger_type,
+    m_double_host_migration: bool,
+    m_function_name: Option<String>,
+    m_execution_mode: e_trigger_execution_mode,
+    m_object_filter: Option<e_megalo_expression>,
+    m_statements: Vec<s_megalo_statement>,
+}
+
+#[derive(Clone, Debug)]
+enum e_megalo_statement {
+    declaration(Vec<e_megalo_token>),
+    action(Vec<e_megalo_token>),
+    block(s_megalo_block),
+    // An if block's conditions and the statements they guard.
+    condition(Vec<e_megalo_token>, Vec<s_megalo_statement>),
+}
+
+#[derive(Clone, Debug)]
+struct s_megalo_statement {
+    m_line: usize,
+    m_statement: e_megalo_statement,
+}
+
+struct c_megalo_parser {
+    m_lines: Vec<(usize, Vec<e_megalo_token>)>,
+    m_position: usize,
+}
+
+impl c_megalo_parser {
+    fn parse_script(&mut self) -> BLFLibResult<Vec<s_megalo_statement>> {
+        let mut statements = Vec::new();
+
+        while let Some((line, tokens)) = self.m_lines.get(self.m_position).cloned() {
+            self.m_position += 1;
+            if tokens.len() == 1 && tokens[0].is_identifier("end") {
+                return Err(at_line(line, "'end' without a matching block".into()));
+            }
+            statements.push(self.parse_statement(line, tokens)?);
+        }
+
+        Ok(statements)
+    }
+
+    fn parse_body(&mut self, opening_line: usize) -> BLFLibResult<Vec<s_megalo_statement>> {
+        let mut statements = Vec::new();
+
+        loop {
+            let Some((line, tokens)) = self.m_lines.get(self.m_position).cloned() else {
+                return Err(at_line(opening_line, "block is never closed with 'end'".into()));
+            };
+            self.m_position += 1;
+
+            if tokens.len() == 1 && tokens[0].is_identifier("end") {
+                return Ok(statements);
+            }
+            statements.push(self.parse_statement(line, tokens)?);
+        }
+    }
+
+    fn parse_statement(&mut self, line: usize, tokens: Vec<e_megalo_token>) -> BLFLibResult<s_megalo_statement> {
+        let statement = if tokens[0].is_identifier("declare") {
+            e_megalo_statement::declaration(tokens[1..].to_vec())
+        }
+        else if tokens[0].is_identifier("if") {
+            if !tokens.last().is_some_and(|token| token.is_identifier("then")) || tokens.len() < 3 {
+                return Err(at_line(line, "expected 'if <conditions> then'".into()));
+            }
+            let body = self.parse_body(line)?;
+            e_megalo_statement::condition(tokens[1..tokens.len() - 1].to_vec(), body)
+        }
+        else if tokens.last().is_some_and(|token| token.is_identifier("do")) {
+            let mut block = parse_block_header(&tokens[..tokens.len() - 1]).map_err(|error| at_line(line, error))?;
+            block.m_statements = self.parse_body(line)?;
+            e_megalo_statement::block(block)
+        }
+        else {
+            e_megalo_statement::action(tokens)
+        };
+
+        Ok(s_megalo_statement { m_line: line, m_statement: statement })
+    }
+}
+
+fn parse_block_header(tokens: &[e_megalo_token]) -> BLFLibResult<s_megalo_block> {
+    let mut reader = c_megalo_token_reader::new(tokens);
+    let mut block = s_megalo_block {
+        m_trigger_type: e_trigger_type::normal,
+        m_double_host_migration: false,
+        m_function_name: None,
+        m_execution_mode: e_trigger_execution_mode::general,
+        m_object_filter: None,
+        m_statements: Vec::new(),
+    };
+
+    if reader.try_identifier("on") {
+        let mut words = Vec::new();
+        while !reader.try_symbol(":") {
+            words.push(reader.expect_identifier()?);
+        }
+
+        let event = words.join(" ");
+        block.m_trigger_type = match event.as_str() {
+            "init" => e_trigger_type::initialization,
+            "local init" => e_trigger_type::local_initialization,
+            "host migration" | "double host migration" => e_trigger_type::host_migration,
+            "object death" => e_trigger_type::object_death,
+            "local" => e_trigger_type::local,
+            "pregame" => e_trigger_type::pregame,
+            _ => return Err(format!("unknown event '{event}'").into()),
+        };
+        block.m_double_host_migration = event == "double host migration";
+    }
+    else if reader.try_identifier("function") {
+        block.m_function_name = Some(reader.expect_identifier()?.to_string());
+        block.m_trigger_type = e_trigger_type::subroutine;
+        reader.expect_symbol("(")?;
+        reader.expect_symbol(")")?;
+        reader.expect_symbol(":")?;
+    }
+
+    if reader.try_identifier("for") {
+        reader.expect_keyword("each")?;
+        block.m_execution_mode = match reader.expect_identifier()? {
+            "player" if reader.try_identifier("randomly") => e_trigger_execution_mode::random_player,
+            "player" => e_trigger_execution_mode::player,
+            "team" => e_trigger_execution_mode::team,
+            "object" if reader.try_identifier("with") => {
+                reader.expect_keyword("label")?;
+                block.m_object_filter = Some(reader.expression()?);
+                e_trigger_execution_mode::object_with_label
+            }
+            "object" => e_trigger_execution_mode::object,
+            other => return Err(format!("can't run a block for each '{other}'").into()),
+        };
+    }
+
+    reader.expect_end()?;
+    Ok(block)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum e_megalo_scope {
+    global,
+    player,
+    object,
+    team,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum e_megalo_variable_kind {
+    number = 0,
+    timer = 1,
+    team = 2,
+    player = 3,
+    object = 4,
+}
+
+enum e_explicit_owner {
+    player(c_explicit_player),
+    object(c_explicit_object),
+    team(c_explicit_team),
+}
+
+impl e_explicit_owner {
+    fn scope(&self) -> e_megalo_scope {
+        match self {
+            e_explicit_owner::player(_) => e_megalo_scope::player,
+            e_explicit_owner::object(_) => e_megalo_scope::object,
+            e_explicit_owner::team(_) => e_megalo_scope::team,
+        }
+    }
+}
+
+#[derive(Default)]
+struct s_trigger_builder {
+    m_conditions: Vec<c_condition>,
+    m_actions: Vec<c_action>,
+    m_union_group: u16,
+    // Calls to functions by name, resolved once every function has a trigger.
+    m_function_calls: Vec<(usize, String, usize)>,
+}
+
+struct c_megalo_compiler<'a> {
+    m_variant: &'a mut c_game_engine_custom_variant,
+    m_conditions: Vec<c_condition>,
+    m_actions: Vec<c_action>,
+    m_triggers: Vec<c_trigger>,
+    m_event_triggers: HashMap<&'static str, i16>,
+    m_functions: HashMap<String, usize>,
+    m_function_calls: Vec<(usize, String, usize)>,
+}
+
+fn variable_counts<const a: usize, const b: usize, const c: usize, const d: usize, const e: usize>(
+    metadata: &s_variable_metadata<a, b, c, d, e>,
+) -> [usize; 5] {
+    [
+        metadata.m_numeric_variables.len(),
+        metadata.m_timer_variables.len(),
+        metadata.m_team_variables.len(),
+        metadata.m_player_variables.len(),
+        metadata.m_object_variables.len(),
+    ]
+}
+
+fn parse_enum<T: DeserializeOwned>(name: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+}
+
+fn math_operation(operator: &str) -> BLFLibResult<e_math_operation> {
+    Ok(match operator {
+        "+=" => e_math_operation::add,
+        "-=" => e_math_operation::subtract,
+        "*=" => e_math_operation::multiply,
+        "/=" => e_math_operation::divide,
+        "=" => e_math_operation::set_to,
+        "%=" => e_math_operation::modulo,
+        "&=" => e_math_operation::and,
+        "|=" => e_math_operation::or,
+        "^=" => e_math_operation::xor,
+        "~=" => e_math_operation::not,
+        "abs=" => e_math_operation::abs,
+        _ => return Err(format!("'{operator}' is not an assignment operator").into()),
+    })
+}
+
+fn numeric_comparison(operator: &str) -> BLFLibResult<e_numeric_comparison> {
+    Ok(match operator {
+        "<" => e_numeric_comparison::less_than,
+        ">" => e_numeric_comparison::greater_than,
+        "==" => e_numeric_comparison::equal_to,
+        "<=" => e_numeric_comparison::less_than_or_equal_to,
+        ">=" => e_numeric_comparison::greater_than_or_equal_to,
+        "!=" => e_numeric_comparison::not_equal_to,
+        _ => return Err(format!("'{operator}' is not a comparison").into()),
+    })
+}
+
+fn symbol_at(tokens: &[e_megalo_token], position: usize) -> &'static str {
+    match tokens[position] {
+        e_megalo_token::symbol(symbol) => symbol,
+        _ => unreachable!(),
+    }
+}
+
+fn name(expression: &e_megalo_expression) -> Option<&str> {
+    match expression {
+        e_megalo_expression::path(path) if path.len() == 1 && path[0].m_index.is_none() => Some(&path[0].m_name),
+        _ => None,
+    }
+}
+
+fn indexed_name(expression: &e_megalo_expression, expected_name: &str) -> Option<i64> {
+    match expression {
+        e_megalo_expression::path(path) if path.len() == 1 && path[0].m_name == expected_name => path[0].m_index,
+        _ => None,
+    }
+}
+
+fn integer<T: TryFrom<i64>>(expression: &e_megalo_expression, minimum: i64, maximum: i64) -> BLFLibResult<T> {
+    match expression {
+        e_megalo_expression::number(number) if (minimum..=maximum).contains(number) => {
+            T::try_from(*number).map_err(|_| format!("{number} is out of range").into())
+        }
+        e_megalo_expression::number(number) => Err(format!("{number} is out of range, expected {minimum} to {maximum}").into()),
+        _ => Err(format!("expected a number but found {}", describe(expression)).into()),
+    }
+}
+
+fn boolean(expression: &e_megalo_expression) -> BLFLibResult<bool> {
+    match name(expression) {
+        Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        _ => Err(format!("expected true or false but found {}", describe(expression)).into()),
+    }
+}
+
+fn choice(expression: &e_megalo_expression, when_true: &str, when_false: &str) -> BLFLibResult<bool> {
+    match name(expression) {
+        Some(value) if value == when_true => Ok(true),
+        Some(value) if value == when_false => Ok(false),
+        _ => Err(format!("expected {when_true} or {when_false} but found {}", describe(expression)).into()),
+    }
+}
+
+fn named_enum<T: DeserializeOwned>(expression: &e_megalo_expression, kind: &str) -> BLFLibResult<T> {
+    name(expression)
+        .and_then(parse_enum)
+        .ok_or_else(|| format!("{} is not a {kind}", describe(expression)).into())
+}
+
+// Names joined with |, or none.
+fn flag_names(expression: &e_megalo_expression) -> BLFLibResult<Vec<&str>> {
+    let flags = match expression {
+        e_megalo_expression::flags(flags) => flags.iter().collect(),
+        flag => vec![flag],
+    };
+
+    let mut names = Vec::new();
+    for flag in flags {
+        match name(flag) {
+            Some("none") if names.is_empty() => {}
+            Some(flag_name) => names.push(flag_name),
+            None => return Err(format!("expected a flag but found {}", describe(flag)).into()),
+        }
+    }
+    Ok(names)
+}
+
+fn object_offset(arguments: &[e_megalo_expression]) -> BLFLibResult<s_object_offset> {
+    Ok(s_object_offset {
+        x: integer(&arguments[0], i8::MIN as i64, i8::MAX as i64)?,
+        y: integer(&arguments[1], i8::MIN as i64, i8::MAX as i64)?,
+        z: integer(&arguments[2], i8::MIN as i64, i8::MAX as i64)?,
+    })
+}
+
+fn expect_arguments(call: &s_megalo_call, counts: &[usize]) -> BLFLibResult {
+    if counts.contains(&call.m_arguments.len()) {
+        return Ok(());
+    }
+
+    let expected: Vec<String> = counts.iter().map(usize::to_string).collect();
+    Err(format!("{}() takes {} arguments but was given {}", call.m_name, expected.join(" or "), call.m_arguments.len()).into())
+}
+
+fn expect_no_target(call: &s_megalo_call) -> BLFLibResult {
+    if call.m_target.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{}() can't be called on {}", call.m_name, describe_path(&call.m_target)).into())
+    }
+}
+
+fn expect_game_target(call: &s_megalo_call) -> BLFLibResult {
+    if call.m_target.len() == 1 && call.m_target[0].m_name == "game" && call.m_target[0].m_index.is_none() {
+        Ok(())
+    } else {
+        Err(format!("{}() must be called on game", call.m_name).into())
+    }
+}
+
+fn for_each_action(trigger_index: usize) -> c_action {
+    c_action {
+        m_type: e_action_type::for_each,
+        m_for_each_parameters: Some(s_action_for_each_parameters { m_trigger_index: trigger_index as u16 }),
+        ..Default::default()
+    }
+}
+
+impl c_megalo_compiler<'_> {
+    fn compile(&mut self, statements: &[s_megalo_statement]) -> BLFLibResult {
+        let game_engine = &mut self.m_variant.m_game_engine;
+        game_engine.m_global_variable_metadata = Default::default();
+        game_engine.m_player_variable_metadata = Default::default();
+        game_engine.m_object_variable_metadata = Default::default();
+        game_engine.m_team_variable_metadata = Default::default();
+        game_engine.m_hud_widgets.clear();
+
+        // Declarations come first so that triggers can use variables declared further down.
+        for statement in statements {
+            if let e_megalo_statement::declaration(tokens) = &statement.m_statement {
+                self.declare(tokens).map_err(|error| at_line(statement.m_line, error))?;
+            }
+        }
+
+        for statement in statements {
+            match &statement.m_statement {
+                e_megalo_statement::declaration(_) => {}
+                e_megalo_statement::block(block) => {
+                    self.compile_block(statement.m_line, block)?;
+                }
+                _ => return Err(at_line(statement.m_line, "statements must be inside a do block".into())),
+            }
+        }
+
+        for (action_index, function_name, line) in std::mem::take(&mut self.m_function_calls) {
+            let trigger_index = *self.m_functions.get(&function_name)
+                .ok_or_else(|| at_line(line, format!("unknown function '{function_name}'").into()))?;
+            self.m_actions[action_index] = for_each_action(trigger_index);
+        }
+
+        if self.m_triggers.len() > k_maximum_triggers {
+            return Err(format!("the script has {} triggers, but at most {k_maximum_triggers} are allowed", self.m_triggers.len()).into());
+        }
+        if self.m_conditions.len() > k_maximum_conditions {
+            return Err(format!("the script has {} conditions, but at most {k_maximum_conditions} are allowed", self.m_conditions.len()).into());
+        }
+        if self.m_actions.len() > k_maximum_actions {
+            return Err(format!("the script has {} actions, but at most {k_maximum_actions} are allowed", self.m_actions.len()).into());
+        }
+
+        let event_trigger = |event: &str| self.m_event_triggers.get(event).copied().unwrap_or(-1);
+        let initialization_trigger_index = event_trigger("init");
+        let local_initialization_trigger_index = event_trigger("local init");
+        let host_migration_trigger_index = event_trigger("host migration");
+        let double_migration_trigger_index = event_trigger("double host migration");
+        let object_death_event_trigger_index = event_trigger("object death");
+        let local_trigger_index = event_trigger("local");
+        let pregame_trigger_index = event_trigger("pregame");
+
+        let game_engine = &mut self.m_variant.m_game_engine;
+        game_engine.m_conditions = std::mem::take(&mut self.m_conditions);
+        game_engine.m_actions = std::mem::take(&mut self.m_actions);
+        game_engine.m_triggers = std::mem::take(&mut self.m_triggers);
+        game_engine.m_initialization_trigger_index = initialization_trigger_index;
+        game_engine.m_local_initialization_trigger_index = local_initialization_trigger_index;
+        game_engine.m_host_migration_trigger_index = host_migration_trigger_index;
+        game_engine.m_double_migration_trigger_index = double_migration_trigger_index;
+        game_engine.m_object_death_event_trigger_index = object_death_event_trigger_index;
+        game_engine.m_local_trigger_index = local_trigger_index;
+        game_engine.m_pregame_trigger_index = pregame_trigger_index;
+
+        Ok(())
+    }
+
+    fn declare(&mut self, tokens: &[e_megalo_token]) -> BLFLibResult {
+        let mut reader = c_megalo_token_reader::new(tokens);
+        let variable = match reader.primary()? {
+            e_megalo_expression::path(path) => path,
+            expression => return Err(format!("can't declare {}", describe(&expression)).into()),
+        };
+
+        if let [widget] = variable.as_slice() {
+            if widget.m_name != "hud_widget" {
+                return Err(format!("can't declare {}", describe_path(&variable)).into());
+            }
+            reader.expect_keyword("at")?;
+            let position: e_megalo_widget_position = named_enum(&reader.expression()?, "HUD widget position")?;
+            reader.expect_end()?;
+
+            let hud_widgets = &mut self.m_variant.m_game_engine.m_hud_widgets;
+            check_declaration_index(widget.m_index, hud_widgets.len(), k_maximum_hud_widgets, "hud_widget")?;
+            hud_widgets.push(position);
+            return Ok(());
+        }
+
+        let [scope, member] = variable.as_slice() else {
+            return Err(format!("can't declare {}", describe_path(&variable)).into());
+        };
+        let scope = match (scope.m_name.as_str(), scope.m_index) {
+            ("global", None) => e_megalo_scope::global,
+            ("player", None) => e_megalo_scope::player,
+            ("object", None) => e_megalo_scope::object,
+            ("team", None) => e_megalo_scope::team,
+            _ => return Err(format!("unknown variable scope {}", describe_path(&variable[..1])).into()),
+        };
+        let kind = match member.m_name.as_str() {
+            "number" => e_megalo_variable_kind::number,
+            "timer" => e_megalo_variable_kind::timer,
+            "team" => e_megalo_variable_kind::team,
+            "player" => e_megalo_variable_kind::player,
+            "object" => e_megalo_variable_kind::object,
+            _ => return Err(format!("can't declare {}", describe_path(&variable)).into()),
+        };
+
+        let initial_value = if reader.try_symbol("=") { Some(reader.expression()?) } else { None };
+        let network_state = if reader.try_identifier("with") {
+            reader.expect_keyword("network")?;
+            reader.expect_keyword("priority")?;
+            let network_state: e_megalo_variable_network_state = named_enum(&reader.expression()?, "network priority")?;
+            Some(network_state)
+        } else {
+            None
+        };
+        reader.expect_end()?;
+
+        let count = self.variable_counts(scope)[kind as usize];
+        let limit = variable_limits(scope)[kind as usize];
+        check_declaration_index(member.m_index, count, limit, &describe_path(&variable))?;
+
+        if kind == e_megalo_variable_kind::timer && network_state.is_some() {
+            return Err("timers don't have a network priority".into());
+        }
+        if matches!(kind, e_megalo_variable_kind::player | e_megalo_variable_kind::object) && initial_value.is_some() {
+            return Err(format!("{} variables can't have an initial value", member.m_name).into());
+        }
+
+        let network_state = network_state.unwrap_or_default();
+        let constant_zero = c_custom_variable_reference {
+            m_type: e_custom_variable_type::constant,
+            m_immediate_value: Some(0),
+            ..Default::default()
+        };
+        let numeric_value = match (kind, &initial_value) {
+            (e_megalo_variable_kind::number | e_megalo_variable_kind::timer, Some(initial_value)) => self.custom_variable(initial_value)?,
+            _ => constant_zero,
+        };
+        let team_value = match (kind, &initial_value) {
+            (e_megalo_variable_kind::team, Some(initial_value)) => named_enum(initial_value, "team designator")?,
+            _ => e_multiplayer_team_designator::none,
+        };
+
+        let game_engine = &mut self.m_variant.m_game_engine;
+        match scope {
+            e_megalo_scope::global => declare_variable(&mut game_engine.m_global_variable_metadata, kind, numeric_value, team_value, network_state),
+            e_megalo_scope::player => declare_variable(&mut game_engine.m_player_variable_metadata, kind, numeric_value, team_value, network_state),
+            e_megalo_scope::object => declare_variable(&mut game_engine.m_object_variable_metadata, kind, numeric_value, team_value, network_state),
+            e_megalo_scope::team => declare_variable(&mut game_engine.m_team_variable_metadata, kind, numeric_value, team_value, network_state),
+        }
+
+        Ok(())
+    }
+
+    fn variable_counts(&self, scope: e_megalo_scope) -> [usize; 5] {
+        let game_engine = &self.m_variant.m_game_engine;
+        match scope {
+            e_megalo_scope::global => variable_counts(&game_engine.m_global_variable_metadata),
+            e_megalo_scope::player => variable_counts(&game_engine.m_player_variable_metadata),
+            e_megalo_scope::object => variable_counts(&game_engine.m_object_variable_metadata),
+            e_megalo_scope::team => variable_counts(&game_engine.m_team_variable_metadata),
+        }
+    }
+
+    fn variable_index(&self, scope: e_megalo_scope, kind: e_megalo_variable_kind, index: Option<i64>, variable: &str) -> BLFLibResult<u8> {
+        let count = self.variable_counts(scope)[kind as usize];
+        match index {
+            Some(index) if index >= 0 && (index as usize) < count => Ok(index as u8),
+            _ => Err(format!("{variable} isn't declared, {count} {scope:?} {kind:?} variables are declared").into()),
+        }
+    }
+
+    fn compile_block(&mut self, line: usize, block: &s_megalo_block) -> BLFLibResult<usize> {
+        let trigger_index = self.m_triggers.len();
+        let object_filter_index = match &block.m_object_filter {
+            Some(object_filter) => self.object_filter(object_filter).map_err(|error| at_line(line, error))?,
+            None => -1,
+        };
+        self.m_triggers.push(c_trigger {
+            m_execution_mode: block.m_execution_mode,
+            m_trigger_type: block.m_trigger_type,
+            m_object_filter_index: object_filter_index,
+            ..Default::default()
+        });
+
+        if let Some(function_name) = &block.m_function_name {
+            if self.m_functions.insert(function_name.clone(), trigger_index).is_some() {
+                return Err(at_line(line, format!("function '{function_name}' is defined more than once").into()));
+            }
+        }
+
+        let event = match block.m_trigger_type {
+            e_trigger_type::initialization => Some("init"),
+            e_trigger_type::local_initialization => Some("local init"),
+            e_trigger_type::host_migration if block.m_double_host_migration => Some("double host migration"),
+            e_trigger_type::host_migration => Some("host migration"),
+            e_trigger_type::object_death => Some("object death"),
+            e_trigger_type::local => Some("local"),
+            e_trigger_type::pregame => Some("pregame"),
+            e_trigger_type::normal | e_trigger_type::subroutine => None,
+        };
+        if let Some(event) = event {
+            if self.m_event_triggers.insert(event, trigger_index as i16).is_some() {
+                return Err(at_line(line, format!("there is more than one 'on {event}' trigger").into()));
+            }
+        }
+
+        let mut builder = s_trigger_builder::default();
+        self.compile_statements(&block.m_statements, &mut builder)?;
+
+        let first_condition = self.m_conditions.len();
+        let first_action = self.m_actions.len();
+        let trigger = &mut self.m_triggers[trigger_index];
+        trigger.m_first_condition = first_condition as u16;
+        trigger.m_condition_count = builder.m_conditions.len() as u16;
+        trigger.m_first_action = first_action as u16;
+        trigger.m_action_count = builder.m_actions.len() as u16;
+
+        self.m_conditions.extend(builder.m_conditions);
+        self.m_actions.extend(builder.m_actions);
+        self.m_function_calls.extend(builder.m_function_calls.into_iter()
+            .map(|(action_offset, function_name, line)| (first_action + action_offset, function_name, line)));
+
+        Ok(trigger_index)
+    }
+
+    fn compile_nested_block(&mut self, line: usize, block: &s_megalo_block) -> BLFLibResult<usize> {
+        if block.m_function_name.is_some() || block.m_trigger_type != e_trigger_type::normal {
+            return Err(at_line(line, "functions and events must be at the top level".into()));
+        }
+
+        let nested_block = s_megalo_block { m_trigger_type: e_trigger_type::subroutine, ..block.clone() };
+        self.compile_block(line, &nested_block)
+    }
+
+    fn compile_statements(&mut self, statements: &[s_megalo_statement], builder: &mut s_trigger_builder) -> BLFLibResult {
+        for (statement_index, statement) in statements.iter().enumerate() {
+            let line = statement.m_line;
+            match &statement.m_statement {
+                e_megalo_statement::declaration(_) => return Err(at_line(line, "declarations must be at the top level".into())),
+                e_megalo_statement::action(tokens) => {
+                    self.compile_action(line, tokens, builder).map_err(|error| at_line(line, error))?;
+                }
+                e_megalo_statement::block(block) => {
+                    let trigger_index = self.compile_nested_block(line, block)?;
+                    builder.m_actions.push(for_each_action(trigger_index));
+                }
+                // Conditions guard every action after them in their trigger,
+                // so an if block followed by more statements is given a trigger of its own.
+                e_megalo_statement::condition(_, _) if statement_index + 1 < statements.len() => {
+                    let block = s_megalo_block {
+                        m_trigger_type: e_trigger_type::normal,
+                        m_double_host_migration: false,
+                        m_function_name: None,
+                        m_execution_mode: e_trigger_execution_mode::general,
+                        m_object_filter: None,
+                        m_statements: vec![statement.clone()],
+                    };
+                    let trigger_index = self.compile_nested_block(line, &block)?;
+                    builder.m_actions.push(for_each_action(trigger_index));
+                }
+                e_megalo_statement::condition(tokens, body) => {
+                    self.compile_conditions(tokens, builder).map_err(|error| at_line(line, error))?;
+                    self.compile_statements(body, builder)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn compile_conditions(&mut self, tokens: &[e_megalo_token], builder: &mut s_trigger_builder) -> BLFLibResult {
+        let execute_before_action = builder.m_actions.len() as u16;
+
+        for term in split_top_level(tokens, "and")? {
+            let union_group = builder.m_union_group;
+            builder.m_union_group += 1;
+
+            for alternative in split_top_level(strip_parentheses(term), "or")? {
+                let negated = alternative[0].is_identifier("not");
+                let alternative = if negated { &alternative[1..] } else { alternative };
+                if alternative.is_empty() {
+                    return Err("'not' is missing an operand".into());
+                }
+
+                let mut condition = self.condition(alternative)?;
+                if condition.m_type != e_condition_type::none {
+                    condition.m_negated = negated;
+                    condition.m_union_group = union_group;
+                    condition.m_execute_before_action = execute_before_action;
+                }
+                builder.m_conditions.push(condition);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_declaration_index(index: Option<i64>, count: usize, limit: usize, variable: &str) -> BLFLibResult {
+    if index != Some(count as i64) {
+        return Err(format!("{variable} is declared out of order, the next index is {count}").into());
+    }
+    if count >= limit {
+        return Err(format!("{variable} can't be declared, at most {limit} are allowed").into());
+    }
+    Ok(())
+}
+
+fn variable_limits(scope: e_megalo_scope) -> [usize; 5] {
+    match scope {
+        e_megalo_scope::global => k_global_variable_limits,
+        e_megalo_scope::player => k_player_variable_limits,
+        e_megalo_scope::object => k_object_variable_limits,
+        e_megalo_scope::team => k_team_variable_limits,
+    }
+}
+
+fn declare_variable<const a: usize, const b: usize, const c: usize, const d: usize, const e: usize>(
+    metadata: &mut s_variable_metadata<a, b, c, d, e>,
+    kind: e_megalo_variable_kind,
+    numeric_value: c_custom_variable_reference,
+    team_value: e_multiplayer_team_designator,
+    network_state: e_megalo_variable_network_state,
+) {
+    match kind {
+        e_megalo_variable_kind::number => metadata.m_numeric_variables.push((numeric_value, network_state)),
+        e_megalo_variable_kind::timer => metadata.m_timer_variables.push(numeric_value),
+        e_megalo_variable_kind::team => metadata.m_team_variables.push((team_value, network_state)),
+        e_megalo_variable_kind::player => metadata.m_player_variables.push(network_state),
+        e_megalo_variable_kind::object => metadata.m_object_variables.push(network_state),
+    }
+}
+
+// References. Each returns None when the path isn't shaped like the kind of reference asked for,
+// and an error when it is but refers to something which doesn't exist.
+impl c_megalo_compiler<'_> {
+    fn explicit_player(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<c_explicit_player>> {
+        let explicit_player_type = match path {
+            [segment] => match (segment.m_name.as_str(), segment.m_index) {
+                ("no_player", None) => e_explicit_player_type::no_player,
+                ("current_player", None) => e_explicit_player_type::current,
+                ("local_player", None) => e_explicit_player_type::hud,
+                ("target_player", None) => e_explicit_player_type::hud_target,
+                ("killer_player", None) => e_explicit_player_type::killer,
+                ("player", Some(index)) if (0..16).contains(&index) => {
+                    FromPrimitive::from_i64(e_explicit_player_type::player_0 as i64 + index).unwrap()
+                }
+                ("player", Some(index)) => return Err(format!("player[{index}] is out of range, expected 0 to 15").into()),
+                _ => return Ok(None),
+            },
+            [scope, segment] if scope.m_name == "global" && scope.m_index.is_none() && segment.m_name == "player" => {
+                let index = self.variable_index(e_megalo_scope::global, e_megalo_variable_kind::player, segment.m_index, &describe_path(path))?;
+                FromPrimitive::from_u8(e_explicit_player_type::global_0 as u8 + index).unwrap()
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(c_explicit_player { m_explicit_player_type: explicit_player_type }))
+    }
+
+    fn explicit_object(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<c_explicit_object>> {
+        let explicit_object_type = match path {
+            [segment] => match (segment.m_name.as_str(), segment.m_index) {
+                ("no_object", None) => e_explicit_object_type::no_object,
+                ("current_object", None) => e_explicit_object_type::current,
+                ("target_object", None) => e_explicit_object_type::hud_target,
+                ("killed_object", None) => e_explicit_object_type::killed,
+                ("killer_object", None) => e_explicit_object_type::killer,
+                ("unknown_object_21", None) => e_explicit_object_type::unknown_21,
+                _ => return Ok(None),
+            },
+            [scope, segment] if scope.m_name == "global" && scope.m_index.is_none() && segment.m_name == "object" => {
+                let index = self.variable_index(e_megalo_scope::global, e_megalo_variable_kind::object, segment.m_index, &describe_path(path))?;
+                FromPrimitive::from_u8(e_explicit_object_type::global_0 as u8 + index).unwrap()
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(c_explicit_object { m_explicit_object_type: explicit_object_type }))
+    }
+
+    fn explicit_team(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<c_explicit_team>> {
+        let explicit_team_type = match path {
+            [segment] => match (segment.m_name.as_str(), segment.m_index) {
+                ("no_team", None) => e_explicit_team_type::none,
+                ("neutral_team", None) => e_explicit_team_type::neutral,
+                ("current_team", None) => e_explicit_team_type::current_team,
+                ("local_team", None) => e_explicit_team_type::local_team,
+                ("target_team", None) => e_explicit_team_type::target_team,
+                ("team", Some(index)) if (0..8).contains(&index) => {
+                    FromPrimitive::from_i64(e_explicit_team_type::team_0 as i64 + index).unwrap()
+                }
+                ("team", Some(index)) => return Err(format!("team[{index}] is out of range, expected 0 to 7").into()),
+                _ => return Ok(None),
+            },
+            [scope, segment] if scope.m_name == "global" && scope.m_index.is_none() && segment.m_name == "team" => {
+                let index = self.variable_index(e_megalo_scope::global, e_megalo_variable_kind::team, segment.m_index, &describe_path(path))?;
+                FromPrimitive::from_u8(e_explicit_team_type::global_0 as u8 + index).unwrap()
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(c_explicit_team { m_explicit_team_type: explicit_team_type }))
+    }
+
+    fn explicit_owner(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<e_explicit_owner>> {
+        if let Some(player) = self.explicit_player(path)? {
+            return Ok(Some(e_explicit_owner::player(player)));
+        }
+        if let Some(object) = self.explicit_object(path)? {
+            return Ok(Some(e_explicit_owner::object(object)));
+        }
+        Ok(self.explicit_team(path)?.map(e_explicit_owner::team))
+    }
+
+    // Splits owner.name[index] into its owner and index, where the owner is an explicit player, object or team.
+    fn member(&self, path: &[s_megalo_path_segment], member_name: &str, kind: e_megalo_variable_kind) -> BLFLibResult<Option<(e_explicit_owner, u8)>> {
+        let Some((member, owner)) = path.split_last() else {
+            return Ok(None);
+        };
+        if member.m_name != member_name || owner.is_empty() {
+            return Ok(None);
+        }
+        let Some(owner) = self.explicit_owner(owner)? else {
+            return Ok(None);
+        };
+
+        let index = self.variable_index(owner.scope(), kind, member.m_index, &describe_path(path))?;
+        Ok(Some((owner, index)))
+    }
+
+    fn player_reference(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<c_player_reference>> {
+        if let Some(player) = self.explicit_player(path)? {
+            return Ok(Some(c_player_reference { m_type: e_player_reference_type::global_player, m_player: Some(player), ..Default::default() }));
+        }
+
+        Ok(self.member(path, "player", e_megalo_variable_kind::player)?.map(|(owner, index)| match owner {
+            e_explicit_owner::player(player) => c_player_reference { m_type: e_player_reference_type::player_player, m_player: Some(player), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::object(object) => c_player_reference { m_type: e_player_reference_type::object_player, m_object: Some(object), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::team(team) => c_player_reference { m_type: e_player_reference_type::team_player, m_team: Some(team), m_variable_index: Some(index), ..Default::default() },
+        }))
+    }
+
+    fn object_reference(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<c_object_reference>> {
+        if let Some(object) = self.explicit_object(path)? {
+            return Ok(Some(c_object_reference { m_type: e_object_reference_type::global_object, m_object: Some(object), ..Default::default() }));
+        }
+
+        if let Some((biped, player_path)) = path.split_last() {
+            if biped.m_name == "biped" && biped.m_index.is_none() {
+                return Ok(self.player_reference(player_path)?.map(|player| c_object_reference {
+                    m_type: match player.m_type {
+                        e_player_reference_type::global_player => e_object_reference_type::player_biped,
+                        e_player_reference_type::player_player => e_object_reference_type::player_player_biped,
+                        e_player_reference_type::object_player => e_object_reference_type::object_player_biped,
+                        e_player_reference_type::team_player => e_object_reference_type::team_player_biped,
+                    },
+                    m_player: player.m_player,
+                    m_object: player.m_object,
+                    m_team: player.m_team,
+                    m_variable_index: player.m_variable_index,
+                }));
+            }
+        }
+
+        Ok(self.member(path, "object", e_megalo_variable_kind::object)?.map(|(owner, index)| match owner {
+            e_explicit_owner::player(player) => c_object_reference { m_type: e_object_reference_type::player_object, m_player: Some(player), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::object(object) => c_object_reference { m_type: e_object_reference_type::object_object, m_object: Some(object), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::team(team) => c_object_reference { m_type: e_object_reference_type::team_object, m_team: Some(team), m_variable_index: Some(index), ..Default::default() },
+        }))
+    }
+
+    fn team_reference(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<c_team_reference>> {
+        if let Some(team) = self.explicit_team(path)? {
+            return Ok(Some(c_team_reference { m_type: e_team_reference_type::global_team, m_team: Some(team), ..Default::default() }));
+        }
+
+        if let Some((team, owner)) = path.split_last() {
+            if team.m_name == "team" && team.m_index.is_none() {
+                if let Some(player) = self.explicit_player(owner)? {
+                    return Ok(Some(c_team_reference { m_type: e_team_reference_type::player_owner_team, m_player: Some(player), ..Default::default() }));
+                }
+                if let Some(object) = self.explicit_object(owner)? {
+                    return Ok(Some(c_team_reference { m_type: e_team_reference_type::object_owner_team, m_object: Some(object), ..Default::default() }));
+                }
+                return Ok(None);
+            }
+        }
+
+        Ok(self.member(path, "team", e_megalo_variable_kind::team)?.map(|(owner, index)| match owner {
+            e_explicit_owner::player(player) => c_team_reference { m_type: e_team_reference_type::player_team, m_player: Some(player), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::object(object) => c_team_reference { m_type: e_team_reference_type::object_team, m_object: Some(object), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::team(team) => c_team_reference { m_type: e_team_reference_type::team_team, m_team: Some(team), m_variable_index: Some(index), ..Default::default() },
+        }))
+    }
+
+    fn custom_timer_reference(&self, path: &[s_megalo_path_segment]) -> BLFLibResult<Option<c_custom_timer_reference>> {
+        if let [scope, timer] = path {
+            if scope.m_name == "game" && scope.m_index.is_none() && timer.m_index.is_none() {
+                let timer_type = match timer.m_name.as_str() {
+                    "round_timer" => e_custom_timer_type::round,
+                    "sudden_death_timer" => e_custom_timer_type::sudden_death,
+                    "grace_period_timer" => e_custom_timer_type::grace_period,
+                    _ => return Ok(None),
+                };
+                return Ok(Some(c_custom_timer_reference { m_type: timer_type, ..Default::default() }));
+            }
+            if scope.m_name == "global" && scope.m_index.is_none() && timer.m_name == "timer" {
+                let index = self.variable_index(e_megalo_scope::global, e_megalo_variable_kind::timer, timer.m_index, &describe_path(path))?;
+                return Ok(Some(c_custom_timer_reference { m_type: e_custom_timer_type::global, m_variable_index: Some(index), ..Default::default() }));
+            }
+        }
+
+        Ok(self.member(path, "timer", e_megalo_variable_kind::timer)?.map(|(owner, index)| match owner {
+            e_explicit_owner::player(player) => c_custom_timer_reference { m_type: e_custom_timer_type::player, m_player: Some(player), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::object(object) => c_custom_timer_reference { m_type: e_custom_timer_type::object, m_object: Some(object), m_variable_index: Some(index), ..Default::default() },
+            e_explicit_owner::team(team) => c_custom_timer_reference { m_type: e_custom_timer_type::team, m_team: Some(team), m_variable_index: Some(index), ..Default::default() },
+        }))
+    }
+
+    fn custom_variable_reference(&self, expression: &e_megalo_expression) -> BLFLibResult<Option<c_custom_variable_reference>> {
+        let path = match expression {
+            e_megalo_expression::number(_) => {
+                return Ok(Some(c_custom_variable_reference {
+                    m_type: e_custom_variable_type::constant,
+                    m_immediate_value: Some(integer(expression, i16::MIN as i64, i16::MAX as i64)?),
+                    ..Default::default()
+                }));
+            }
+            e_megalo_expression::path(path) => path.as_slice(),
+            _ => return Ok(None),
+        };
+
+        match path {
+            [option] if option.m_name == "script_option" => {
+                let option_count = self.m_variant.m_user_defined_options.len();
+                return match option.m_index {
+                    Some(index) if index >= 0 && (index as usize) < option_count => Ok(Some(c_custom_variable_reference {
+                        m_type: e_custom_variable_type::option,
+                        m_option_index: Some(index as u8),
+                        ..Default::default()
+                    })),
+                    _ => Err(format!("{} doesn't exist, the variant has {option_count} script options", describe_path(path)).into()),
+                };
+            }
+            [scope, number] if scope.m_name == "global" && scope.m_index.is_none() && number.m_name == "number" => {
+                let index = self.variable_index(e_megalo_scope::global, e_megalo_variable_kind::number, number.m_index, &describe_path(path))?;
+                return Ok(Some(c_custom_variable_reference { m_type: e_custom_variable_type::global_number, m_variable_index: Some(index), ..Default::default() }));
+            }
+            [scope, setting] if scope.m_name == "game" && scope.m_index.is_none() && setting.m_index.is_none() => {
+                return Ok(parse_enum::<e_custom_variable_type>(&setting.m_name)
+                    .filter(|variable_type| *variable_type as u8 >= e_custom_variable_type::round_index as u8)
+                    .map(|variable_type| c_custom_variable_reference { m_type: variable_type, ..Default::default() }));
+            }
+            _ => {}
+        }
+
+        let Some((member, owner)) = path.split_last() else {
+            return Ok(None);
+        };
+
+        match (member.m_name.as_str(), member.m_index) {
+            ("score", None) => {
+                if let Some(player) = self.explicit_player(owner)? {
+                    return Ok(Some(c_custom_variable_reference { m_type: e_custom_variable_type::player_score, m_player: Some(player), ..Default::default() }));
+                }
+                Ok(self.explicit_team(owner)?.map(|team| c_custom_variable_reference { m_type: e_custom_variable_type::team_score, m_team: Some(team), ..Default::default() }))
+            }
+            ("money", None) | ("rating", None) => {
+                let variable_type = if member.m_name == "money" { e_custom_variable_type::player_money } else { e_custom_variable_type::player_rating };
+                Ok(self.explicit_player(owner)?.map(|player| c_custom_variable_reference { m_type: variable_type, m_player: Some(player), ..Default::default() }))
+            }
+            ("spawn_sequence", None) => {
+                Ok(self.explicit_object(owner)?.map(|object| c_custom_variable_reference { m_type: e_custom_variable_type::spawn_object, m_object: Some(object), ..Default::default() }))
+            }
+            ("script_stat", index) => {
+                let statistic_count = self.m_variant.m_game_engine.m_statistics.len();
+                let statistic_index = match index {
+                    Some(index) if index >= 0 && (index as usize) < statistic_count => index as u8,
+                    _ => return Err(format!("{} doesn't exist, the variant has {statistic_count} script stats", describe_path(path)).into()),
+                };
+                if let Some(player) = self.explicit_player(owner)? {
+                    return Ok(Some(c_custom_variable_reference { m_type: e_custom_variable_type::player_stat, m_player: Some(player), m_statistic_index: Some(statistic_index), ..Default::default() }));
+                }
+                Ok(self.explicit_team(owner)?.map(|team| c_custom_variable_reference { m_type: e_custom_variable_type::team_stat, m_team: Some(team), m_statistic_index: Some(statistic_index), ..Default::default() }))
+            }
+            _ => Ok(self.member(path, "number", e_megalo_variable_kind::number)?.map(|(owner, index)| match owner {
+                e_explicit_owner::player(player) => c_custom_variable_reference { m_type: e_custom_variable_type::player_number, m_player: Some(player), m_variable_index: Some(index), ..Default::default() },
+                e_explicit_owner::object(object) => c_custom_variable_reference { m_type: e_custom_variable_type::object_number, m_object: Some(object), m_variable_index: Some(index), ..Default::default() },
+                e_explicit_owner::team(team) => c_custom_variable_reference { m_type: e_custom_variable_type::team_number, m_team: Some(team), m_variable_index: Some(index), ..Default::default() },
+            })),
+        }
+    }
+
+    fn path<'e>(&self, expression: &'e e_megalo_expression) -> &'e [s_megalo_path_segment] {
+        match expression {
+            e_megalo_expression::path(path) => path,
+            _ => &[],
+        }
+    }
+
+    fn player(&self, expression: &e_megalo_expression) -> BLFLibResult<c_player_reference> {
+        self.player_reference(self.path(expression))?
+            .ok_or_else(|| format!("expected a player but found {}", describe(expression)).into())
+    }
+
+    fn object(&self, expression: &e_megalo_expression) -> BLFLibResult<c_object_reference> {
+        self.object_reference(self.path(expression))?
+            .ok_or_else(|| format!("expected an object but found {}", describe(expression)).into())
+    }
+
+    fn team(&self, expression: &e_megalo_expression) -> BLFLibResult<c_team_reference> {
+        self.team_reference(self.path(expression))?
+            .ok_or_else(|| format!("expected a team but found {}", describe(expression)).into())
+    }
+
+    fn timer(&self, expression: &e_megalo_expression) -> BLFLibResult<c_custom_timer_reference> {
+        self.custom_timer_reference(self.path(expression))?
+            .ok_or_else(|| format!("expected a timer but found {}", describe(expression)).into())
+    }
+
+    fn custom_variable(&self, expression: &e_megalo_expression) -> BLFLibResult<c_custom_variable_reference> {
+        self.custom_variable_reference(expression)?
+            .ok_or_else(|| format!("expected a number but found {}", describe(expression)).into())
+    }
+
+    fn target_player(&self, call: &s_megalo_call) -> BLFLibResult<c_player_reference> {
+        self.player(&e_megalo_expression::path(call.m_target.clone()))
+    }
+
+    fn target_object(&self, call: &s_megalo_call) -> BLFLibResult<c_object_reference> {
+        self.object(&e_megalo_expression::path(call.m_target.clone()))
+    }
+
+    fn target_team(&self, call: &s_megalo_call) -> BLFLibResult<c_team_reference> {
+        self.team(&e_megalo_expression::path(call.m_target.clone()))
+    }
+
+    fn target_timer(&self, call: &s_megalo_call) -> BLFLibResult<c_custom_timer_reference> {
+        self.timer(&e_megalo_expression::path(call.m_target.clone()))
+    }
+
+    fn variant_variable(&self, expression: &e_megalo_expression) -> BLFLibResult<s_variant_variable> {
+        if let Some(custom_variable) = self.custom_variable_reference(expression)? {
+            return Ok(s_variant_variable { m_type: e_variable_type::custom_variable, m_custom_variable: Some(custom_variable), ..Default::default() });
+        }
+
+        let path = self.path(expression);
+        if let Some(timer) = self.custom_timer_reference(path)? {
+            return Ok(s_variant_variable { m_type: e_variable_type::custom_timer, m_custom_timer: Some(timer), ..Default::default() });
+        }
+        if let Some(player) = self.player_reference(path)? {
+            return Ok(s_variant_variable { m_type: e_variable_type::player, m_player: Some(player), ..Default::default() });
+        }
+        if let Some(object) = self.object_reference(path)? {
+            return Ok(s_variant_variable { m_type: e_variable_type::object, m_object: Some(object), ..Default::default() });
+        }
+        if let Some(team) = self.team_reference(path)? {
+            return Ok(s_variant_variable { m_type: e_variable_type::team, m_team: Some(team), ..Default::default() });
+        }
+
+        Err(format!("expected a variable but found {}", describe(expression)).into())
+    }
+
+    fn replaceable_token(&self, expression: &e_megalo_expression) -> BLFLibResult<c_replaceable_token> {
+        if name(expression) == Some("none") {
+            return Ok(c_replaceable_token { m_type: e_replaceable_token_type::none, ..Default::default() });
+        }
+
+        let variable = self.variant_variable(expression)?;
+        Ok(c_replaceable_token {
+            m_type: match variable.m_type {
+                e_variable_type::custom_variable => e_replaceable_token_type::custom_variable,
+                e_variable_type::player => e_replaceable_token_type::player,
+                e_variable_type::object => e_replaceable_token_type::object,
+                e_variable_type::team => e_replaceable_token_type::team,
+                e_variable_type::custom_timer => e_replaceable_token_type::custom_timer,
+            },
+            m_player: variable.m_player,
+            m_object: variable.m_object,
+            m_team: variable.m_team,
+            m_custom_timer: variable.m_custom_timer,
+            m_custom_variable: variable.m_custom_variable,
+        })
+    }
+
+    fn team_or_player_target(&self, expression: &e_megalo_expression) -> BLFLibResult<s_team_or_player_target> {
+        if name(expression) == Some("everyone") {
+            return Ok(s_team_or_player_target { m_target: e_action_team_or_player_target::everyone, ..Default::default() });
+        }
+
+        let path = self.path(expression);
+        if let Some(player) = self.player_reference(path)? {
+            return Ok(s_team_or_player_target { m_target: e_action_team_or_player_target::player, m_player: Some(player), ..Default::default() });
+        }
+        if let Some(team) = self.team_reference(path)? {
+            return Ok(s_team_or_player_target { m_target: e_action_team_or_player_target::team, m_team: Some(team), ..Default::default() });
+        }
+
+        Err(format!("expected a player, a team or everyone but found {}", describe(expression)).into())
+    }
+
+    fn target(&self, call: &s_megalo_call) -> BLFLibResult<s_team_or_player_target> {
+        self.team_or_player_target(&e_megalo_expression::path(call.m_target.clone()))
+    }
+
+    // A player filter takes the rest of the arguments, which are either a filter type or mod_player, <player>, <number>.
+    fn player_filter(&self, arguments: &[e_megalo_expression]) -> BLFLibResult<c_player_filter_modifier> {
+        match arguments {
+            [modifier, player, variable] if name(modifier) == Some("mod_player") => Ok(c_player_filter_modifier {
+                m_type: e_player_filter_type::specific_player,
+                m_player: Some(self.player(player)?),
+                m_variable: Some(self.custom_variable(variable)?),
+            }),
+            [filter] => {
+                let filter_type: e_player_filter_type = named_enum(filter, "player filter")?;
+                if filter_type == e_player_filter_type::specific_player {
+                    return Err("specific players are filtered with mod_player, <player>, <number>".into());
+                }
+                Ok(c_player_filter_modifier { m_type: filter_type, ..Default::default() })
+            }
+            _ => Err("expected a player filter, or mod_player, <player>, <number>".into()),
+        }
+    }
+
+    fn hud_meter_input(&self, arguments: &[e_megalo_expression]) -> BLFLibResult<c_megalogamengine_hud_meter_input> {
+        match arguments {
+            [input] if name(input) == Some("none") => Ok(c_megalogamengine_hud_meter_input::default()),
+            [input, variable_1, variable_2] if name(input) == Some("number") => Ok(c_megalogamengine_hud_meter_input {
+                m_type: e_megalogamengine_hud_meter_input_type::number,
+                m_variable_1: Some(self.custom_variable(variable_1)?),
+                m_variable_2: Some(self.custom_variable(variable_2)?),
+                ..Default::default()
+            }),
+            [input, timer] if name(input) == Some("timer") => Ok(c_megalogamengine_hud_meter_input {
+                m_type: e_megalogamengine_hud_meter_input_type::timer,
+                m_timer: Some(self.timer(timer)?),
+                ..Default::default()
+            }),
+            _ => Err("expected none, number, <number>, <number> or timer, <timer>".into()),
+        }
+    }
+
+    fn hud_widget(&self, call: &s_megalo_call) -> BLFLibResult<i8> {
+        let widget_count = self.m_variant.m_game_engine.m_hud_widgets.len();
+        match call.m_target.as_slice() {
+            [widget] if widget.m_name == "hud_widget" => match widget.m_index {
+                Some(index) if index >= 0 && (index as usize) < widget_count => Ok(index as i8),
+                _ => Err(format!("{} isn't declared, {widget_count} HUD widgets are declared", describe_path(&call.m_target)).into()),
+            },
+            _ => Err(format!("{}() must be called on a hud_widget", call.m_name).into()),
+        }
+    }
+
+    // An object's timer, written <object>.timer[<index>], or none.
+    fn object_timer(&self, expression: &e_megalo_expression) -> BLFLibResult<i8> {
+        if name(expression) == Some("none") {
+            return Ok(-1);
+        }
+
+        match self.path(expression).last() {
+            Some(timer) if timer.m_name == "timer" => match timer.m_index {
+                Some(index) if (0..4).contains(&index) => Ok(index as i8),
+                _ => Err(format!("{} is out of range, objects have at most 4 timers", describe(expression)).into()),
+            },
+            _ => Err(format!("expected an object timer but found {}", describe(expression)).into()),
+        }
+    }
+
+    fn object_type(&mut self, expression: &e_megalo_expression) -> BLFLibResult<c_object_type_reference> {
+        if name(expression) == Some("none") {
+            return Ok(c_object_type_reference { m_object_type_index: -1 });
+        }
+
+        match indexed_name(expression, "object_type") {
+            Some(index) if index >= 0 && (index as usize) < k_maximum_object_types - 1 => {
+                self.m_variant.m_game_engine.m_objects_used[index as usize] = true;
+                Ok(c_object_type_reference { m_object_type_index: index as i16 })
+            }
+            Some(index) => Err(format!("object_type[{index}] is out of range").into()),
+            None => Err(format!("expected an object type but found {}", describe(expression)).into()),
+        }
+    }
+
+    // An object filter, written as its label string, object_filter[<index>] or none.
+    fn object_filter(&self, expression: &e_megalo_expression) -> BLFLibResult<i8> {
+        let object_filters = &self.m_variant.m_game_engine.m_object_filters;
+        if name(expression) == Some("none") {
+            return Ok(-1);
+        }
+
+        let filter_index = match expression {
+            e_megalo_expression::string(label) => object_filters.iter()
+                .position(|filter| self.m_variant.m_script_strings.get_string(k_script_string_language_index, filter.m_label_string_index as usize) == Some(label.as_str()))
+                .ok_or_else(|| BLFLibError::from(format!("the variant has no object filter labelled {label:?}")))?,
+            _ => match indexed_name(expression, "object_filter") {
+                Some(index) if index >= 0 && (index as usize) < object_filters.len() => index as usize,
+                Some(index) => return Err(format!("object_filter[{index}] doesn't exist, the variant has {} object filters", object_filters.len()).into()),
+                None => return Err(format!("expected an object filter but found {}", describe(expression)).into()),
+            },
+        };
+
+        Ok(filter_index as i8)
+    }
+
+    // A script string, given as a string or script_string[<index>], followed by up to three tokens.
+    fn dynamic_string(&mut self, arguments: &[e_megalo_expression]) -> BLFLibResult<c_dynamic_string> {
+        let Some((string, tokens)) = arguments.split_first() else {
+            return Err("expected a string".into());
+        };
+        if tokens.len() > k_maximum_dynamic_string_tokens {
+            return Err(format!("strings can have at most {k_maximum_dynamic_string_tokens} tokens").into());
+        }
+
+        let script_strings = &mut self.m_variant.m_script_strings;
+        let string_index = match string {
+            e_megalo_expression::string(string) => match script_strings.find_string(k_script_string_language_index, string) {
+                Some(string_index) => string_index,
+                None => script_strings.add_string(string)?,
+            },
+            _ => match indexed_name(string, "script_string") {
+                Some(index) if index >= 0 && (index as usize) < script_strings.get_string_count() => index as usize,
+                Some(index) => return Err(format!("script_string[{index}] doesn't exist, the variant has {} script strings", script_strings.get_string_count()).into()),
+                None => return Err(format!("expected a string but found {}", describe(string)).into()),
+            },
+        };
+
+        Ok(c_dynamic_string {
+            m_string_index: string_index as u8,
+            m_tokens: tokens.iter().map(|token| self.replaceable_token(token)).collect::<BLFLibResult<Vec<c_replaceable_token>>>()?,
+        })
+    }
+}
+
+// Conditions.
+impl c_megalo_compiler<'_> {
+    fn condition(&mut self, tokens: &[e_megalo_token]) -> BLFLibResult<c_condition> {
+        if let Some(position) = find_top_level(tokens, |token| k_comparison_operators.iter().any(|operator| token.is_symbol(operator))) {
+            let parameters = s_condition_if_parameters {
+                m_left: self.variant_variable(&parse_expression(&tokens[..position])?)?,
+                m_right: self.variant_variable(&parse_expression(&tokens[position + 1..])?)?,
+                m_comparison: numeric_comparison(symbol_at(tokens, position))?,
+            };
+            return Ok(c_condition { m_type: e_condition_type::r#if, m_if_parameters: Some(parameters), ..Default::default() });
+        }
+
+        let expression = parse_expression(tokens)?;
+        if name(&expression) == Some("none") {
+            return Ok(c_condition::default());
+        }
+        let e_megalo_expression::call(call) = expression else {
+            return Err(format!("expected a condition but found {}", describe(&expression)).into());
+        };
+
+        let mut condition = c_condition::default();
+        match call.m_name.as_str() {
+            "shape_contains" => {
+                expect_arguments(&call, &[1])?;
+                condition.m_type = e_condition_type::object_in_area;
+                condition.m_object_in_area_parameters = Some(s_condition_object_in_area_parameters {
+                    m_object_reference_1: self.object(&call.m_arguments[0])?,
+                    m_object_reference_2: self.target_object(&call)?,
+                });
+            }
+            "killer_type_is" => {
+                expect_arguments(&call, &[1])?;
+                let mut killer_type = e_player_death_killer_type_flags::default();
+                for flag in flag_names(&call.m_arguments[0])? {
+                    match flag {
+                        "environment" => killer_type.environment = true,
+                        "suicide" => killer_type.suicide = true,
+                        "enemy" => killer_type.enemy = true,
+                        "betrayal" => killer_type.betrayal = true,
+                        "quit_game" => killer_type.quit_game = true,
+                        _ => return Err(format!("{flag} is not a killer type").into()),
+                    }
+                }
+                condition.m_type = e_condition_type::player_died;
+                condition.m_player_died_parameters = Some(s_condition_player_died_parameters {
+                    m_player: self.target_player(&call)?,
+                    m_killer_type: killer_type,
+                });
+            }
+            "has_alliance_status" => {
+                expect_arguments(&call, &[2])?;
+                condition.m_type = e_condition_type::team_disposition;
+                condition.m_team_disposition_parameters = Some(s_condition_team_disposition_parameters {
+                    m_team_1: self.target_team(&call)?,
+                    m_team_2: self.team(&call.m_arguments[0])?,
+                    m_disposition: named_enum(&call.m_arguments[1], "disposition")?,
+                });
+            }
+            "is_zero" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::timer_expired;
+                condition.m_timer_expired_parameters = Some(s_condition_timer_expired_parameters { m_timer: self.target_timer(&call)? });
+            }
+            "is_of_type" => {
+                expect_arguments(&call, &[1])?;
+                condition.m_type = e_condition_type::object_is_type;
+                condition.m_object_is_type_parameters = Some(s_condition_object_is_type_parameters {
+                    m_object: self.target_object(&call)?,
+                    m_object_type: self.object_type(&call.m_arguments[0])?,
+                });
+            }
+            "has_any_players" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::team_is_active;
+                condition.m_team_is_active_parameters = Some(s_condition_team_is_active_parameters { m_team: self.target_team(&call)? });
+            }
+            "is_out_of_bounds" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::object_out_of_bounds;
+                condition.m_object_out_of_bounds_parameters = Some(s_condition_object_out_of_bounds_parameters { m_object: self.target_object(&call)? });
+            }
+            "is_fireteam_leader" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::player_is_fire_team_leader;
+                condition.m_player_is_fire_team_leader_parameters = Some(s_condition_player_is_fire_team_leader_parameters { m_player: self.target_player(&call)? });
+            }
+            "assisted_kill_of" => {
+                expect_arguments(&call, &[1])?;
+                condition.m_type = e_condition_type::player_assisted_with_kill;
+                condition.m_player_assisted_with_kill_parameters = Some(s_condition_player_assisted_with_kill_parameters {
+                    m_player_1: self.target_player(&call)?,
+                    m_player_2: self.player(&call.m_arguments[0])?,
+                });
+            }
+            "has_forge_label" => {
+                expect_arguments(&call, &[1])?;
+                condition.m_type = e_condition_type::object_matches_filter;
+                condition.m_object_matches_filter_parameters = Some(s_condition_object_matches_filter_parameters {
+                    m_object: self.target_object(&call)?,
+                    m_filter_index: self.object_filter(&call.m_arguments[0])?,
+                });
+            }
+            "is_not_respawning" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::player_is_active;
+                condition.m_player_is_active_parameters = Some(s_condition_player_is_active_parameters { m_player: self.target_player(&call)? });
+            }
+            "is_in_use" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::equipment_is_active;
+                condition.m_equipment_is_active_parameters = Some(s_condition_equipment_is_active_parameters { m_object: self.target_object(&call)? });
+            }
+            "is_spartan" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::player_is_spartan;
+                condition.m_player_is_spartan_parameters = Some(s_condition_player_is_spartan_parameters { m_player: self.target_player(&call)? });
+            }
+            "is_elite" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::player_is_elite;
+                condition.m_player_is_elite_parameters = Some(s_condition_player_is_elite_parameters { m_player: self.target_player(&call)? });
+            }
+            "is_monitor" => {
+                expect_arguments(&call, &[0])?;
+                condition.m_type = e_condition_type::player_is_editor;
+                condition.m_player_is_editor_parameters = Some(s_condition_player_is_editor_parameters { m_player: self.target_player(&call)? });
+            }
+            "is_forge" => {
+                expect_arguments(&call, &[0])?;
+                expect_game_target(&call)?;
+                condition.m_type = e_condition_type::game_is_forge;
+                condition.m_game_is_forge_parameters = Some(s_condition_game_is_forge_parameters {});
+            }
+            _ => return Err(format!("unknown condition {}()", call.m_name).into()),
+        }
+
+        Ok(condition)
+    }
+}
+
+// Actions.
+impl c_megalo_compiler<'_> {
+    fn compile_action(&mut self, line: usize, tokens: &[e_megalo_token], builder: &mut s_trigger_builder) -> BLFLibResult {
+        let assignment = find_top_level(tokens, |token| k_assignment_operators.iter().any(|operator| token.is_symbol(operator)));
+
+        let action = match assignment {
+            Some(position) => {
+                let variable = parse_expression(&tokens[..position])?;
+                let operator = symbol_at(tokens, position);
+                match parse_expression(&tokens[position + 1..])? {
+                    e_megalo_expression::call(call) if operator == "=" => self.getter_action(&variable, &call)?,
+                    e_megalo_expression::call(call) => return Err(format!("the result of {}() can only be assigned with =", call.m_name).into()),
+                    value => self.assignment_action(&variable, math_operation(operator)?, &value)?,
+                }
+            }
+            None => match parse_expression(tokens)? {
+                e_megalo_expression::call(call) => match self.call_action(&call)? {
+                    Some(action) => action,
+                    None => {
+                        builder.m_function_calls.push((builder.m_actions.len(), call.m_name.clone(), line));
+                        for_each_action(0)
+                    }
+                },
+                expression => return Err(format!("expected an action but found {}", describe(&expression)).into()),
+            },
+        };
+
+        builder.m_actions.push(action);
+        Ok(())
+    }
+
+    fn assignment_action(&mut self, variable: &e_megalo_expression, operation: e_math_operation, value: &e_megalo_expression) -> BLFLibResult<c_action> {
+        if let Some((member, owner)) = self.path(variable).split_last() {
+            let owner_expression = e_megalo_expression::path(owner.to_vec());
+            match (member.m_name.as_str(), member.m_index) {
+                ("score", None) => return Ok(c_action {
+                    m_type: e_action_type::set_score,
+                    m_set_score_parameters: Some(s_action_set_score_parameters {
+                        m_target: self.team_or_player_target(&owner_expression)?,
+                        m_operation: operation,
+                        m_variable: self.custom_variable(value)?,
+                    }),
+                    ..Default::default()
+                }),
+                ("money", None) => return Ok(c_action {
+                    m_type: e_action_type::player_adjust_money,
+                    m_player_adjust_money_parameters: Some(s_action_player_adjust_money_parameters {
+                        m_player: self.player(&owner_expression)?,
+                        m_math_operation: operation,
+                        m_variable: self.custom_variable(value)?,
+                    }),
+                    ..Default::default()
+                }),
+                ("shields", None) => return Ok(c_action {
+                    m_type: e_action_type::object_adjust_shield,
+                    m_object_adjust_shield_parameters: Some(s_action_object_adjust_shield_parameters {
+                        m_object: self.object(&owner_expression)?,
+                        m_operation: operation,
+                        m_variable: self.custom_variable(value)?,
+                    }),
+                    ..Default::default()
+                }),
+                ("health", None) => return Ok(c_action {
+                    m_type: e_action_type::object_adjust_health,
+                    m_object_adjust_health_parameters: Some(s_action_object_adjust_health_parameters {
+                        m_object: self.object(&owner_expression)?,
+                        m_operation: operation,
+                        m_variable: self.custom_variable(value)?,
+                    }),
+                    ..Default::default()
+                }),
+                ("max_shields", None) => return Ok(c_action {
+                    m_type: e_action_type::object_adjust_maximum_shield,
+                    m_object_adjust_maximum_shield_parameters: Some(s_action_object_adjust_maximum_shield_parameters {
+                        m_object: self.object(&owner_expression)?,
+                        m_operation: operation,
+                        m_variable: self.custom_variable(value)?,
+                    }),
+                    ..Default::default()
+                }),
+                ("max_health", None) => return Ok(c_action {
+                    m_type: e_action_type::object_adjust_maximum_health,
+                    m_object_adjust_maximum_health_parameters: Some(s_action_object_adjust_maximum_health_parameters {
+                        m_object: self.object(&owner_expression)?,
+                        m_operation: operation,
+                        m_variable: self.custom_variable(value)?,
+                    }),
+                    ..Default::default()
+                }),
+                ("frag_grenades" | "plasma_grenades", None) => {
+                    let player = match owner.split_last() {
+                        Some((biped, player)) if biped.m_name == "biped" && biped.m_index.is_none() => e_megalo_expression::path(player.to_vec()),
+                        _ => return Err(format!("grenades belong to a player's biped, not {}", describe(&owner_expression)).into()),
+                    };
+                    return Ok(c_action {
+                        m_type: e_action_type::adjust_grenades,
+                        m_adjust_grenades_parameters: Some(s_action_adjust_grenades_parameters {
+                            m_player: self.player(&player)?,
+                            m_grenade_type: if member.m_name == "frag_grenades" { e_grenade_type::frag_grenade } else { e_grenade_type::plasma_grenade },
+                            m_math_operation: operation,
+                            m_variable: self.custom_variable(value)?,
+                        }),
+                        ..Default::default()
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(c_action {
+            m_type: e_action_type::set,
+            m_set_parameters: Some(s_action_set_parameters {
+                m_variable_1: self.variant_variable(variable)?,
+                m_variable_2: self.variant_variable(value)?,
+                m_operation: operation,
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn getter_action(&mut self, variable: &e_megalo_expression, call: &s_megalo_call) -> BLFLibResult<c_action> {
+        let arguments = &call.m_arguments;
+
+        Ok(match call.m_name.as_str() {
+            "try_get_carrier" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::get_player_holding_object,
+                    m_get_player_holding_object_parameters: Some(s_action_get_player_holding_object_parameters {
+                        m_object: self.target_object(call)?,
+                        m_player: self.player(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "rand" => {
+                expect_arguments(call, &[1])?;
+                expect_no_target(call)?;
+                c_action {
+                    m_type: e_action_type::random,
+                    m_random_parameters: Some(s_action_random_parameters {
+                        m_variable_1: self.custom_variable(variable)?,
+                        m_variable_2: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_orientation" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::object_get_orientation,
+                    m_object_get_orientation_parameters: Some(s_action_object_get_orientation_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_speed" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::object_get_velocity,
+                    m_object_get_velocity_parameters: Some(s_action_object_get_velocity_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "try_get_killer" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_death_get_killing_player,
+                    m_player_death_get_killing_player_parameters: Some(s_action_player_death_get_killing_player_parameters {
+                        m_player_1: self.target_player(call)?,
+                        m_player_2: self.player(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "try_get_death_damage_type" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_death_get_damage_type,
+                    m_player_death_get_damage_type_parameters: Some(s_action_player_death_get_damage_type_parameters {
+                        m_player: self.target_player(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "try_get_death_damage_mod" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_death_get_special_type,
+                    m_player_death_get_special_type_parameters: Some(s_action_player_death_get_special_type_parameters {
+                        m_player: self.target_player(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_scoreboard_pos" => {
+                expect_arguments(call, &[0])?;
+                match self.player_reference(&call.m_target)? {
+                    Some(player) => c_action {
+                        m_type: e_action_type::player_get_place,
+                        m_player_get_place_parameters: Some(s_action_player_get_place_parameters {
+                            m_player: player,
+                            m_variable: self.custom_variable(variable)?,
+                        }),
+                        ..Default::default()
+                    },
+                    None => c_action {
+                        m_type: e_action_type::team_get_place,
+                        m_team_get_place_parameters: Some(s_action_team_get_place_parameters {
+                            m_team: self.target_team(call)?,
+                            m_variable: self.custom_variable(variable)?,
+                        }),
+                        ..Default::default()
+                    },
+                }
+            }
+            "get_spree_count" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_get_killing_spree_count,
+                    m_player_get_killing_spree_count_parameters: Some(s_action_player_get_killing_spree_count_parameters {
+                        m_player: self.target_player(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_vehicle" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_get_vehicle,
+                    m_player_get_vehicle_parameters: Some(s_action_player_get_vehicle_parameters {
+                        m_player: self.target_player(call)?,
+                        m_object: self.object(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_shields" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::object_get_shield,
+                    m_object_get_shield_parameters: Some(s_action_object_get_shield_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_health" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::object_get_health,
+                    m_object_get_health_parameters: Some(s_action_object_get_health_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_fireteam" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_get_fireteam_index,
+                    m_player_get_fireteam_index_parameters: Some(s_action_player_get_fireteam_index_parameters {
+                        m_player: self.target_player(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_distance_to" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::object_get_distance,
+                    m_object_get_distance_parameters: Some(s_action_object_get_distance_parameters {
+                        m_object_1: self.target_object(call)?,
+                        m_object_2: self.object(&arguments[0])?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_device_power" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::device_get_power,
+                    m_device_get_power_parameters: Some(s_action_device_get_power_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_device_position" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::device_get_position,
+                    m_device_get_position_parameters: Some(s_action_device_get_position_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_weapon" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::player_get_weapon,
+                    m_player_get_weapon_parameters: Some(s_action_player_get_weapon_parameters {
+                        m_player: self.target_player(call)?,
+                        m_primary: choice(&arguments[0], "primary", "secondary")?,
+                        m_object: self.object(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_armor_ability" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_get_equipment,
+                    m_player_get_equipment_parameters: Some(s_action_player_get_equipment_parameters {
+                        m_player: self.target_player(call)?,
+                        m_object: self.object(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_crosshair_target" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::player_get_target_object,
+                    m_player_get_target_object_parameters: Some(s_action_player_get_target_object_parameters {
+                        m_player: self.target_player(call)?,
+                        m_object: self.object(variable)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "place_at_me" => {
+                expect_arguments(call, &[7])?;
+                let mut flags = e_create_object_flags::default();
+                for flag in flag_names(&arguments[2])? {
+                    match flag {
+                        "never_garbage_collect" => flags.never_garbage_collect = true,
+                        "suppress_effect" => flags.suppress_effect = true,
+                        "absolute_orientation" => flags.absolute_orientation = true,
+                        _ => return Err(format!("{flag} is not an object creation flag").into()),
+                    }
+                }
+                let variant_name_index = match indexed_name(&arguments[6], "variant_name") {
+                    Some(index) if (0..=u8::MAX as i64).contains(&index) => index as u8,
+                    Some(index) => return Err(format!("variant_name[{index}] is out of range").into()),
+                    None => return Err(format!("expected a variant_name but found {}", describe(&arguments[6])).into()),
+                };
+                c_action {
+                    m_type: e_action_type::create_object,
+                    m_create_object_parameters: Some(s_action_create_object_parameters {
+                        m_object_type: self.object_type(&arguments[0])?,
+                        m_object_reference_1: self.object(variable)?,
+                        m_object_reference_2: self.target_object(call)?,
+                        m_filter_index: self.object_filter(&arguments[1])?,
+                        m_flags: flags,
+                        m_offset: object_offset(&arguments[3..6])?,
+                        m_variant_name_index: variant_name_index,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "get_random_object" => {
+                expect_arguments(call, &[2])?;
+                expect_no_target(call)?;
+                c_action {
+                    m_type: e_action_type::get_random_object,
+                    m_get_random_object_parameters: Some(s_action_get_random_object_parameters {
+                        m_object_1: self.object(variable)?,
+                        m_object_2: self.object(&arguments[1])?,
+                        m_filter_index: self.object_filter(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            _ => return Err(format!("{}() doesn't return a value", call.m_name).into()),
+        })
+    }
+}
+
+// Actions written as calls. Calls to script functions aren't actions, these return None.
+impl c_megalo_compiler<'_> {
+    fn call_action(&mut self, call: &s_megalo_call) -> BLFLibResult<Option<c_action>> {
+        let arguments = &call.m_arguments;
+
+        Ok(Some(match call.m_name.as_str() {
+            "none" => {
+                expect_arguments(call, &[0])?;
+                expect_no_target(call)?;
+                c_action::default()
+            }
+            "delete" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::delete_object,
+                    m_delete_object_parameters: Some(s_action_delete_object_parameters { m_object: self.target_object(call)? }),
+                    ..Default::default()
+                }
+            }
+            "set_waypoint_visibility" => c_action {
+                m_type: e_action_type::navpoint_set_visible,
+                m_navpoint_set_visible_parameters: Some(s_action_navpoint_set_visible_parameters {
+                    m_object: self.target_object(call)?,
+                    m_player_filter_modifier: self.player_filter(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_waypoint_icon" => {
+                expect_arguments(call, &[1, 2])?;
+                c_action {
+                    m_type: e_action_type::navpoint_set_icon,
+                    m_navpoint_set_icon_parameters: Some(s_action_navpoint_set_icon_parameters {
+                        m_object: self.target_object(call)?,
+                        m_navpoint_icon: named_enum(&arguments[0], "waypoint icon")?,
+                        m_navpoint_number: arguments.get(1).map(|number| self.custom_variable(number)).transpose()?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_waypoint_priority" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::navpoint_set_priority,
+                    m_navpoint_set_priority_parameters: Some(s_action_navpoint_set_priority_parameters {
+                        m_object: self.target_object(call)?,
+                        m_priority: named_enum(&arguments[0], "waypoint priority")?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_waypoint_timer" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::navpoint_set_timer,
+                    m_navpoint_set_timer_parameters: Some(s_action_navpoint_set_timer_parameters {
+                        m_object: self.target_object(call)?,
+                        m_timer_index: self.object_timer(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_waypoint_range" => {
+                expect_arguments(call, &[2])?;
+                c_action {
+                    m_type: e_action_type::navpoint_set_visible_range,
+                    m_navpoint_set_visible_range_parameters: Some(s_action_navpoint_set_visible_range_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable_1: self.custom_variable(&arguments[0])?,
+                        m_variable_2: self.custom_variable(&arguments[1])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_shape" => {
+                let Some((shape, dimensions)) = arguments.split_first() else {
+                    return Err("set_shape() takes a shape followed by its dimensions".into());
+                };
+                let shape: e_boundary_shape = named_enum(shape, "shape")?;
+                let dimension_count = match shape {
+                    e_boundary_shape::unused => 0,
+                    e_boundary_shape::sphere => 1,
+                    e_boundary_shape::cylinder => 3,
+                    e_boundary_shape::r#box => 4,
+                };
+                if dimensions.len() != dimension_count {
+                    return Err(format!("a {shape:?} shape has {dimension_count} dimensions but was given {}", dimensions.len()).into());
+                }
+                let mut dimensions = dimensions.iter().map(|dimension| self.custom_variable(dimension)).collect::<BLFLibResult<Vec<_>>>()?.into_iter();
+                c_action {
+                    m_type: e_action_type::set_boundary,
+                    m_set_boundary_parameters: Some(s_action_set_boundary_parameters {
+                        m_object: self.target_object(call)?,
+                        m_shape: shape,
+                        m_variable_1: dimensions.next(),
+                        m_variable_2: dimensions.next(),
+                        m_variable_3: dimensions.next(),
+                        m_variable_4: dimensions.next(),
+                    }),
+                    ..Default::default()
+                }
+            }
+            "apply_traits" => {
+                expect_arguments(call, &[1])?;
+                let trait_count = self.m_variant.m_player_traits.len();
+                let trait_index = match indexed_name(&arguments[0], "script_traits") {
+                    Some(index) if index >= 0 && (index as usize) < trait_count => index as u8,
+                    Some(index) => return Err(format!("script_traits[{index}] doesn't exist, the variant has {trait_count} script traits").into()),
+                    None => return Err(format!("expected script traits but found {}", describe(&arguments[0])).into()),
+                };
+                c_action {
+                    m_type: e_action_type::apply_player_traits,
+                    m_apply_player_traits_parameters: Some(s_action_apply_player_traits_parameters {
+                        m_player: self.target_player(call)?,
+                        m_trait_index: trait_index,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_pickup_permissions" => c_action {
+                m_type: e_action_type::set_pickup_filter,
+                m_set_pickup_filter_parameters: Some(s_action_set_pickup_filter_parameters {
+                    m_object: self.target_object(call)?,
+                    m_player_filter_modifier: self.player_filter(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_spawn_location_permissions" => c_action {
+                m_type: e_action_type::set_respawn_filter,
+                m_set_respawn_filter_parameters: Some(s_action_set_respawn_filter_parameters {
+                    m_object: self.target_object(call)?,
+                    m_player_filter_modifier: self.player_filter(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_spawn_location_fireteams" => {
+                expect_arguments(call, &[1])?;
+                let fireteams = match &arguments[0] {
+                    fireteams if name(fireteams) == Some("none") => Vec::new(),
+                    e_megalo_expression::flags(fireteams) => fireteams.iter().collect(),
+                    fireteam => vec![fireteam],
+                };
+                let mut fireteam_filter = 0u8;
+                for fireteam in fireteams {
+                    fireteam_filter |= 1 << integer::<u8>(fireteam, 0, 7)?;
+                }
+                c_action {
+                    m_type: e_action_type::set_fireteam_respawn_filter,
+                    m_set_fireteam_respawn_filter_parameters: Some(s_action_set_fireteam_respawn_filter_parameters {
+                        m_object: self.target_object(call)?,
+                        m_fireteam_filter: e_fireteam_filter_flags::from_raw(fireteam_filter),
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_progress_bar" => {
+                let Some((timer, filter)) = arguments.split_first() else {
+                    return Err("set_progress_bar() takes a timer followed by a player filter".into());
+                };
+                c_action {
+                    m_type: e_action_type::set_progress_bar,
+                    m_set_progress_bar_parameters: Some(s_action_set_progress_bar_parameters {
+                        m_object: self.target_object(call)?,
+                        m_player_filter_modifier: self.player_filter(filter)?,
+                        m_timer_index: self.object_timer(timer)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "show_message_to" => {
+                let Some((sound, string)) = arguments.split_first() else {
+                    return Err("show_message_to() takes a sound followed by a string".into());
+                };
+                c_action {
+                    m_type: e_action_type::hud_post_message,
+                    m_hud_post_message_parameters: Some(s_action_hud_post_message_parameters {
+                        m_target: self.target(call)?,
+                        m_sound_index: named_enum::<e_megalo_sound>(sound, "sound")?,
+                        m_string: self.dynamic_string(string)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_rate" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::timer_set_rate,
+                    m_timer_set_rate_parameters: Some(s_action_timer_set_rate_parameters {
+                        m_timer: self.target_timer(call)?,
+                        m_rate: named_enum::<e_game_engine_timer_rate>(&arguments[0], "timer rate")?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "debug_print" => {
+                expect_no_target(call)?;
+                c_action {
+                    m_type: e_action_type::print_variable,
+                    m_print_variable_parameters: Some(s_action_print_variable_parameters { m_string: self.dynamic_string(arguments)? }),
+                    ..Default::default()
+                }
+            }
+            "end_round" => {
+                expect_arguments(call, &[0])?;
+                expect_game_target(call)?;
+                c_action { m_type: e_action_type::end_round, ..Default::default() }
+            }
+            "set_shape_visibility" => c_action {
+                m_type: e_action_type::boundary_set_visible,
+                m_boundary_set_visible_parameters: Some(s_action_boundary_set_visible_parameters {
+                    m_object: self.target_object(call)?,
+                    m_player_filter_modifier: self.player_filter(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "kill" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::object_destroy,
+                    m_object_destroy_parameters: Some(s_action_object_destroy_parameters {
+                        m_object: self.target_object(call)?,
+                        m_no_statistics: boolean(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_invincibility" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::object_set_invincibility,
+                    m_object_set_invincibility_parameters: Some(s_action_object_set_invincibility_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "debug_break" => {
+                expect_arguments(call, &[0])?;
+                expect_no_target(call)?;
+                c_action { m_type: e_action_type::break_into_debugger, ..Default::default() }
+            }
+            "debug_enable_tracing" => {
+                expect_arguments(call, &[1])?;
+                expect_no_target(call)?;
+                c_action {
+                    m_type: e_action_type::debugging_enable_tracing,
+                    m_debugging_enable_tracing_parameters: Some(s_action_debugging_enable_tracing_parameters { m_tracing_enabled: boolean(&arguments[0])? }),
+                    ..Default::default()
+                }
+            }
+            "attach_to" => {
+                expect_arguments(call, &[5])?;
+                c_action {
+                    m_type: e_action_type::object_attach,
+                    m_object_attach_parameters: Some(s_action_object_attach_parameters {
+                        m_object_1: self.target_object(call)?,
+                        m_object_2: self.object(&arguments[0])?,
+                        m_offset: object_offset(&arguments[1..4])?,
+                        m_absolute_orientation: choice(&arguments[4], "absolute", "relative")?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "detach" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::object_detach,
+                    m_object_detach_parameters: Some(s_action_object_detach_parameters { m_object: self.target_object(call)? }),
+                    ..Default::default()
+                }
+            }
+            "set_requisition_enabled" => {
+                expect_arguments(call, &[2])?;
+                c_action {
+                    m_type: e_action_type::player_enable_purchases,
+                    m_player_enable_purchases_parameters: Some(s_action_player_enable_purchases_parameters {
+                        m_player: self.target_player(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                        m_mode: integer(&arguments[1], 0, 31)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "force_into_vehicle" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::player_set_vehicle,
+                    m_player_set_vehicle_parameters: Some(s_action_player_set_vehicle_parameters {
+                        m_player: self.target_player(call)?,
+                        m_object: self.object(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_biped" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::player_set_unit,
+                    m_player_set_unit_parameters: Some(s_action_player_set_unit_parameters {
+                        m_player: self.target_player(call)?,
+                        m_object: self.object(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "reset" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::timer_reset,
+                    m_timer_reset_parameters: Some(s_action_timer_reset_parameters { m_timer: self.target_timer(call)? }),
+                    ..Default::default()
+                }
+            }
+            "set_pickup_priority" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::weapon_set_pickup_priority,
+                    m_weapon_set_pickup_priority_parameters: Some(s_action_weapon_set_pickup_priority_parameters {
+                        m_object: self.target_object(call)?,
+                        m_weapon_pickup_priority: named_enum(&arguments[0], "pickup priority")?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "push_upward" => {
+                expect_arguments(call, &[0])?;
+                c_action {
+                    m_type: e_action_type::object_bounce,
+                    m_object_bounce_parameters: Some(s_action_object_bounce_parameters { m_object: self.target_object(call)? }),
+                    ..Default::default()
+                }
+            }
+            "set_text" => c_action {
+                m_type: e_action_type::hud_widget_set_text,
+                m_hud_widget_set_text_parameters: Some(s_action_hud_widget_set_text_parameters {
+                    m_widget_index: self.hud_widget(call)?,
+                    m_string: self.dynamic_string(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_value_text" => c_action {
+                m_type: e_action_type::hud_widget_set_value,
+                m_hud_widget_set_value_parameters: Some(s_action_hud_widget_set_value_parameters {
+                    m_widget_index: self.hud_widget(call)?,
+                    m_string: self.dynamic_string(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_meter_params" => c_action {
+                m_type: e_action_type::hud_widget_set_meter,
+                m_hud_widget_set_meter_parameters: Some(s_action_hud_widget_set_meter_parameters {
+                    m_widget_index: self.hud_widget(call)?,
+                    m_meter_input: self.hud_meter_input(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_icon" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::hud_widget_set_icon,
+                    m_hud_widget_set_icon_parameters: Some(s_action_hud_widget_set_icon_parameters {
+                        m_widget_index: self.hud_widget(call)?,
+                        m_icon_index: integer(&arguments[0], 0, 63)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_visibility" => {
+                expect_arguments(call, &[2])?;
+                c_action {
+                    m_type: e_action_type::hud_widget_set_visibility,
+                    m_hud_widget_set_visibility_parameters: Some(s_action_hud_widget_set_visibility_parameters {
+                        m_widget_index: self.hud_widget(call)?,
+                        m_player: self.player(&arguments[0])?,
+                        m_visible: boolean(&arguments[1])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "play_sound" => {
+                expect_arguments(call, &[2])?;
+                c_action {
+                    m_type: e_action_type::play_sound,
+                    m_play_sound_parameters: Some(s_action_play_sound_parameters {
+                        m_sound_index: named_enum::<e_megalo_sound>(&arguments[0], "sound")?,
+                        m_immediate: choice(&arguments[1], "immediate", "queued")?,
+                        m_target: self.target(call)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_scale" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::object_set_scale,
+                    m_object_set_scale_parameters: Some(s_action_object_set_scale_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_waypoint_text" => c_action {
+                m_type: e_action_type::navpoint_set_text,
+                m_navpoint_set_text_parameters: Some(s_action_navpoint_set_text_parameters {
+                    m_object: self.target_object(call)?,
+                    m_string: self.dynamic_string(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_round_card_title" => c_action {
+                m_type: e_action_type::player_set_objective,
+                m_player_set_objective_parameters: Some(s_action_player_set_objective_parameters {
+                    m_player: self.target_player(call)?,
+                    m_string: self.dynamic_string(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_round_card_text" => c_action {
+                m_type: e_action_type::player_set_objective_allegiance,
+                m_player_set_objective_allegiance_parameters: Some(s_action_player_set_objective_allegiance_parameters {
+                    m_player: self.target_player(call)?,
+                    m_string: self.dynamic_string(arguments)?,
+                }),
+                ..Default::default()
+            },
+            "set_round_card_icon" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::player_set_objective_allegiance_icon,
+                    m_player_set_objective_allegiance_icon_parameters: Some(s_action_player_set_objective_allegiance_icon_parameters {
+                        m_player: self.target_player(call)?,
+                        m_icon_index: integer(&arguments[0], 0, 127)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_co_op_spawning" => {
+                expect_arguments(call, &[1])?;
+                let enabled = boolean(&arguments[0])?;
+                match self.player_reference(&call.m_target)? {
+                    Some(player) => c_action {
+                        m_type: e_action_type::player_set_coop_spawning,
+                        m_player_set_coop_spawning_parameters: Some(s_action_player_set_coop_spawning_parameters { m_player: player, m_enabled: enabled }),
+                        ..Default::default()
+                    },
+                    None => c_action {
+                        m_type: e_action_type::team_set_coop_spawning,
+                        m_team_set_coop_spawning_parameters: Some(s_action_team_set_coop_spawning_parameters { m_team: self.target_team(call)?, m_enabled: enabled }),
+                        ..Default::default()
+                    },
+                }
+            }
+            "set_primary_respawn_object" => {
+                expect_arguments(call, &[1])?;
+                let object = self.object(&arguments[0])?;
+                match self.player_reference(&call.m_target)? {
+                    Some(player) => c_action {
+                        m_type: e_action_type::player_set_primary_respawn_object,
+                        m_player_set_primary_respawn_object_parameters: Some(s_action_player_set_primary_respawn_object_parameters { m_player: player, m_object: object }),
+                        ..Default::default()
+                    },
+                    None => c_action {
+                        m_type: e_action_type::team_set_primary_respawn_object,
+                        m_team_set_primary_respawn_object_parameters: Some(s_action_team_set_primary_respawn_object_parameters { m_team: self.target_team(call)?, m_object: object }),
+                        ..Default::default()
+                    },
+                }
+            }
+            "set_fireteam" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::player_set_fireteam_index,
+                    m_player_set_fireteam_index_parameters: Some(s_action_player_set_fireteam_index_parameters {
+                        m_player: self.target_player(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_requisition_palette" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::player_set_requisition_palette,
+                    m_player_set_requisition_palette_parameters: Some(s_action_player_set_requisition_palette_parameters {
+                        m_player: self.target_player(call)?,
+                        m_new_palette: integer(&arguments[0], 0, 15)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_device_power" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::device_set_power,
+                    m_device_set_power_parameters: Some(s_action_device_set_power_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_device_position" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::device_set_position,
+                    m_device_set_position_parameters: Some(s_action_device_set_position_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_device_actual_position" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::device_set_position_immediate,
+                    m_device_set_position_immediate_parameters: Some(s_action_device_set_position_immediate_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_device_animation_position_track" => {
+                expect_arguments(call, &[2])?;
+                let animation_name_index = match indexed_name(&arguments[0], "animation_name") {
+                    Some(index) if (0..=u8::MAX as i64).contains(&index) => index as u8,
+                    Some(index) => return Err(format!("animation_name[{index}] is out of range").into()),
+                    None => return Err(format!("expected an animation_name but found {}", describe(&arguments[0])).into()),
+                };
+                c_action {
+                    m_type: e_action_type::device_set_position_track,
+                    m_device_set_position_track_parameters: Some(s_action_device_set_position_track_parameters {
+                        m_object: self.target_object(call)?,
+                        m_animation_name_index: animation_name_index,
+                        m_variable: self.custom_variable(&arguments[1])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "animate_device_position" => {
+                expect_arguments(call, &[4])?;
+                c_action {
+                    m_type: e_action_type::device_animate_position,
+                    m_device_animate_position_parameters: Some(s_action_device_animate_position_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable_1: self.custom_variable(&arguments[0])?,
+                        m_variable_2: self.custom_variable(&arguments[1])?,
+                        m_variable_3: self.custom_variable(&arguments[2])?,
+                        m_variable_4: self.custom_variable(&arguments[3])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "send_incident" => {
+                expect_arguments(call, &[3, 4])?;
+                expect_no_target(call)?;
+                let incident_id = integer(&arguments[0], i16::MIN as i64, i16::MAX as i64)?;
+                let target_1 = self.team_or_player_target(&arguments[1])?;
+                let target_2 = self.team_or_player_target(&arguments[2])?;
+                match arguments.get(3) {
+                    Some(variable) => c_action {
+                        m_type: e_action_type::submit_incident_with_custom_value,
+                        m_submit_incident_with_custom_value_parameters: Some(s_action_submit_incident_with_custom_value_parameters {
+                            m_incident_id: incident_id,
+                            m_target_1: target_1,
+                            m_target_2: target_2,
+                            m_variable: self.custom_variable(variable)?,
+                        }),
+                        ..Default::default()
+                    },
+                    None => c_action {
+                        m_type: e_action_type::submit_incident,
+                        m_submit_incident_parameters: Some(s_action_submit_incident_parameters {
+                            m_incident_id: incident_id,
+                            m_target_1: target_1,
+                            m_target_2: target_2,
+                        }),
+                        ..Default::default()
+                    },
+                }
+            }
+            "set_loadout_palette" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::set_loadout_palette,
+                    m_set_loadout_palette_parameters: Some(s_action_set_loadout_palette_parameters {
+                        m_target: self.target(call)?,
+                        m_loadout_palette_index: integer(&arguments[0], 0, 7)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "insert_theater_film_marker" => {
+                expect_no_target(call)?;
+                let Some((variable, string)) = arguments.split_first() else {
+                    return Err("insert_theater_film_marker() takes a number followed by a string".into());
+                };
+                c_action {
+                    m_type: e_action_type::saved_film_insert_marker,
+                    m_saved_film_insert_marker_parameters: Some(s_action_saved_film_insert_marker_parameters {
+                        m_variable: self.custom_variable(variable)?,
+                        m_string: self.dynamic_string(string)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "enable_spawn_zone" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::respawn_zone_enable,
+                    m_respawn_zone_enable_parameters: Some(s_action_respawn_zone_enable_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_garbage_collection_disabled" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::object_set_never_garbage,
+                    m_object_set_never_garbage_parameters: Some(s_action_object_set_never_garbage_parameters {
+                        m_object: self.target_object(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "create_tunnel" => {
+                expect_arguments(call, &[5])?;
+                expect_no_target(call)?;
+                c_action {
+                    m_type: e_action_type::create_tunnel,
+                    m_create_tunnel_parameters: Some(s_action_create_tunnel_parameters {
+                        m_object_1: self.player(&arguments[0])?,
+                        m_object_2: self.player(&arguments[1])?,
+                        m_object_type: self.object(&arguments[2])?,
+                        m_variable: self.custom_variable(&arguments[3])?,
+                        m_object_3: self.player(&arguments[4])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "debug_force_player_view_count" => {
+                expect_arguments(call, &[1])?;
+                expect_no_target(call)?;
+                c_action {
+                    m_type: e_action_type::debug_force_player_view_count,
+                    m_debug_force_player_view_count_parameters: Some(s_action_debug_force_player_view_count_parameters { m_variable: self.custom_variable(&arguments[0])? }),
+                    ..Default::default()
+                }
+            }
+            // Players pick up an existing weapon, bipeds are given a new one.
+            "add_weapon" => {
+                expect_arguments(call, &[1, 2])?;
+                match arguments.as_slice() {
+                    [object] => c_action {
+                        m_type: e_action_type::player_pick_up_weapon,
+                        m_player_pick_up_weapon_parameters: Some(s_action_player_pick_up_weapon_parameters {
+                            m_player: self.target_player(call)?,
+                            m_object: self.object(object)?,
+                        }),
+                        ..Default::default()
+                    },
+                    [object_type, mode] => c_action {
+                        m_type: e_action_type::biped_give_weapon,
+                        m_biped_give_weapon_parameters: Some(s_action_biped_give_weapon_parameters {
+                            m_object: self.target_object(call)?,
+                            m_object_type: self.object_type(object_type)?,
+                            m_mode: named_enum(mode, "weapon mode")?,
+                        }),
+                        ..Default::default()
+                    },
+                    _ => unreachable!(),
+                }
+            }
+            "copy_rotation_from" => {
+                expect_arguments(call, &[2])?;
+                c_action {
+                    m_type: e_action_type::object_set_orientation,
+                    m_object_set_orientation_parameters: Some(s_action_object_set_orientation_parameters {
+                        m_object_1: self.target_object(call)?,
+                        m_object_2: self.object(&arguments[0])?,
+                        m_absolute_orientation: choice(&arguments[1], "absolute", "relative")?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "face_toward" => {
+                expect_arguments(call, &[4])?;
+                c_action {
+                    m_type: e_action_type::object_face_object,
+                    m_object_face_object_parameters: Some(s_action_object_face_object_parameters {
+                        m_object_1: self.target_object(call)?,
+                        m_object_2: self.object(&arguments[0])?,
+                        m_offset: object_offset(&arguments[1..4])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "remove_weapon" => {
+                expect_arguments(call, &[2])?;
+                c_action {
+                    m_type: e_action_type::biped_drop_weapon,
+                    m_biped_drop_weapon_parameters: Some(s_action_biped_drop_weapon_parameters {
+                        m_object: self.target_object(call)?,
+                        m_primary: choice(&arguments[0], "primary", "secondary")?,
+                        m_delete_on_drop: boolean(&arguments[1])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_scenario_interpolator_state" => {
+                expect_arguments(call, &[2])?;
+                expect_no_target(call)?;
+                c_action {
+                    m_type: e_action_type::set_scenario_interpolator_state,
+                    m_set_scenario_interpolator_state_parameters: Some(s_action_set_scenario_interpolator_state_parameters {
+                        m_variable_1: self.custom_variable(&arguments[0])?,
+                        m_variable_2: self.custom_variable(&arguments[1])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "record_griefer_penalty" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::game_grief_record_custom_penalty,
+                    m_game_grief_record_custom_penalty_parameters: Some(s_action_game_grief_record_custom_penalty_parameters {
+                        m_player: self.target_player(call)?,
+                        m_variable: self.custom_variable(&arguments[0])?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            "set_shape_owner_color" => {
+                expect_arguments(call, &[1])?;
+                c_action {
+                    m_type: e_action_type::boundary_set_player_color,
+                    m_boundary_set_player_color_parameters: Some(s_action_boundary_set_player_color_parameters {
+                        m_object: self.target_object(call)?,
+                        m_player_index: integer(&arguments[0], i8::MIN as i64, i8::MAX as i64)?,
+                    }),
+                    ..Default::default()
+                }
+            }
+            _ if call.m_target.is_empty() && arguments.is_empty() => return Ok(None),
+            _ => return Err(format!("unknown action {}()", call.m_name).into()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_decompiler::decompile_megalo_script;
+
+    const k_script: &str = r#"
+declare hud_widget[0] at top_left
+declare global.number[0] = 5 with network priority networked
+declare player.timer[0] = 30
+
+-- Scores a point for the current player.
+function award(): do
+    current_player.score += 1
+    hud_widget[0].set_text("Scored %n", current_player.score)
+end
+
+on init: do
+    global.number[0] = 0
+end
+
+for each player do
+    if not current_player.is_elite() and (global.number[0] > 2 or current_player.timer[0].is_zero()) then
+        award()
+    end
+    current_player.timer[0].reset()
+end
+"#;
+
+    #[test]
+    fn compile_round_trips_through_the_decompiler() {
+        let mut variant = c_game_engine_custom_variant::default();
+        compile_megalo_script(k_script, &mut variant).unwrap();
+
+        let game_engine = &variant.m_game_engine;
+        assert_eq!(game_engine.m_triggers.len(), 4);
+        assert_eq!(game_engine.m_initialization_trigger_index, 1);
+        assert_eq!(game_engine.m_hud_widgets.len(), 1);
+        assert_eq!(variant.m_script_strings.find_string(k_script_string_language_index, "Scored %n"), Some(0));
+
+        let decompiled = decompile_megalo_script(&variant);
+        let mut recompiled_variant = c_game_engine_custom_variant::default();
+        compile_megalo_script(&decompiled, &mut recompiled_variant).unwrap();
+
+        assert_eq!(decompile_megalo_script(&recompiled_variant), decompiled);
+        assert_eq!(recompiled_variant.m_script_strings, variant.m_script_strings);
+    }
+
+    #[test]
+    fn compile_reports_the_failing_line() {
+        let mut variant = c_game_engine_custom_variant::default();
+        let error = compile_megalo_script("do\n    global.number[3] = 1\nend\n", &mut variant).unwrap_err();
+
+        assert!(error.to_string().starts_with("line 2: "), "{error}");
+    }
+
+    #[test]
+    fn failed_compile_leaves_the_variant_unchanged() {
+        let mut variant = c_game_engine_custom_variant::default();
+        compile_megalo_script(k_script, &mut variant).unwrap();
+        let compiled_variant = variant.clone();
+
+        let failing_script = "declare hud_widget[0] at top_left\ndo\n    hud_widget[0].set_text(\"Unused\")\n    global.number[3] = 1\nend\n";
+        compile_megalo_script(failing_script, &mut variant).unwrap_err();
+
+        assert_eq!(variant, compiled_variant);
+    }
+}
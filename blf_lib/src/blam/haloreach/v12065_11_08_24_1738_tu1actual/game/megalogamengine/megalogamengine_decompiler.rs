@@ -0,0 +1,883 @@
+use std::fmt::Write;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_variant::c_game_engine_custom_variant;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_actions::{c_action, c_megalogamengine_hud_meter_input, c_player_filter_modifier, e_action_team_or_player_target, e_action_type, e_math_operation, e_player_filter_type, s_object_offset, s_team_or_player_target};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_conditions::{c_condition, e_condition_type, e_numeric_comparison, e_player_death_killer_type_flags};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_custom_timer_reference::{c_custom_timer_reference, e_custom_timer_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_custom_variable_reference::{c_custom_variable_reference, e_custom_variable_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_explicit_object::{c_explicit_object, e_explicit_object_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_explicit_player::{c_explicit_player, e_explicit_player_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_explicit_team::{c_explicit_team, e_explicit_team_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_hud_widgets::e_megalogamengine_hud_meter_input_type;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_object_reference::{c_object_reference, e_object_reference_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_object_type_reference::c_object_type_reference;
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_player_reference::{c_player_reference, e_player_reference_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_team_reference::{c_team_reference, e_team_reference_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_text::{c_dynamic_string, c_replaceable_token, e_replaceable_token_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_trigger::{c_trigger, e_trigger_execution_mode, e_trigger_type};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_variant_variable::{e_variable_type, s_variant_variable};
+
+// Script strings are shown in English.
+const k_script_string_language_index: usize = 0;
+
+const k_indent: &str = "    ";
+
+/// Decompiles a megalo script into readable pseudo-code.
+///
+/// Triggers run by a for_each action are nested inside the trigger which runs them,
+/// subroutines run from more than one place are written as functions,
+/// and every other trigger is written at the top level in script order.
+/// The output can be compiled back into a variant with `compile_megalo_script`.
+/// Conditions become if blocks wrapping the actions which follow them,
+/// conditions sharing a union group are or'd together, and separate groups are and'd.
+pub fn decompile_megalo_script(variant: &c_game_engine_custom_variant) -> String {
+    let mut decompiler = c_megalo_decompiler {
+        m_variant: variant,
+        m_output: String::new(),
+        m_indent: 0,
+        m_trigger_stack: Vec::new(),
+    };
+
+    decompiler.decompile();
+    decompiler.m_output
+}
+
+struct c_megalo_decompiler<'a> {
+    m_variant: &'a c_game_engine_custom_variant,
+    m_output: String,
+    m_indent: usize,
+    // Guards against triggers which run themselves.
+    m_trigger_stack: Vec<usize>,
+}
+
+impl c_megalo_decompiler<'_> {
+    fn decompile(&mut self) {
+        self.write_declarations();
+
+        let variant = self.m_variant;
+        let triggers = &variant.m_game_engine.m_triggers;
+        for (trigger_index, trigger) in triggers.iter().enumerate() {
+            if self.is_nested(trigger_index) {
+                continue;
+            }
+
+            let event = match trigger.m_trigger_type {
+                e_trigger_type::initialization => String::from("on init: "),
+                e_trigger_type::local_initialization => String::from("on local init: "),
+                e_trigger_type::host_migration if trigger_index as i16 == variant.m_game_engine.m_double_migration_trigger_index => String::from("on double host migration: "),
+                e_trigger_type::host_migration => String::from("on host migration: "),
+                e_trigger_type::object_death => String::from("on object death: "),
+                e_trigger_type::local => String::from("on local: "),
+                e_trigger_type::pregame => String::from("on pregame: "),
+                e_trigger_type::subroutine => format!("function trigger_{trigger_index}(): "),
+                e_trigger_type::normal => String::new(),
+            };
+
+            self.write_trigger(trigger_index, &event);
+            self.write_line("");
+        }
+    }
+
+    // Triggers run by a single for_each action are written where they run, as are normal triggers run by any.
+    // Subroutines run from several places are written once as functions.
+    fn is_nested(&self, trigger_index: usize) -> bool {
+        let variant = self.m_variant;
+        let Some(trigger) = variant.m_game_engine.m_triggers.get(trigger_index) else {
+            return false;
+        };
+
+        let reference_count = variant.m_game_engine.m_actions.iter()
+            .filter_map(|action| action.m_for_each_parameters.as_ref())
+            .filter(|parameters| parameters.m_trigger_index as usize == trigger_index)
+            .count();
+
+        match trigger.m_trigger_type {
+            e_trigger_type::subroutine => reference_count == 1,
+            _ => reference_count > 0,
+        }
+    }
+
+    fn write_declarations(&mut self) {
+        let variant = self.m_variant;
+
+        for (option_index, option) in variant.m_user_defined_options.iter().enumerate() {
+            self.write_line(format!("-- script_option[{option_index}]: {}", self.script_string(option.m_name_string_index as usize)));
+        }
+        for (statistic_index, statistic) in variant.m_game_engine.m_statistics.iter().enumerate() {
+            self.write_line(format!("-- script_stat[{statistic_index}]: {}", self.script_string(statistic.m_name_string_index as usize)));
+        }
+        for (widget_index, widget) in variant.m_game_engine.m_hud_widgets.iter().enumerate() {
+            self.write_line(format!("declare hud_widget[{widget_index}] at {widget:?}"));
+        }
+
+        let scopes = [
+            ("global", &variant.m_game_engine.m_global_variable_metadata.m_numeric_variables, &variant.m_game_engine.m_global_variable_metadata.m_timer_variables, &variant.m_game_engine.m_global_variable_metadata.m_team_variables, &variant.m_game_engine.m_global_variable_metadata.m_player_variables, &variant.m_game_engine.m_global_variable_metadata.m_object_variables),
+            ("player", &variant.m_game_engine.m_player_variable_metadata.m_numeric_variables, &variant.m_game_engine.m_player_variable_metadata.m_timer_variables, &variant.m_game_engine.m_player_variable_metadata.m_team_variables, &variant.m_game_engine.m_player_variable_metadata.m_player_variables, &variant.m_game_engine.m_player_variable_metadata.m_object_variables),
+            ("object", &variant.m_game_engine.m_object_variable_metadata.m_numeric_variables, &variant.m_game_engine.m_object_variable_metadata.m_timer_variables, &variant.m_game_engine.m_object_variable_metadata.m_team_variables, &variant.m_game_engine.m_object_variable_metadata.m_player_variables, &variant.m_game_engine.m_object_variable_metadata.m_object_variables),
+            ("team", &variant.m_game_engine.m_team_variable_metadata.m_numeric_variables, &variant.m_game_engine.m_team_variable_metadata.m_timer_variables, &variant.m_game_engine.m_team_variable_metadata.m_team_variables, &variant.m_game_engine.m_team_variable_metadata.m_player_variables, &variant.m_game_engine.m_team_variable_metadata.m_object_variables),
+        ];
+
+        for (scope, numbers, timers, teams, players, objects) in scopes {
+            for (index, (initial_value, network_state)) in numbers.iter().enumerate() {
+                self.write_line(format!("declare {scope}.number[{index}] = {} with network priority {network_state:?}", custom_variable(initial_value)));
+            }
+            for (index, initial_value) in timers.iter().enumerate() {
+                self.write_line(format!("declare {scope}.timer[{index}] = {}", custom_variable(initial_value)));
+            }
+            for (index, (initial_value, network_state)) in teams.iter().enumerate() {
+                self.write_line(format!("declare {scope}.team[{index}] = {initial_value:?} with network priority {network_state:?}"));
+            }
+            for (index, network_state) in players.iter().enumerate() {
+                self.write_line(format!("declare {scope}.player[{index}] with network priority {network_state:?}"));
+            }
+            for (index, network_state) in objects.iter().enumerate() {
+                self.write_line(format!("declare {scope}.object[{index}] with network priority {network_state:?}"));
+            }
+        }
+
+        if !self.m_output.is_empty() {
+            self.write_line("");
+        }
+    }
+
+    fn write_trigger(&mut self, trigger_index: usize, prefix: &str) {
+        let variant = self.m_variant;
+        let Some(trigger) = variant.m_game_engine.m_triggers.get(trigger_index) else {
+            self.write_line(format!("-- invalid trigger {trigger_index}"));
+            return;
+        };
+
+        let header = match trigger.m_execution_mode {
+            e_trigger_execution_mode::general => String::from("do"),
+            e_trigger_execution_mode::player => String::from("for each player do"),
+            e_trigger_execution_mode::random_player => String::from("for each player randomly do"),
+            e_trigger_execution_mode::team => String::from("for each team do"),
+            e_trigger_execution_mode::object => String::from("for each object do"),
+            e_trigger_execution_mode::object_with_label => format!("for each object with label {} do", self.object_filter(trigger.m_object_filter_index)),
+        };
+
+        self.write_line(format!("{prefix}{header}"));
+        self.write_trigger_body(trigger_index);
+        self.write_line("end");
+    }
+
+    fn write_trigger_body(&mut self, trigger_index: usize) {
+        if self.m_trigger_stack.contains(&trigger_index) {
+            self.m_indent += 1;
+            self.write_line(format!("-- trigger {trigger_index} runs itself"));
+            self.m_indent -= 1;
+            return;
+        }
+
+        let variant = self.m_variant;
+        let game_engine = &variant.m_game_engine;
+        let trigger: &c_trigger = &game_engine.m_triggers[trigger_index];
+        let conditions: Vec<&c_condition> = (0..trigger.m_condition_count as usize)
+            .filter_map(|index| game_engine.m_conditions.get(trigger.m_first_condition as usize + index))
+            .collect();
+        let action_count = trigger.m_action_count as usize;
+        let first_action = trigger.m_first_action as usize;
+
+        self.m_trigger_stack.push(trigger_index);
+        self.m_indent += 1;
+        let indent = self.m_indent;
+
+        // Conditions placed after the last action still guard nothing, but are kept so they can be reviewed.
+        for action_offset in 0..=action_count {
+            let guards: Vec<&c_condition> = conditions.iter()
+                .copied()
+                .filter(|condition| {
+                    let execute_before_action = condition.m_execute_before_action as usize;
+                    execute_before_action == action_offset || (action_offset == action_count && execute_before_action > action_count)
+                })
+                .collect();
+
+            if !guards.is_empty() {
+                self.write_line(format!("if {} then", self.condition_expression(&guards)));
+                self.m_indent += 1;
+            }
+
+            if action_offset < action_count {
+                match game_engine.m_actions.get(first_action + action_offset) {
+                    Some(action) => self.write_action(action),
+                    None => self.write_line(format!("-- invalid action {}", first_action + action_offset)),
+                }
+            }
+        }
+
+        while self.m_indent > indent {
+            self.m_indent -= 1;
+            self.write_line("end");
+        }
+
+        self.m_indent -= 1;
+        self.m_trigger_stack.pop();
+    }
+
+    fn condition_expression(&self, conditions: &[&c_condition]) -> String {
+        let mut union_groups: Vec<(u16, Vec<String>)> = Vec::new();
+
+        for condition in conditions {
+            let expression = format!("{}{}", if condition.m_negated { "not " } else { "" }, self.condition(condition));
+            match union_groups.last_mut() {
+                Some((union_group, expressions)) if *union_group == condition.m_union_group => expressions.push(expression),
+                _ => union_groups.push((condition.m_union_group, vec![expression])),
+            }
+        }
+
+        let group_count = union_groups.len();
+        union_groups.into_iter()
+            .map(|(_, expressions)| {
+                if expressions.len() > 1 && group_count > 1 {
+                    format!("({})", expressions.join(" or "))
+                } else {
+                    expressions.join(" or ")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" and ")
+    }
+
+    fn condition(&self, condition: &c_condition) -> String {
+        match condition.m_type {
+            e_condition_type::none => Some(String::from("none")),
+            e_condition_type::r#if => condition.m_if_parameters.as_ref().map(|parameters|
+                format!("{} {} {}", variant_variable(&parameters.m_left), numeric_comparison(parameters.m_comparison), variant_variable(&parameters.m_right))),
+            e_condition_type::object_in_area => condition.m_object_in_area_parameters.as_ref().map(|parameters|
+                format!("{}.shape_contains({})", object_reference(&parameters.m_object_reference_2), object_reference(&parameters.m_object_reference_1))),
+            e_condition_type::player_died => condition.m_player_died_parameters.as_ref().map(|parameters|
+                format!("{}.killer_type_is({})", player_reference(&parameters.m_player), killer_types(&parameters.m_killer_type))),
+            e_condition_type::team_disposition => condition.m_team_disposition_parameters.as_ref().map(|parameters|
+                format!("{}.has_alliance_status({}, {:?})", team_reference(&parameters.m_team_1), team_reference(&parameters.m_team_2), parameters.m_disposition)),
+            e_condition_type::timer_expired => condition.m_timer_expired_parameters.as_ref().map(|parameters|
+                format!("{}.is_zero()", custom_timer(&parameters.m_timer))),
+            e_condition_type::object_is_type => condition.m_object_is_type_parameters.as_ref().map(|parameters|
+                format!("{}.is_of_type({})", object_reference(&parameters.m_object), object_type(&parameters.m_object_type))),
+            e_condition_type::team_is_active => condition.m_team_is_active_parameters.as_ref().map(|parameters|
+                format!("{}.has_any_players()", team_reference(&parameters.m_team))),
+            e_condition_type::object_out_of_bounds => condition.m_object_out_of_bounds_parameters.as_ref().map(|parameters|
+                format!("{}.is_out_of_bounds()", object_reference(&parameters.m_object))),
+            e_condition_type::player_is_fire_team_leader => condition.m_player_is_fire_team_leader_parameters.as_ref().map(|parameters|
+                format!("{}.is_fireteam_leader()", player_reference(&parameters.m_player))),
+            e_condition_type::player_assisted_with_kill => condition.m_player_assisted_with_kill_parameters.as_ref().map(|parameters|
+                format!("{}.assisted_kill_of({})", player_reference(&parameters.m_player_1), player_reference(&parameters.m_player_2))),
+            e_condition_type::object_matches_filter => condition.m_object_matches_filter_parameters.as_ref().map(|parameters|
+                format!("{}.has_forge_label({})", object_reference(&parameters.m_object), self.object_filter(parameters.m_filter_index))),
+            e_condition_type::player_is_active => condition.m_player_is_active_parameters.as_ref().map(|parameters|
+                format!("{}.is_not_respawning()", player_reference(&parameters.m_player))),
+            e_condition_type::equipment_is_active => condition.m_equipment_is_active_parameters.as_ref().map(|parameters|
+                format!("{}.is_in_use()", object_reference(&parameters.m_object))),
+            e_condition_type::player_is_spartan => condition.m_player_is_spartan_parameters.as_ref().map(|parameters|
+                format!("{}.is_spartan()", player_reference(&parameters.m_player))),
+            e_condition_type::player_is_elite => condition.m_player_is_elite_parameters.as_ref().map(|parameters|
+                format!("{}.is_elite()", player_reference(&parameters.m_player))),
+            e_condition_type::player_is_editor => condition.m_player_is_editor_parameters.as_ref().map(|parameters|
+                format!("{}.is_monitor()", player_reference(&parameters.m_player))),
+            e_condition_type::game_is_forge => Some(String::from("game.is_forge()")),
+        }.unwrap_or_else(|| format!("invalid_{:?}()", condition.m_type))
+    }
+
+    fn write_action(&mut self, action: &c_action) {
+        if let Some(parameters) = action.m_for_each_parameters.as_ref() {
+            let trigger_index = parameters.m_trigger_index as usize;
+            let variant = self.m_variant;
+            if self.is_nested(trigger_index) {
+                self.write_trigger(trigger_index, "");
+            } else {
+                self.write_line(format!("trigger_{trigger_index}()"));
+            }
+            return;
+        }
+
+        let statement = self.action(action);
+        self.write_line(statement);
+    }
+
+    fn action(&self, action: &c_action) -> String {
+        match action.m_type {
+            e_action_type::none => Some(String::from("none()")),
+            e_action_type::set_score => action.m_set_score_parameters.as_ref().map(|parameters|
+                format!("{}.score {} {}", team_or_player_target(&parameters.m_target), math_operation(parameters.m_operation), custom_variable(&parameters.m_variable))),
+            e_action_type::create_object => action.m_create_object_parameters.as_ref().map(|parameters| {
+                let mut flags = Vec::new();
+                if parameters.m_flags.never_garbage_collect { flags.push("never_garbage_collect"); }
+                if parameters.m_flags.suppress_effect { flags.push("suppress_effect"); }
+                if parameters.m_flags.absolute_orientation { flags.push("absolute_orientation"); }
+                format!("{} = {}.place_at_me({}, {}, {}, {}, variant_name[{}])",
+                    object_reference(&parameters.m_object_reference_1),
+                    object_reference(&parameters.m_object_reference_2),
+                    object_type(&parameters.m_object_type),
+                    self.object_filter(parameters.m_filter_index),
+                    if flags.is_empty() { String::from("none") } else { flags.join(" | ") },
+                    object_offset(&parameters.m_offset),
+                    parameters.m_variant_name_index)
+            }),
+            e_action_type::delete_object => action.m_delete_object_parameters.as_ref().map(|parameters|
+                format!("{}.delete()", object_reference(&parameters.m_object))),
+            e_action_type::navpoint_set_visible => action.m_navpoint_set_visible_parameters.as_ref().map(|parameters|
+                format!("{}.set_waypoint_visibility({})", object_reference(&parameters.m_object), player_filter(&parameters.m_player_filter_modifier))),
+            e_action_type::navpoint_set_icon => action.m_navpoint_set_icon_parameters.as_ref().map(|parameters| match &parameters.m_navpoint_number {
+                Some(number) => format!("{}.set_waypoint_icon({:?}, {})", object_reference(&parameters.m_object), parameters.m_navpoint_icon, custom_variable(number)),
+                None => format!("{}.set_waypoint_icon({:?})", object_reference(&parameters.m_object), parameters.m_navpoint_icon),
+            }),
+            e_action_type::navpoint_set_priority => action.m_navpoint_set_priority_parameters.as_ref().map(|parameters|
+                format!("{}.set_waypoint_priority({:?})", object_reference(&parameters.m_object), parameters.m_priority)),
+            e_action_type::navpoint_set_timer => action.m_navpoint_set_timer_parameters.as_ref().map(|parameters|
+                format!("{}.set_waypoint_timer({})", object_reference(&parameters.m_object), object_timer(&parameters.m_object, parameters.m_timer_index))),
+            e_action_type::navpoint_set_visible_range => action.m_navpoint_set_visible_range_parameters.as_ref().map(|parameters|
+                format!("{}.set_waypoint_range({}, {})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable_1), custom_variable(&parameters.m_variable_2))),
+            e_action_type::set => action.m_set_parameters.as_ref().map(|parameters|
+                format!("{} {} {}", variant_variable(&parameters.m_variable_1), math_operation(parameters.m_operation), variant_variable(&parameters.m_variable_2))),
+            e_action_type::set_boundary => action.m_set_boundary_parameters.as_ref().map(|parameters| {
+                let dimensions: Vec<String> = [&parameters.m_variable_1, &parameters.m_variable_2, &parameters.m_variable_3, &parameters.m_variable_4]
+                    .into_iter()
+                    .flatten()
+                    .map(custom_variable)
+                    .collect();
+                let mut arguments = vec![format!("{:?}", parameters.m_shape)];
+                arguments.extend(dimensions);
+                format!("{}.set_shape({})", object_reference(&parameters.m_object), arguments.join(", "))
+            }),
+            e_action_type::apply_player_traits => action.m_apply_player_traits_parameters.as_ref().map(|parameters|
+                format!("{}.apply_traits(script_traits[{}])", player_reference(&parameters.m_player), parameters.m_trait_index)),
+            e_action_type::set_pickup_filter => action.m_set_pickup_filter_parameters.as_ref().map(|parameters|
+                format!("{}.set_pickup_permissions({})", object_reference(&parameters.m_object), player_filter(&parameters.m_player_filter_modifier))),
+            e_action_type::set_respawn_filter => action.m_set_respawn_filter_parameters.as_ref().map(|parameters|
+                format!("{}.set_spawn_location_permissions({})", object_reference(&parameters.m_object), player_filter(&parameters.m_player_filter_modifier))),
+            e_action_type::set_fireteam_respawn_filter => action.m_set_fireteam_respawn_filter_parameters.as_ref().map(|parameters| {
+                let raw = parameters.m_fireteam_filter.to_raw();
+                let fireteams: Vec<String> = (0..8).filter(|bit| raw & (1 << bit) != 0).map(|bit| bit.to_string()).collect();
+                format!("{}.set_spawn_location_fireteams({})", object_reference(&parameters.m_object), if fireteams.is_empty() { String::from("none") } else { fireteams.join(" | ") })
+            }),
+            e_action_type::set_progress_bar => action.m_set_progress_bar_parameters.as_ref().map(|parameters|
+                format!("{}.set_progress_bar({}, {})", object_reference(&parameters.m_object), object_timer(&parameters.m_object, parameters.m_timer_index), player_filter(&parameters.m_player_filter_modifier))),
+            e_action_type::hud_post_message => action.m_hud_post_message_parameters.as_ref().map(|parameters|
+                format!("{}.show_message_to({:?}, {})", team_or_player_target(&parameters.m_target), parameters.m_sound_index, self.dynamic_string(&parameters.m_string))),
+            e_action_type::timer_set_rate => action.m_timer_set_rate_parameters.as_ref().map(|parameters|
+                format!("{}.set_rate({:?})", custom_timer(&parameters.m_timer), parameters.m_rate)),
+            e_action_type::print_variable => action.m_print_variable_parameters.as_ref().map(|parameters|
+                format!("debug_print({})", self.dynamic_string(&parameters.m_string))),
+            e_action_type::get_player_holding_object => action.m_get_player_holding_object_parameters.as_ref().map(|parameters|
+                format!("{} = {}.try_get_carrier()", player_reference(&parameters.m_player), object_reference(&parameters.m_object))),
+            // Written by write_action.
+            e_action_type::for_each => None,
+            e_action_type::end_round => Some(String::from("game.end_round()")),
+            e_action_type::boundary_set_visible => action.m_boundary_set_visible_parameters.as_ref().map(|parameters|
+                format!("{}.set_shape_visibility({})", object_reference(&parameters.m_object), player_filter(&parameters.m_player_filter_modifier))),
+            e_action_type::object_destroy => action.m_object_destroy_parameters.as_ref().map(|parameters|
+                format!("{}.kill({})", object_reference(&parameters.m_object), parameters.m_no_statistics)),
+            e_action_type::object_set_invincibility => action.m_object_set_invincibility_parameters.as_ref().map(|parameters|
+                format!("{}.set_invincibility({})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable))),
+            e_action_type::random => action.m_random_parameters.as_ref().map(|parameters|
+                format!("{} = rand({})", custom_variable(&parameters.m_variable_1), custom_variable(&parameters.m_variable_2))),
+            e_action_type::break_into_debugger => Some(String::from("debug_break()")),
+            e_action_type::object_get_orientation => action.m_object_get_orientation_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_orientation()", custom_variable(&parameters.m_variable), object_reference(&parameters.m_object))),
+            e_action_type::object_get_velocity => action.m_object_get_velocity_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_speed()", custom_variable(&parameters.m_variable), object_reference(&parameters.m_object))),
+            e_action_type::player_death_get_killing_player => action.m_player_death_get_killing_player_parameters.as_ref().map(|parameters|
+                format!("{} = {}.try_get_killer()", player_reference(&parameters.m_player_2), player_reference(&parameters.m_player_1))),
+            e_action_type::player_death_get_damage_type => action.m_player_death_get_damage_type_parameters.as_ref().map(|parameters|
+                format!("{} = {}.try_get_death_damage_type()", custom_variable(&parameters.m_variable), player_reference(&parameters.m_player))),
+            e_action_type::player_death_get_special_type => action.m_player_death_get_special_type_parameters.as_ref().map(|parameters|
+                format!("{} = {}.try_get_death_damage_mod()", custom_variable(&parameters.m_variable), player_reference(&parameters.m_player))),
+            e_action_type::debugging_enable_tracing => action.m_debugging_enable_tracing_parameters.as_ref().map(|parameters|
+                format!("debug_enable_tracing({})", parameters.m_tracing_enabled)),
+            e_action_type::object_attach => action.m_object_attach_parameters.as_ref().map(|parameters|
+                format!("{}.attach_to({}, {}, {})", object_reference(&parameters.m_object_1), object_reference(&parameters.m_object_2), object_offset(&parameters.m_offset), if parameters.m_absolute_orientation { "absolute" } else { "relative" })),
+            e_action_type::object_detach => action.m_object_detach_parameters.as_ref().map(|parameters|
+                format!("{}.detach()", object_reference(&parameters.m_object))),
+            e_action_type::player_get_place => action.m_player_get_place_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_scoreboard_pos()", custom_variable(&parameters.m_variable), player_reference(&parameters.m_player))),
+            e_action_type::team_get_place => action.m_team_get_place_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_scoreboard_pos()", custom_variable(&parameters.m_variable), team_reference(&parameters.m_team))),
+            e_action_type::player_get_killing_spree_count => action.m_player_get_killing_spree_count_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_spree_count()", custom_variable(&parameters.m_variable), player_reference(&parameters.m_player))),
+            e_action_type::player_adjust_money => action.m_player_adjust_money_parameters.as_ref().map(|parameters|
+                format!("{}.money {} {}", player_reference(&parameters.m_player), math_operation(parameters.m_math_operation), custom_variable(&parameters.m_variable))),
+            e_action_type::player_enable_purchases => action.m_player_enable_purchases_parameters.as_ref().map(|parameters|
+                format!("{}.set_requisition_enabled({}, {})", player_reference(&parameters.m_player), custom_variable(&parameters.m_variable), parameters.m_mode)),
+            e_action_type::player_get_vehicle => action.m_player_get_vehicle_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_vehicle()", object_reference(&parameters.m_object), player_reference(&parameters.m_player))),
+            e_action_type::player_set_vehicle => action.m_player_set_vehicle_parameters.as_ref().map(|parameters|
+                format!("{}.force_into_vehicle({})", player_reference(&parameters.m_player), object_reference(&parameters.m_object))),
+            e_action_type::player_set_unit => action.m_player_set_unit_parameters.as_ref().map(|parameters|
+                format!("{}.set_biped({})", player_reference(&parameters.m_player), object_reference(&parameters.m_object))),
+            e_action_type::timer_reset => action.m_timer_reset_parameters.as_ref().map(|parameters|
+                format!("{}.reset()", custom_timer(&parameters.m_timer))),
+            e_action_type::weapon_set_pickup_priority => action.m_weapon_set_pickup_priority_parameters.as_ref().map(|parameters|
+                format!("{}.set_pickup_priority({:?})", object_reference(&parameters.m_object), parameters.m_weapon_pickup_priority)),
+            e_action_type::object_bounce => action.m_object_bounce_parameters.as_ref().map(|parameters|
+                format!("{}.push_upward()", object_reference(&parameters.m_object))),
+            e_action_type::hud_widget_set_text => action.m_hud_widget_set_text_parameters.as_ref().map(|parameters|
+                format!("{}.set_text({})", hud_widget(parameters.m_widget_index), self.dynamic_string(&parameters.m_string))),
+            e_action_type::hud_widget_set_value => action.m_hud_widget_set_value_parameters.as_ref().map(|parameters|
+                format!("{}.set_value_text({})", hud_widget(parameters.m_widget_index), self.dynamic_string(&parameters.m_string))),
+            e_action_type::hud_widget_set_meter => action.m_hud_widget_set_meter_parameters.as_ref().map(|parameters|
+                format!("{}.set_meter_params({})", hud_widget(parameters.m_widget_index), hud_meter_input(&parameters.m_meter_input))),
+            e_action_type::hud_widget_set_icon => action.m_hud_widget_set_icon_parameters.as_ref().map(|parameters|
+                format!("{}.set_icon({})", hud_widget(parameters.m_widget_index), parameters.m_icon_index)),
+            e_action_type::hud_widget_set_visibility => action.m_hud_widget_set_visibility_parameters.as_ref().map(|parameters|
+                format!("{}.set_visibility({}, {})", hud_widget(parameters.m_widget_index), player_reference(&parameters.m_player), parameters.m_visible)),
+            e_action_type::play_sound => action.m_play_sound_parameters.as_ref().map(|parameters|
+                format!("{}.play_sound({:?}, {})", team_or_player_target(&parameters.m_target), parameters.m_sound_index, if parameters.m_immediate { "immediate" } else { "queued" })),
+            e_action_type::object_set_scale => action.m_object_set_scale_parameters.as_ref().map(|parameters|
+                format!("{}.set_scale({})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable))),
+            e_action_type::navpoint_set_text => action.m_navpoint_set_text_parameters.as_ref().map(|parameters|
+                format!("{}.set_waypoint_text({})", object_reference(&parameters.m_object), self.dynamic_string(&parameters.m_string))),
+            e_action_type::object_get_shield => action.m_object_get_shield_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_shields()", custom_variable(&parameters.m_variable), object_reference(&parameters.m_object))),
+            e_action_type::object_get_health => action.m_object_get_health_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_health()", custom_variable(&parameters.m_variable), object_reference(&parameters.m_object))),
+            e_action_type::player_set_objective => action.m_player_set_objective_parameters.as_ref().map(|parameters|
+                format!("{}.set_round_card_title({})", player_reference(&parameters.m_player), self.dynamic_string(&parameters.m_string))),
+            e_action_type::player_set_objective_allegiance => action.m_player_set_objective_allegiance_parameters.as_ref().map(|parameters|
+                format!("{}.set_round_card_text({})", player_reference(&parameters.m_player), self.dynamic_string(&parameters.m_string))),
+            e_action_type::player_set_objective_allegiance_icon => action.m_player_set_objective_allegiance_icon_parameters.as_ref().map(|parameters|
+                format!("{}.set_round_card_icon({})", player_reference(&parameters.m_player), parameters.m_icon_index)),
+            e_action_type::team_set_coop_spawning => action.m_team_set_coop_spawning_parameters.as_ref().map(|parameters|
+                format!("{}.set_co_op_spawning({})", team_reference(&parameters.m_team), parameters.m_enabled)),
+            e_action_type::team_set_primary_respawn_object => action.m_team_set_primary_respawn_object_parameters.as_ref().map(|parameters|
+                format!("{}.set_primary_respawn_object({})", team_reference(&parameters.m_team), object_reference(&parameters.m_object))),
+            e_action_type::player_set_primary_respawn_object => action.m_player_set_primary_respawn_object_parameters.as_ref().map(|parameters|
+                format!("{}.set_primary_respawn_object({})", player_reference(&parameters.m_player), object_reference(&parameters.m_object))),
+            e_action_type::player_get_fireteam_index => action.m_player_get_fireteam_index_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_fireteam()", custom_variable(&parameters.m_variable), player_reference(&parameters.m_player))),
+            e_action_type::player_set_fireteam_index => action.m_player_set_fireteam_index_parameters.as_ref().map(|parameters|
+                format!("{}.set_fireteam({})", player_reference(&parameters.m_player), custom_variable(&parameters.m_variable))),
+            e_action_type::object_adjust_shield => action.m_object_adjust_shield_parameters.as_ref().map(|parameters|
+                format!("{}.shields {} {}", object_reference(&parameters.m_object), math_operation(parameters.m_operation), custom_variable(&parameters.m_variable))),
+            e_action_type::object_adjust_health => action.m_object_adjust_health_parameters.as_ref().map(|parameters|
+                format!("{}.health {} {}", object_reference(&parameters.m_object), math_operation(parameters.m_operation), custom_variable(&parameters.m_variable))),
+            e_action_type::object_get_distance => action.m_object_get_distance_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_distance_to({})", custom_variable(&parameters.m_variable), object_reference(&parameters.m_object_1), object_reference(&parameters.m_object_2))),
+            e_action_type::object_adjust_maximum_shield => action.m_object_adjust_maximum_shield_parameters.as_ref().map(|parameters|
+                format!("{}.max_shields {} {}", object_reference(&parameters.m_object), math_operation(parameters.m_operation), custom_variable(&parameters.m_variable))),
+            e_action_type::object_adjust_maximum_health => action.m_object_adjust_maximum_health_parameters.as_ref().map(|parameters|
+                format!("{}.max_health {} {}", object_reference(&parameters.m_object), math_operation(parameters.m_operation), custom_variable(&parameters.m_variable))),
+            e_action_type::player_set_requisition_palette => action.m_player_set_requisition_palette_parameters.as_ref().map(|parameters|
+                format!("{}.set_requisition_palette({})", player_reference(&parameters.m_player), parameters.m_new_palette)),
+            e_action_type::device_set_power => action.m_device_set_power_parameters.as_ref().map(|parameters|
+                format!("{}.set_device_power({})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable))),
+            e_action_type::device_get_power => action.m_device_get_power_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_device_power()", custom_variable(&parameters.m_variable), object_reference(&parameters.m_object))),
+            e_action_type::device_set_position => action.m_device_set_position_parameters.as_ref().map(|parameters|
+                format!("{}.set_device_position({})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable))),
+            e_action_type::device_get_position => action.m_device_get_position_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_device_position()", custom_variable(&parameters.m_variable), object_reference(&parameters.m_object))),
+            e_action_type::adjust_grenades => action.m_adjust_grenades_parameters.as_ref().map(|parameters|
+                format!("{}.biped.{:?}s {} {}", player_reference(&parameters.m_player), parameters.m_grenade_type, math_operation(parameters.m_math_operation), custom_variable(&parameters.m_variable))),
+            e_action_type::submit_incident => action.m_submit_incident_parameters.as_ref().map(|parameters|
+                format!("send_incident({}, {}, {})", parameters.m_incident_id, team_or_player_target(&parameters.m_target_1), team_or_player_target(&parameters.m_target_2))),
+            e_action_type::submit_incident_with_custom_value => action.m_submit_incident_with_custom_value_parameters.as_ref().map(|parameters|
+                format!("send_incident({}, {}, {}, {})", parameters.m_incident_id, team_or_player_target(&parameters.m_target_1), team_or_player_target(&parameters.m_target_2), custom_variable(&parameters.m_variable))),
+            e_action_type::set_loadout_palette => action.m_set_loadout_palette_parameters.as_ref().map(|parameters|
+                format!("{}.set_loadout_palette({})", team_or_player_target(&parameters.m_target), parameters.m_loadout_palette_index)),
+            e_action_type::device_set_position_track => action.m_device_set_position_track_parameters.as_ref().map(|parameters|
+                format!("{}.set_device_animation_position_track(animation_name[{}], {})", object_reference(&parameters.m_object), parameters.m_animation_name_index, custom_variable(&parameters.m_variable))),
+            e_action_type::device_animate_position => action.m_device_animate_position_parameters.as_ref().map(|parameters|
+                format!("{}.animate_device_position({}, {}, {}, {})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable_1), custom_variable(&parameters.m_variable_2), custom_variable(&parameters.m_variable_3), custom_variable(&parameters.m_variable_4))),
+            e_action_type::device_set_position_immediate => action.m_device_set_position_immediate_parameters.as_ref().map(|parameters|
+                format!("{}.set_device_actual_position({})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable))),
+            e_action_type::saved_film_insert_marker => action.m_saved_film_insert_marker_parameters.as_ref().map(|parameters|
+                format!("insert_theater_film_marker({}, {})", custom_variable(&parameters.m_variable), self.dynamic_string(&parameters.m_string))),
+            e_action_type::respawn_zone_enable => action.m_respawn_zone_enable_parameters.as_ref().map(|parameters|
+                format!("{}.enable_spawn_zone({})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable))),
+            e_action_type::player_get_weapon => action.m_player_get_weapon_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_weapon({})", object_reference(&parameters.m_object), player_reference(&parameters.m_player), if parameters.m_primary { "primary" } else { "secondary" })),
+            e_action_type::player_get_equipment => action.m_player_get_equipment_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_armor_ability()", object_reference(&parameters.m_object), player_reference(&parameters.m_player))),
+            e_action_type::object_set_never_garbage => action.m_object_set_never_garbage_parameters.as_ref().map(|parameters|
+                format!("{}.set_garbage_collection_disabled({})", object_reference(&parameters.m_object), custom_variable(&parameters.m_variable))),
+            e_action_type::player_get_target_object => action.m_player_get_target_object_parameters.as_ref().map(|parameters|
+                format!("{} = {}.get_crosshair_target()", object_reference(&parameters.m_object), player_reference(&parameters.m_player))),
+            e_action_type::create_tunnel => action.m_create_tunnel_parameters.as_ref().map(|parameters|
+                format!("create_tunnel({}, {}, {}, {}, {})", player_reference(&parameters.m_object_1), player_reference(&parameters.m_object_2), object_reference(&parameters.m_object_type), custom_variable(&parameters.m_variable), player_reference(&parameters.m_object_3))),
+            e_action_type::debug_force_player_view_count => action.m_debug_force_player_view_count_parameters.as_ref().map(|parameters|
+                format!("debug_force_player_view_count({})", custom_variable(&parameters.m_variable))),
+            e_action_type::player_pick_up_weapon => action.m_player_pick_up_weapon_parameters.as_ref().map(|parameters|
+                format!("{}.add_weapon({})", player_reference(&parameters.m_player), object_reference(&parameters.m_object))),
+            e_action_type::player_set_coop_spawning => action.m_player_set_coop_spawning_parameters.as_ref().map(|parameters|
+                format!("{}.set_co_op_spawning({})", player_reference(&parameters.m_player), parameters.m_enabled)),
+            e_action_type::object_set_orientation => action.m_object_set_orientation_parameters.as_ref().map(|parameters|
+                format!("{}.copy_rotation_from({}, {})", object_reference(&parameters.m_object_1), object_reference(&parameters.m_object_2), if parameters.m_absolute_orientation { "absolute" } else { "relative" })),
+            e_action_type::object_face_object => action.m_object_face_object_parameters.as_ref().map(|parameters|
+                format!("{}.face_toward({}, {})", object_reference(&parameters.m_object_1), object_reference(&parameters.m_object_2), object_offset(&parameters.m_offset))),
+            e_action_type::biped_give_weapon => action.m_biped_give_weapon_parameters.as_ref().map(|parameters|
+                format!("{}.add_weapon({}, {:?})", object_reference(&parameters.m_object), object_type(&parameters.m_object_type), parameters.m_mode)),
+            e_action_type::biped_drop_weapon => action.m_biped_drop_weapon_parameters.as_ref().map(|parameters|
+                format!("{}.remove_weapon({}, {})", object_reference(&parameters.m_object), if parameters.m_primary { "primary" } else { "secondary" }, parameters.m_delete_on_drop)),
+            e_action_type::set_scenario_interpolator_state => action.m_set_scenario_interpolator_state_parameters.as_ref().map(|parameters|
+                format!("set_scenario_interpolator_state({}, {})", custom_variable(&parameters.m_variable_1), custom_variable(&parameters.m_variable_2))),
+            e_action_type::get_random_object => action.m_get_random_object_parameters.as_ref().map(|parameters|
+                format!("{} = get_random_object({}, {})", object_reference(&parameters.m_object_1), self.object_filter(parameters.m_filter_index), object_reference(&parameters.m_object_2))),
+            e_action_type::game_grief_record_custom_penalty => action.m_game_grief_record_custom_penalty_parameters.as_ref().map(|parameters|
+                format!("{}.record_griefer_penalty({})", player_reference(&parameters.m_player), custom_variable(&parameters.m_variable))),
+            e_action_type::boundary_set_player_color => action.m_boundary_set_player_color_parameters.as_ref().map(|parameters|
+                format!("{}.set_shape_owner_color({})", object_reference(&parameters.m_object), parameters.m_player_index)),
+        }.unwrap_or_else(|| format!("invalid_{:?}()", action.m_type))
+    }
+
+    fn dynamic_string(&self, string: &c_dynamic_string) -> String {
+        let mut arguments = vec![self.script_string(string.m_string_index as usize)];
+        arguments.extend(string.m_tokens.iter().map(replaceable_token));
+        arguments.join(", ")
+    }
+
+    fn script_string(&self, string_index: usize) -> String {
+        match self.m_variant.m_script_strings.get_string(k_script_string_language_index, string_index) {
+            Some(string) => format!("{string:?}"),
+            None => format!("script_string[{string_index}]"),
+        }
+    }
+
+    fn object_filter(&self, filter_index: i8) -> String {
+        if filter_index < 0 {
+            return String::from("none");
+        }
+
+        match self.m_variant.m_game_engine.m_object_filters.get(filter_index as usize) {
+            Some(filter) => self.script_string(filter.m_label_string_index as usize),
+            None => format!("object_filter[{filter_index}]"),
+        }
+    }
+
+    fn write_line(&mut self, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        if line.is_empty() {
+            self.m_output.push('\n');
+        } else {
+            writeln!(self.m_output, "{}{line}", k_indent.repeat(self.m_indent)).unwrap();
+        }
+    }
+}
+
+fn explicit_player(player: &c_explicit_player) -> String {
+    let index = player.m_explicit_player_type as u8;
+    match player.m_explicit_player_type {
+        e_explicit_player_type::no_player => String::from("no_player"),
+        e_explicit_player_type::current => String::from("current_player"),
+        e_explicit_player_type::hud => String::from("local_player"),
+        e_explicit_player_type::hud_target => String::from("target_player"),
+        e_explicit_player_type::killer => String::from("killer_player"),
+        _ if index >= e_explicit_player_type::global_0 as u8 => format!("global.player[{}]", index - e_explicit_player_type::global_0 as u8),
+        _ => format!("player[{}]", index - e_explicit_player_type::player_0 as u8),
+    }
+}
+
+fn explicit_object(object: &c_explicit_object) -> String {
+    let index = object.m_explicit_object_type as u8;
+    match object.m_explicit_object_type {
+        e_explicit_object_type::no_object => String::from("no_object"),
+        e_explicit_object_type::current => String::from("current_object"),
+        e_explicit_object_type::hud_target => String::from("target_object"),
+        e_explicit_object_type::killed => String::from("killed_object"),
+        e_explicit_object_type::killer => String::from("killer_object"),
+        e_explicit_object_type::unknown_21 => String::from("unknown_object_21"),
+        _ => format!("global.object[{}]", index - e_explicit_object_type::global_0 as u8),
+    }
+}
+
+fn explicit_team(team: &c_explicit_team) -> String {
+    let index = team.m_explicit_team_type as u8;
+    match team.m_explicit_team_type {
+        e_explicit_team_type::none => String::from("no_team"),
+        e_explicit_team_type::neutral => String::from("neutral_team"),
+        e_explicit_team_type::current_team => String::from("current_team"),
+        e_explicit_team_type::local_team => String::from("local_team"),
+        e_explicit_team_type::target_team => String::from("target_team"),
+        _ if index >= e_explicit_team_type::global_0 as u8 => format!("global.team[{}]", index - e_explicit_team_type::global_0 as u8),
+        _ => format!("team[{}]", index - e_explicit_team_type::team_0 as u8),
+    }
+}
+
+fn invalid(value: Option<String>) -> String {
+    value.unwrap_or_else(|| String::from("<invalid>"))
+}
+
+fn member(owner: Option<String>, name: &str, index: Option<u8>) -> String {
+    match index {
+        Some(index) => format!("{}.{name}[{index}]", invalid(owner)),
+        None => format!("{}.{name}[<invalid>]", invalid(owner)),
+    }
+}
+
+fn player_reference(player: &c_player_reference) -> String {
+    let explicit_player = player.m_player.as_ref().map(explicit_player);
+    let explicit_object = player.m_object.as_ref().map(explicit_object);
+    let explicit_team = player.m_team.as_ref().map(explicit_team);
+
+    match player.m_type {
+        e_player_reference_type::global_player => invalid(explicit_player),
+        e_player_reference_type::player_player => member(explicit_player, "player", player.m_variable_index),
+        e_player_reference_type::object_player => member(explicit_object, "player", player.m_variable_index),
+        e_player_reference_type::team_player => member(explicit_team, "player", player.m_variable_index),
+    }
+}
+
+fn object_reference(object: &c_object_reference) -> String {
+    let explicit_player = object.m_player.as_ref().map(explicit_player);
+    let explicit_object = object.m_object.as_ref().map(explicit_object);
+    let explicit_team = object.m_team.as_ref().map(explicit_team);
+
+    match object.m_type {
+        e_object_reference_type::global_object => invalid(explicit_object),
+        e_object_reference_type::player_object => member(explicit_player, "object", object.m_variable_index),
+        e_object_reference_type::object_object => member(explicit_object, "object", object.m_variable_index),
+        e_object_reference_type::team_object => member(explicit_team, "object", object.m_variable_index),
+        e_object_reference_type::player_biped => format!("{}.biped", invalid(explicit_player)),
+        e_object_reference_type::player_player_biped => format!("{}.biped", member(explicit_player, "player", object.m_variable_index)),
+        e_object_reference_type::object_player_biped => format!("{}.biped", member(explicit_object, "player", object.m_variable_index)),
+        e_object_reference_type::team_player_biped => format!("{}.biped", member(explicit_team, "player", object.m_variable_index)),
+    }
+}
+
+fn team_reference(team: &c_team_reference) -> String {
+    let explicit_player = team.m_player.as_ref().map(explicit_player);
+    let explicit_object = team.m_object.as_ref().map(explicit_object);
+    let explicit_team = team.m_team.as_ref().map(explicit_team);
+
+    match team.m_type {
+        e_team_reference_type::global_team => invalid(explicit_team),
+        e_team_reference_type::player_team => member(explicit_player, "team", team.m_variable_index),
+        e_team_reference_type::object_team => member(explicit_object, "team", team.m_variable_index),
+        e_team_reference_type::team_team => member(explicit_team, "team", team.m_variable_index),
+        e_team_reference_type::player_owner_team => format!("{}.team", invalid(explicit_player)),
+        e_team_reference_type::object_owner_team => format!("{}.team", invalid(explicit_object)),
+    }
+}
+
+fn custom_timer(timer: &c_custom_timer_reference) -> String {
+    match timer.m_type {
+        e_custom_timer_type::global => member(Some(String::from("global")), "timer", timer.m_variable_index),
+        e_custom_timer_type::player => member(timer.m_player.as_ref().map(explicit_player), "timer", timer.m_variable_index),
+        e_custom_timer_type::team => member(timer.m_team.as_ref().map(explicit_team), "timer", timer.m_variable_index),
+        e_custom_timer_type::object => member(timer.m_object.as_ref().map(explicit_object), "timer", timer.m_variable_index),
+        e_custom_timer_type::round => String::from("game.round_timer"),
+        e_custom_timer_type::sudden_death => String::from("game.sudden_death_timer"),
+        e_custom_timer_type::grace_period => String::from("game.grace_period_timer"),
+    }
+}
+
+fn custom_variable(variable: &c_custom_variable_reference) -> String {
+    let explicit_player = variable.m_player.as_ref().map(explicit_player);
+    let explicit_object = variable.m_object.as_ref().map(explicit_object);
+    let explicit_team = variable.m_team.as_ref().map(explicit_team);
+
+    match variable.m_type {
+        e_custom_variable_type::constant => invalid(variable.m_immediate_value.map(|value| value.to_string())),
+        e_custom_variable_type::player_number => member(explicit_player, "number", variable.m_variable_index),
+        e_custom_variable_type::object_number => member(explicit_object, "number", variable.m_variable_index),
+        e_custom_variable_type::team_number => member(explicit_team, "number", variable.m_variable_index),
+        e_custom_variable_type::global_number => member(Some(String::from("global")), "number", variable.m_variable_index),
+        e_custom_variable_type::option => invalid(variable.m_option_index.map(|option_index| format!("script_option[{option_index}]"))),
+        e_custom_variable_type::spawn_object => format!("{}.spawn_sequence", invalid(explicit_object)),
+        e_custom_variable_type::team_score => format!("{}.score", invalid(explicit_team)),
+        e_custom_variable_type::player_score => format!("{}.score", invalid(explicit_player)),
+        e_custom_variable_type::player_money => format!("{}.money", invalid(explicit_player)),
+        e_custom_variable_type::player_rating => format!("{}.rating", invalid(explicit_player)),
+        e_custom_variable_type::player_stat => member(explicit_player, "script_stat", variable.m_statistic_index),
+        e_custom_variable_type::team_stat => member(explicit_team, "script_stat", variable.m_statistic_index),
+        game_setting => format!("game.{game_setting:?}"),
+    }
+}
+
+fn variant_variable(variable: &s_variant_variable) -> String {
+    match variable.m_type {
+        e_variable_type::custom_variable => invalid(variable.m_custom_variable.as_ref().map(custom_variable)),
+        e_variable_type::player => invalid(variable.m_player.as_ref().map(player_reference)),
+        e_variable_type::object => invalid(variable.m_object.as_ref().map(object_reference)),
+        e_variable_type::team => invalid(variable.m_team.as_ref().map(team_reference)),
+        e_variable_type::custom_timer => invalid(variable.m_custom_timer.as_ref().map(custom_timer)),
+    }
+}
+
+fn replaceable_token(token: &c_replaceable_token) -> String {
+    match token.m_type {
+        e_replaceable_token_type::none => String::from("none"),
+        e_replaceable_token_type::player => invalid(token.m_player.as_ref().map(player_reference)),
+        e_replaceable_token_type::team => invalid(token.m_team.as_ref().map(team_reference)),
+        e_replaceable_token_type::object => invalid(token.m_object.as_ref().map(object_reference)),
+        e_replaceable_token_type::custom_variable => invalid(token.m_custom_variable.as_ref().map(custom_variable)),
+        e_replaceable_token_type::custom_timer => invalid(token.m_custom_timer.as_ref().map(custom_timer)),
+    }
+}
+
+fn team_or_player_target(target: &s_team_or_player_target) -> String {
+    match target.m_target {
+        e_action_team_or_player_target::team => invalid(target.m_team.as_ref().map(team_reference)),
+        e_action_team_or_player_target::player => invalid(target.m_player.as_ref().map(player_reference)),
+        e_action_team_or_player_target::everyone => String::from("everyone"),
+    }
+}
+
+fn player_filter(filter: &c_player_filter_modifier) -> String {
+    match filter.m_type {
+        e_player_filter_type::specific_player => format!(
+            "mod_player, {}, {}",
+            invalid(filter.m_player.as_ref().map(player_reference)),
+            invalid(filter.m_variable.as_ref().map(custom_variable)),
+        ),
+        filter_type => format!("{filter_type:?}"),
+    }
+}
+
+fn hud_meter_input(input: &c_megalogamengine_hud_meter_input) -> String {
+    match input.m_type {
+        e_megalogamengine_hud_meter_input_type::none => String::from("none"),
+        e_megalogamengine_hud_meter_input_type::number => format!(
+            "number, {}, {}",
+            invalid(input.m_variable_1.as_ref().map(custom_variable)),
+            invalid(input.m_variable_2.as_ref().map(custom_variable)),
+        ),
+        e_megalogamengine_hud_meter_input_type::timer => format!("timer, {}", invalid(input.m_timer.as_ref().map(custom_timer))),
+    }
+}
+
+fn hud_widget(widget_index: i8) -> String {
+    format!("hud_widget[{widget_index}]")
+}
+
+fn object_timer(object: &c_object_reference, timer_index: i8) -> String {
+    if timer_index < 0 {
+        String::from("none")
+    } else {
+        format!("{}.timer[{timer_index}]", object_reference(object))
+    }
+}
+
+fn object_type(object_type: &c_object_type_reference) -> String {
+    if object_type.m_object_type_index < 0 {
+        String::from("none")
+    } else {
+        format!("object_type[{}]", object_type.m_object_type_index)
+    }
+}
+
+fn object_offset(offset: &s_object_offset) -> String {
+    format!("{}, {}, {}", offset.x, offset.y, offset.z)
+}
+
+fn killer_types(killer_type: &e_player_death_killer_type_flags) -> String {
+    let killer_types: Vec<&str> = [
+        (killer_type.environment, "environment"),
+        (killer_type.suicide, "suicide"),
+        (killer_type.enemy, "enemy"),
+        (killer_type.betrayal, "betrayal"),
+        (killer_type.quit_game, "quit_game"),
+    ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect();
+
+    if killer_types.is_empty() { String::from("none") } else { killer_types.join(" | ") }
+}
+
+fn math_operation(operation: e_math_operation) -> &'static str {
+    match operation {
+        e_math_operation::add => "+=",
+        e_math_operation::subtract => "-=",
+        e_math_operation::multiply => "*=",
+        e_math_operation::divide => "/=",
+        e_math_operation::set_to => "=",
+        e_math_operation::modulo => "%=",
+        e_math_operation::and => "&=",
+        e_math_operation::or => "|=",
+        e_math_operation::xor => "^=",
+        e_math_operation::not => "~=",
+        e_math_operation::abs => "abs=",
+    }
+}
+
+fn numeric_comparison(comparison: e_numeric_comparison) -> &'static str {
+    match comparison {
+        e_numeric_comparison::less_than => "<",
+        e_numeric_comparison::greater_than => ">",
+        e_numeric_comparison::equal_to => "==",
+        e_numeric_comparison::less_than_or_equal_to => "<=",
+        e_numeric_comparison::greater_than_or_equal_to => ">=",
+        e_numeric_comparison::not_equal_to => "!=",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_actions::{s_action_for_each_parameters, s_action_set_parameters};
+    use crate::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_conditions::s_condition_player_is_elite_parameters;
+
+    fn global_number(index: u8) -> s_variant_variable {
+        s_variant_variable {
+            m_type: e_variable_type::custom_variable,
+            m_custom_variable: Some(c_custom_variable_reference {
+                m_type: e_custom_variable_type::global_number,
+                m_variable_index: Some(index),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn constant(value: i16) -> s_variant_variable {
+        s_variant_variable {
+            m_type: e_variable_type::custom_variable,
+            m_custom_variable: Some(c_custom_variable_reference {
+                m_type: e_custom_variable_type::constant,
+                m_immediate_value: Some(value),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decompile_nests_for_each_triggers_and_conditions() {
+        let mut variant = c_game_engine_custom_variant::default();
+        let game_engine = &mut variant.m_game_engine;
+
+        game_engine.m_triggers = vec![
+            c_trigger { m_first_action: 0, m_action_count: 2, ..Default::default() },
+            c_trigger { m_trigger_type: e_trigger_type::subroutine, m_execution_mode: e_trigger_execution_mode::player, m_first_condition: 0, m_condition_count: 1, m_first_action: 2, m_action_count: 1, ..Default::default() },
+        ];
+        game_engine.m_conditions = vec![c_condition {
+            m_type: e_condition_type::player_is_elite,
+            m_negated: true,
+            m_player_is_elite_parameters: Some(s_condition_player_is_elite_parameters {
+                m_player: c_player_reference {
+                    m_player: Some(c_explicit_player { m_explicit_player_type: e_explicit_player_type::current }),
+                    ..Default::default()
+                },
+            }),
+            ..Default::default()
+        }];
+        game_engine.m_actions = vec![
+            c_action {
+                m_type: e_action_type::set,
+                m_set_parameters: Some(s_action_set_parameters { m_variable_1: global_number(0), m_variable_2: constant(5), m_operation: e_math_operation::set_to }),
+                ..Default::default()
+            },
+            c_action {
+                m_type: e_action_type::for_each,
+                m_for_each_parameters: Some(s_action_for_each_parameters { m_trigger_index: 1 }),
+                ..Default::default()
+            },
+            c_action {
+                m_type: e_action_type::set,
+                m_set_parameters: Some(s_action_set_parameters { m_variable_1: global_number(0), m_variable_2: constant(1), m_operation: e_math_operation::add }),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            decompile_megalo_script(&variant),
+            "do\n\
+             \x20   global.number[0] = 5\n\
+             \x20   for each player do\n\
+             \x20       if not current_player.is_elite() then\n\
+             \x20           global.number[0] += 1\n\
+             \x20       end\n\
+             \x20   end\n\
+             end\n\
+             \n"
+        );
+    }
+}
@@ -5,6 +5,13 @@ use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogameng
 use blf_lib::io::bitstream::{c_bitstream_reader, c_bitstream_writer};
 use blf_lib_derivable::result::BLFLibResult;
 
+// Global variables a script can declare, per type.
+pub const k_maximum_global_numeric_variables: usize = 12;
+pub const k_maximum_global_timer_variables: usize = 8;
+pub const k_maximum_global_team_variables: usize = 8;
+pub const k_maximum_global_player_variables: usize = 8;
+pub const k_maximum_global_object_variables: usize = 16;
+
 /// Variable replication mode (`network-state`, 2 bits).
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToPrimitive, FromPrimitive, crate::derive::c_enum)]
@@ -171,6 +171,40 @@ c_string_table<
     count_bit_length,
 >
 {
+    pub fn get_string(&self, language_index: usize, string_index: usize) -> Option<&str> {
+        if language_index >= k_language_count {
+            return None;
+        }
+
+        self.strings[language_index].get(string_index)?.as_deref()
+    }
+
+    pub fn get_string_count(&self) -> usize {
+        self.strings[0].len()
+    }
+
+    pub fn find_string(&self, language_index: usize, string: &str) -> Option<usize> {
+        if language_index >= k_language_count {
+            return None;
+        }
+
+        self.strings[language_index].iter().position(|existing| existing.as_deref() == Some(string))
+    }
+
+    /// Appends a non-localized string, the same in every language, and returns its index.
+    pub fn add_string(&mut self, string: &str) -> BLFLibResult<usize> {
+        let string_index = self.get_string_count();
+        if string_index >= max_string_count {
+            return Err(format!("String table is full, it can hold at most {max_string_count} strings").into());
+        }
+
+        for language_index in 0..k_language_count {
+            self.strings[language_index].push(Some(string.to_string()));
+        }
+
+        Ok(string_index)
+    }
+
     pub fn decode(&mut self, bitstream: &mut c_bitstream_reader) -> BLFLibResult {
         let string_count: usize = bitstream.read_integer("string-count", count_bit_length)?;
 
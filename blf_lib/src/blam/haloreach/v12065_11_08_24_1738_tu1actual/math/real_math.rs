@@ -146,7 +146,11 @@ pub fn dequantize_real(
 
 // Octahedral Quantization
 pub fn quantize_unit_vector3d_fast<const N: usize>(v: &real_vector3d) -> BLFLibResult<i32> {
-    let constants = get_unit_vector_encoding_constants(N)?;
+    quantize_unit_vector3d(v, N)
+}
+
+pub fn quantize_unit_vector3d(v: &real_vector3d, bit_count: usize) -> BLFLibResult<i32> {
+    let constants = get_unit_vector_encoding_constants(bit_count)?;
 
     let x = v.i.0 as f64;
     let y = v.j.0 as f64;
@@ -11,3 +11,4 @@ pub mod game_engine_player_rating_parameters;
 pub mod game_engine_survival;
 pub mod game_engine_sandbox;
 pub mod game_engine_timer;
+pub mod game_variant_conversion;
@@ -0,0 +1,470 @@
+//! Converts game variants between Halo: Reach TU1 (Xbox 360) and MCC.
+//!
+//! Both builds share nearly every structure, so variants are mapped field by field through their
+//! JSON form, where enums are matched by name. The few structures that differ are patched on the
+//! way through.
+//!
+//! TU1 → MCC always succeeds for custom and survival variants. MCC → TU1 rejects MCC-only megalo
+//! features, and moves temporary object, player, team and number references onto free globals,
+//! failing when there aren't enough.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use serde_json::{Map, Value};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::game_variant::{c_game_variant as c_game_variant_tu1, e_game_engine_type as e_game_engine_type_tu1};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_variable_metadata::{k_maximum_global_numeric_variables, k_maximum_global_object_variables, k_maximum_global_player_variables, k_maximum_global_team_variables, s_variable_metadata as s_variable_metadata_tu1};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::saved_games::saved_game_files::c_content_item_metadata;
+use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::game_variant::{c_game_engine_custom_variant, c_game_variant, e_game_engine_type, s_custom_game_engine_definition};
+use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::megalogamengine::megalogamengine_actions::{e_action_type, e_player_purchase_mode_flags};
+use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::string_table::c_string_table;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
+
+/// TU1 survival variants store a campaign difficulty where MCC stores its encoding version.
+const k_tu1_survival_campaign_difficulty_level: u8 = 1;
+/// The MCC survival encoding version that carries `m_additional_flags`.
+const k_mcc_survival_encoding_version: u8 = 2;
+/// MCC names built-in variants with string ids like `$hr_gvar_team_slayer`, which TU1 would display verbatim.
+const k_hr_gvar_prefix: &str = "$hr_gvar_";
+
+const k_mcc_exclusive_actions: [e_action_type; 8] = [
+    e_action_type::begin,
+    e_action_type::hs_function_call,
+    e_action_type::get_button_time,
+    e_action_type::team_set_vehicle_spawning,
+    e_action_type::player_set_vehicle_spawning,
+    e_action_type::set_player_respawn_vehicle,
+    e_action_type::set_team_respawn_vehicle,
+    e_action_type::hide_object,
+];
+
+const k_mcc_exclusive_math_operations: [&str; 2] = ["lshift", "rshift"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum e_reach_gametype_conversion_error {
+    forge_variant,
+    campaign_variant,
+    mcc_exclusive_action,
+    mcc_exclusive_math_operator,
+    mcc_survival_additional_flags,
+    insufficient_global_slots,
+}
+
+impl e_reach_gametype_conversion_error {
+    /// Recovers the conversion error from an error returned by one of the conversions, if it was one.
+    pub fn from_error(error: &BLFLibError) -> Option<Self> {
+        match error {
+            BLFLibError::Other(error) => error.downcast_ref::<Self>().copied(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for e_reach_gametype_conversion_error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::forge_variant => write!(f, "Forge variants can't be converted"),
+            Self::campaign_variant => write!(f, "Campaign variants can't be converted"),
+            Self::mcc_exclusive_action => write!(f, "The script uses actions which only exist on MCC"),
+            Self::mcc_exclusive_math_operator => write!(f, "The script uses math operators which only exist on MCC"),
+            Self::mcc_survival_additional_flags => write!(f, "The survival variant uses additional flags which only exist on MCC"),
+            Self::insufficient_global_slots => write!(f, "There are not enough free globals to hold the script's temporary variables"),
+        }
+    }
+}
+
+impl std::error::Error for e_reach_gametype_conversion_error {}
+
+impl From<e_reach_gametype_conversion_error> for BLFLibError {
+    fn from(error: e_reach_gametype_conversion_error) -> BLFLibError {
+        BLFLibError::Other(Box::new(error))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum e_slot_kind {
+    object,
+    player,
+    team,
+    number,
+}
+
+const k_slot_kind_count: usize = 4;
+const k_maximum_global_slots: [usize; k_slot_kind_count] = [
+    k_maximum_global_object_variables,
+    k_maximum_global_player_variables,
+    k_maximum_global_team_variables,
+    k_maximum_global_numeric_variables,
+];
+
+/// Maps each temporary index to the global index replacing it, per slot kind.
+#[derive(Default)]
+struct s_temporary_relocation {
+    slots: [BTreeMap<usize, usize>; k_slot_kind_count],
+}
+
+pub fn convert_tu1_game_variant_to_mcc(from: &c_game_variant_tu1) -> BLFLibResult<c_game_variant> {
+    match from.m_game_engine {
+        e_game_engine_type_tu1::sandbox => return Err(e_reach_gametype_conversion_error::forge_variant.into()),
+        e_game_engine_type_tu1::campaign => return Err(e_reach_gametype_conversion_error::campaign_variant.into()),
+        _ => {}
+    }
+
+    let mut variant = serde_json::to_value(from)?;
+
+    visit_objects(&mut variant, &mut |object| {
+        if let Some(Value::Object(parameters)) = object.get_mut("m_player_enable_purchases_parameters") {
+            let mode = parameters.get("m_mode").and_then(Value::as_u64).unwrap_or_default();
+            parameters.insert("m_mode".to_string(), serde_json::to_value(e_player_purchase_mode_flags::from_raw(mode as u8))?);
+        }
+        Ok(())
+    })?;
+
+    if let Some(Value::Object(survival_variant)) = variant.get_mut("m_survival_variant") {
+        survival_variant.remove("m_campaign_difficulty_level");
+        survival_variant.insert("m_encoding_version".to_string(), k_mcc_survival_encoding_version.into());
+        survival_variant.insert("m_additional_flags".to_string(), 0.into());
+    }
+
+    Ok(serde_json::from_value(variant)?)
+}
+
+pub fn convert_mcc_game_variant_to_tu1(from: &c_game_variant) -> BLFLibResult<c_game_variant_tu1> {
+    match from.m_game_engine {
+        e_game_engine_type::sandbox => return Err(e_reach_gametype_conversion_error::forge_variant.into()),
+        e_game_engine_type::campaign => return Err(e_reach_gametype_conversion_error::campaign_variant.into()),
+        e_game_engine_type::survival => {
+            if from.m_survival_variant.as_ref().is_some_and(|survival_variant| survival_variant.m_additional_flags != 0) {
+                return Err(e_reach_gametype_conversion_error::mcc_survival_additional_flags.into());
+            }
+        }
+        _ => {}
+    }
+
+    let mut variant = serde_json::to_value(from)?;
+
+    let mut relocation = s_temporary_relocation::default();
+    if let (Some(custom_variant), Some(engine)) = (&from.m_custom_variant, variant.pointer_mut("/m_custom_variant/m_game_engine")) {
+        check_mcc_exclusive_script(&custom_variant.m_game_engine, engine)?;
+        relocation = plan_temporary_relocation(&custom_variant.m_game_engine, engine)?;
+        relocate_temporaries(engine, &relocation)?;
+    }
+
+    visit_objects(&mut variant, &mut |object| {
+        if let Some(Value::Object(parameters)) = object.get_mut("m_player_enable_purchases_parameters") {
+            if let Some(mode) = parameters.get_mut("m_mode") {
+                let mode_flags: e_player_purchase_mode_flags = serde_json::from_value(mode.take())?;
+                *mode = mode_flags.to_raw().into();
+            }
+        }
+        Ok(())
+    })?;
+
+    if let Some(Value::Object(survival_variant)) = variant.get_mut("m_survival_variant") {
+        survival_variant.remove("m_encoding_version");
+        survival_variant.remove("m_additional_flags");
+        survival_variant.insert("m_campaign_difficulty_level".to_string(), k_tu1_survival_campaign_difficulty_level.into());
+    }
+
+    let mut converted: c_game_variant_tu1 = serde_json::from_value(variant)?;
+
+    if let (Some(from_custom_variant), Some(custom_variant)) = (&from.m_custom_variant, &mut converted.m_custom_variant) {
+        extend_global_variable_metadata(&mut custom_variant.m_game_engine.m_global_variable_metadata, &relocation);
+        resolve_hr_gvar_strings(from_custom_variant, &mut custom_variant.m_base_variant.m_metadata)?;
+    }
+
+    Ok(converted)
+}
+
+fn check_mcc_exclusive_script(definition: &s_custom_game_engine_definition, engine: &mut Value) -> BLFLibResult {
+    if definition.m_actions.iter().any(|action| k_mcc_exclusive_actions.contains(&action.m_type)) {
+        return Err(e_reach_gametype_conversion_error::mcc_exclusive_action.into());
+    }
+
+    let mut uses_mcc_exclusive_math_operation = false;
+    visit_objects(engine, &mut |object| {
+        let operation = object.get("m_operation").or_else(|| object.get("m_math_operation"));
+        if operation.and_then(Value::as_str).is_some_and(|operation| k_mcc_exclusive_math_operations.contains(&operation)) {
+            uses_mcc_exclusive_math_operation = true;
+        }
+        Ok(())
+    })?;
+    if uses_mcc_exclusive_math_operation {
+        return Err(e_reach_gametype_conversion_error::mcc_exclusive_math_operator.into());
+    }
+
+    Ok(())
+}
+
+/// Assigns every temporary the script uses the lowest global slot which is neither referenced nor declared.
+fn plan_temporary_relocation(definition: &s_custom_game_engine_definition, engine: &mut Value) -> BLFLibResult<s_temporary_relocation> {
+    let metadata = &definition.m_global_variable_metadata;
+    let mut used_globals: [BTreeSet<usize>; k_slot_kind_count] = [
+        (0..metadata.m_object_variables.len()).collect(),
+        (0..metadata.m_player_variables.len()).collect(),
+        (0..metadata.m_team_variables.len()).collect(),
+        (0..metadata.m_numeric_variables.len()).collect(),
+    ];
+    let mut used_temporaries: [BTreeSet<usize>; k_slot_kind_count] = Default::default();
+
+    visit_objects(engine, &mut |object| {
+        if let Some((kind, is_temporary, index)) = get_slot_reference(object) {
+            if is_temporary {
+                used_temporaries[kind as usize].insert(index);
+            } else {
+                used_globals[kind as usize].insert(index);
+            }
+        }
+        Ok(())
+    })?;
+
+    let mut relocation = s_temporary_relocation::default();
+    for kind in 0..k_slot_kind_count {
+        for &temporary_index in &used_temporaries[kind] {
+            let global_index = (0..k_maximum_global_slots[kind])
+                .find(|index| !used_globals[kind].contains(index))
+                .ok_or(e_reach_gametype_conversion_error::insufficient_global_slots)?;
+            used_globals[kind].insert(global_index);
+            relocation.slots[kind].insert(temporary_index, global_index);
+        }
+    }
+
+    Ok(relocation)
+}
+
+fn relocate_temporaries(engine: &mut Value, relocation: &s_temporary_relocation) -> BLFLibResult {
+    visit_objects(engine, &mut |object| {
+        let Some((kind, true, temporary_index)) = get_slot_reference(object) else {
+            return Ok(());
+        };
+        let global_index = relocation.slots[kind as usize][&temporary_index];
+        match kind {
+            e_slot_kind::object => { object.insert("m_explicit_object_type".to_string(), format!("global_{global_index}").into()); }
+            e_slot_kind::player => { object.insert("m_explicit_player_type".to_string(), format!("global_{global_index}").into()); }
+            e_slot_kind::team => { object.insert("m_explicit_team_type".to_string(), format!("global_{global_index}").into()); }
+            e_slot_kind::number => {
+                object.insert("m_type".to_string(), "global_number".into());
+                object.insert("m_variable_index".to_string(), global_index.into());
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Declares the globals which temporaries were moved onto, as local variables.
+fn extend_global_variable_metadata<
+    const numeric_variable_count_bits: usize,
+    const timer_variable_count_bits: usize,
+    const team_variable_count_bits: usize,
+    const player_variable_count_bits: usize,
+    const object_variable_count_bits: usize,
+>(
+    metadata: &mut s_variable_metadata_tu1<
+        numeric_variable_count_bits,
+        timer_variable_count_bits,
+        team_variable_count_bits,
+        player_variable_count_bits,
+        object_variable_count_bits,
+    >,
+    relocation: &s_temporary_relocation,
+) {
+    let required_count = |kind: e_slot_kind| relocation.slots[kind as usize].values().max().map_or(0, |index| index + 1);
+
+    let object_count = required_count(e_slot_kind::object).max(metadata.m_object_variables.len());
+    metadata.m_object_variables.resize_with(object_count, Default::default);
+    let player_count = required_count(e_slot_kind::player).max(metadata.m_player_variables.len());
+    metadata.m_player_variables.resize_with(player_count, Default::default);
+    let team_count = required_count(e_slot_kind::team).max(metadata.m_team_variables.len());
+    metadata.m_team_variables.resize_with(team_count, Default::default);
+    let numeric_count = required_count(e_slot_kind::number).max(metadata.m_numeric_variables.len());
+    metadata.m_numeric_variables.resize_with(numeric_count, Default::default);
+}
+
+fn resolve_hr_gvar_strings(
+    from: &c_game_engine_custom_variant,
+    metadata: &mut c_content_item_metadata,
+) -> BLFLibResult {
+    let name = metadata.name.get_string();
+    if let Some(string_id) = name.strip_prefix(k_hr_gvar_prefix) {
+        let name = get_first_localized_string(&from.m_localized_name)
+            .map(str::to_string)
+            .unwrap_or_else(|| string_id.replace('_', " "));
+        metadata.name.set_string_trimmed(&name)?;
+    }
+
+    if metadata.description.get_string().starts_with(k_hr_gvar_prefix) {
+        let description = get_first_localized_string(&from.m_localized_description).unwrap_or_default();
+        metadata.description.set_string_trimmed(&description.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn get_first_localized_string<
+    const max_string_count: usize,
+    const max_string_length: usize,
+    const offset_bit_length: usize,
+    const buffer_size_bit_length: usize,
+    const count_bit_length: usize,
+>(
+    string_table: &c_string_table<max_string_count, max_string_length, offset_bit_length, buffer_size_bit_length, count_bit_length>,
+) -> Option<&str> {
+    string_table.get_string(0, 0).filter(|string| !string.is_empty())
+}
+
+/// Reads an explicit object, player or team, or a custom variable reference, as a slot kind,
+/// whether it's a temporary, and its index.
+fn get_slot_reference(object: &Map<String, Value>) -> Option<(e_slot_kind, bool, usize)> {
+    let explicit_types = [
+        ("m_explicit_object_type", e_slot_kind::object),
+        ("m_explicit_player_type", e_slot_kind::player),
+        ("m_explicit_team_type", e_slot_kind::team),
+    ];
+    for (field, kind) in explicit_types {
+        if let Some(name) = object.get(field).and_then(Value::as_str) {
+            if let Some(index) = name.strip_prefix("temporary_").and_then(|index| index.parse().ok()) {
+                return Some((kind, true, index));
+            }
+            if let Some(index) = name.strip_prefix("global_").and_then(|index| index.parse().ok()) {
+                return Some((kind, false, index));
+            }
+            return None;
+        }
+    }
+
+    let variable_index = object.get("m_variable_index").and_then(Value::as_u64)? as usize;
+    match object.get("m_type").and_then(Value::as_str)? {
+        "temporary_number" => Some((e_slot_kind::number, true, variable_index)),
+        "global_number" => Some((e_slot_kind::number, false, variable_index)),
+        _ => None,
+    }
+}
+
+fn visit_objects(value: &mut Value, visitor: &mut impl FnMut(&mut Map<String, Value>) -> BLFLibResult) -> BLFLibResult {
+    match value {
+        Value::Object(object) => {
+            visitor(object)?;
+            for child in object.values_mut() {
+                visit_objects(child, visitor)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                visit_objects(item, visitor)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::game::megalogamengine::megalogamengine_explicit_player::e_explicit_player_type as e_explicit_player_type_tu1;
+    use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::game_engine_sandbox::c_game_engine_sandbox_variant;
+    use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::game_engine_survival::c_game_engine_survival_variant;
+    use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::megalogamengine::megalogamengine_actions::{c_action, e_math_operation, s_action_set_parameters};
+    use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::megalogamengine::megalogamengine_explicit_player::{c_explicit_player, e_explicit_player_type};
+    use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::megalogamengine::megalogamengine_player_reference::c_player_reference;
+    use blf_lib::blam::haloreach_mcc::v_untracked_25_08_16_1352::game::megalogamengine::megalogamengine_variant_variable::{e_variable_type, s_variant_variable};
+
+    fn custom_variant_with_actions(actions: Vec<c_action>) -> c_game_variant {
+        let mut custom_variant = c_game_engine_custom_variant::default();
+        custom_variant.m_game_engine.m_actions = actions;
+        c_game_variant {
+            m_game_engine: e_game_engine_type::megalogamengine,
+            m_custom_variant: Some(custom_variant),
+            ..Default::default()
+        }
+    }
+
+    fn set_player_action(player_type: e_explicit_player_type, operation: e_math_operation) -> c_action {
+        let variable = s_variant_variable {
+            m_type: e_variable_type::player,
+            m_player: Some(c_player_reference {
+                m_player: Some(c_explicit_player { m_explicit_player_type: player_type }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        c_action {
+            m_type: e_action_type::set,
+            m_set_parameters: Some(s_action_set_parameters {
+                m_variable_1: variable.clone(),
+                m_variable_2: variable,
+                m_operation: operation,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn conversion_error(result: BLFLibResult<c_game_variant_tu1>) -> Option<e_reach_gametype_conversion_error> {
+        e_reach_gametype_conversion_error::from_error(&result.err()?)
+    }
+
+    #[test]
+    fn rejects_forge_and_mcc_exclusive_features() {
+        let forge_variant = c_game_variant {
+            m_game_engine: e_game_engine_type::sandbox,
+            m_sandbox_variant: Some(c_game_engine_sandbox_variant::default()),
+            ..Default::default()
+        };
+        assert_eq!(conversion_error(convert_mcc_game_variant_to_tu1(&forge_variant)), Some(e_reach_gametype_conversion_error::forge_variant));
+
+        let survival_variant = c_game_variant {
+            m_game_engine: e_game_engine_type::survival,
+            m_survival_variant: Some(c_game_engine_survival_variant { m_encoding_version: 2, m_additional_flags: 1, ..Default::default() }),
+            ..Default::default()
+        };
+        assert_eq!(conversion_error(convert_mcc_game_variant_to_tu1(&survival_variant)), Some(e_reach_gametype_conversion_error::mcc_survival_additional_flags));
+
+        let hide_object = c_action { m_type: e_action_type::hide_object, m_hide_object_parameters: Some(Default::default()), ..Default::default() };
+        assert_eq!(conversion_error(convert_mcc_game_variant_to_tu1(&custom_variant_with_actions(vec![hide_object]))), Some(e_reach_gametype_conversion_error::mcc_exclusive_action));
+
+        let lshift = set_player_action(e_explicit_player_type::current, e_math_operation::lshift);
+        assert_eq!(conversion_error(convert_mcc_game_variant_to_tu1(&custom_variant_with_actions(vec![lshift]))), Some(e_reach_gametype_conversion_error::mcc_exclusive_math_operator));
+    }
+
+    #[test]
+    fn relocates_temporaries_onto_free_globals() {
+        let mut variant = custom_variant_with_actions(vec![
+            set_player_action(e_explicit_player_type::global_0, e_math_operation::set_to),
+            set_player_action(e_explicit_player_type::temporary_1, e_math_operation::set_to),
+        ]);
+        variant.m_custom_variant.as_mut().unwrap().m_game_engine.m_global_variable_metadata.m_player_variables.push(Default::default());
+
+        let converted = convert_mcc_game_variant_to_tu1(&variant).unwrap();
+        let engine = &converted.m_custom_variant.as_ref().unwrap().m_game_engine;
+        let player_type = engine.m_actions[1].m_set_parameters.as_ref().unwrap()
+            .m_variable_1.m_player.as_ref().unwrap()
+            .m_player.as_ref().unwrap()
+            .m_explicit_player_type;
+        assert_eq!(player_type, e_explicit_player_type_tu1::global_1);
+        assert_eq!(engine.m_global_variable_metadata.m_player_variables.len(), 2);
+
+        let mut variant = custom_variant_with_actions(vec![
+            set_player_action(e_explicit_player_type::temporary_0, e_math_operation::set_to),
+            set_player_action(e_explicit_player_type::temporary_1, e_math_operation::set_to),
+            set_player_action(e_explicit_player_type::temporary_2, e_math_operation::set_to),
+        ]);
+        variant.m_custom_variant.as_mut().unwrap().m_game_engine.m_global_variable_metadata.m_player_variables.resize_with(6, Default::default);
+        assert_eq!(conversion_error(convert_mcc_game_variant_to_tu1(&variant)), Some(e_reach_gametype_conversion_error::insufficient_global_slots));
+    }
+
+    #[test]
+    fn survival_variants_round_trip() {
+        let variant = c_game_variant {
+            m_game_engine: e_game_engine_type::survival,
+            m_survival_variant: Some(c_game_engine_survival_variant { m_encoding_version: 2, m_set_count: 3, ..Default::default() }),
+            ..Default::default()
+        };
+
+        let tu1_variant = convert_mcc_game_variant_to_tu1(&variant).unwrap();
+        let tu1_survival_variant = tu1_variant.m_survival_variant.as_ref().unwrap();
+        assert_eq!(tu1_survival_variant.m_campaign_difficulty_level, k_tu1_survival_campaign_difficulty_level);
+        assert_eq!(tu1_survival_variant.m_set_count, 3);
+
+        assert_eq!(convert_tu1_game_variant_to_mcc(&tu1_variant).unwrap(), variant);
+    }
+}
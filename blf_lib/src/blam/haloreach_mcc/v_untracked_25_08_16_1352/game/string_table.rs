@@ -171,6 +171,14 @@ c_string_table<
     count_bit_length,
 >
 {
+    pub fn get_string(&self, language_index: usize, string_index: usize) -> Option<&str> {
+        if language_index >= k_language_count {
+            return None;
+        }
+
+        self.strings[language_index].get(string_index)?.as_deref()
+    }
+
     pub fn decode(&mut self, bitstream: &mut c_bitstream_reader) -> BLFLibResult {
         let string_count: usize = bitstream.read_integer("string-count", count_bit_length)?;
 
@@ -5,16 +5,22 @@ use std::path::Path;
 use lazy_static::lazy_static;
 use sha1::{Digest, Sha1};
 use sha1::digest::Update;
+use blf_lib::blam::common::memory::rsa_signature::RsaPrivateKey;
 use blf_lib::blam::common::memory::secure_signature::s_network_http_request_hash;
-use blf_lib_derivable::blf::chunks::{BlfChunk, SerializableBlfChunk};
+use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file_with_rsa;
+use blf_lib_derivable::blf::chunks::{BlfChunk, DynTitleAndBuild, SerializableBlfChunk};
 
 pub mod chunks;
 pub mod versions;
+pub mod registry;
+pub mod detection;
+pub mod reader;
+pub mod content;
 
 pub use blf_lib_derivable::blf::s_blf_header::s_blf_header;
 
 pub use blf_lib_derivable::blf::BlfFile;
-use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
 
 pub struct BlfFileBuilder {
     chunks: Vec<Box<dyn SerializableBlfChunk>>,
@@ -42,11 +48,18 @@ impl BlfFileBuilder {
         &self.chunks
     }
 
-    pub fn get_chunk<T: BlfChunk + SerializableBlfChunk + 'static>(&self) -> Result<&T, Box<dyn Error>> {
+    pub fn get_chunk<T: BlfChunk + SerializableBlfChunk + 'static>(&self) -> BLFLibResult<&T> {
         self.chunks
             .iter()
             .find_map(|chunk| chunk.as_ref().as_any().downcast_ref::<T>())
-            .ok_or_else(|| format!("Chunk {} {} not found", T::get_signature(), T::get_version()).into())
+            .ok_or(BLFLibError::ChunkNotFound { signature: T::get_signature(), version: Some(T::get_version()) })
+    }
+
+    pub fn get_chunk_mut<T: BlfChunk + SerializableBlfChunk + 'static>(&mut self) -> BLFLibResult<&mut T> {
+        self.chunks
+            .iter_mut()
+            .find_map(|chunk| chunk.as_mut().as_any_mut().downcast_mut::<T>())
+            .ok_or(BLFLibError::ChunkNotFound { signature: T::get_signature(), version: Some(T::get_version()) })
     }
 
     pub fn write(&mut self) -> BLFLibResult<Vec<u8>> {
@@ -59,6 +72,17 @@ impl BlfFileBuilder {
         Ok(data)
     }
 
+    /// Writes the file followed by an _eof signed with the given key, the builder shouldn't contain an _eof.
+    pub fn write_signed(&mut self, private_key: &RsaPrivateKey) -> BLFLibResult<Vec<u8>> {
+        let mut data = self.write()?;
+
+        let mut end_of_file = s_blf_chunk_end_of_file_with_rsa::default();
+        end_of_file.sign(&data, private_key)?;
+        data.append(&mut end_of_file.write(&data)?);
+
+        Ok(data)
+    }
+
     pub fn write_file(&mut self, path: impl Into<String>) -> BLFLibResult {
         let path = &path.into();
         let data = self.write()?;
@@ -82,17 +106,19 @@ impl BlfFileBuilder {
             header = s_blf_header::decode(&header_bytes)?;
 
             if header.signature != chunk.signature() || header.version != chunk.version() {
-                return Err(format!("Failed to read chunk {} {}, found {} {} instead!",
-                                             chunk.signature(),
-                                             chunk.version(),
-                                             header.signature,
-                                             header.version
-                ).into())
+                return Err(BLFLibError::ChunkMismatch {
+                    offset: previously_read.len() as u64,
+                    expected_signature: chunk.signature(),
+                    expected_version: chunk.version(),
+                    found_signature: header.signature,
+                    found_version: header.version,
+                })
             }
 
             let mut body_bytes = vec![0u8; (header.chunk_size as usize) - s_blf_header::size()];
             reader.read_exact(&mut body_bytes)?;
-            chunk.decode_body(&body_bytes, &previously_read)?;
+            chunk.decode_body(&body_bytes, &previously_read)
+                .map_err(|err| err.in_chunk(previously_read.len() as u64, header.signature, header.version))?;
 
             previously_read.extend_from_slice(&header_bytes);
             previously_read.extend_from_slice(&body_bytes);
@@ -108,6 +134,13 @@ impl BlfFileBuilder {
         self.read(&data)
     }
 
+    // Reads a file without knowing its chunks up front, see registry::parse_any.
+    pub fn parse_any(buffer: &[u8], build: &dyn DynTitleAndBuild) -> BLFLibResult<BlfFileBuilder> {
+        Ok(BlfFileBuilder {
+            chunks: registry::parse_any(buffer, build)?,
+        })
+    }
+
     pub fn chunk_count(&self) -> usize {
         self.chunks.len()
     }
@@ -147,4 +180,61 @@ macro_rules! blf_file {
         #[derive(blf_lib::derive::BlfFile, Default, PartialEq, Debug, Clone, serde::Serialize)]
         $i
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use super::*;
+    use crate::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_author, s_blf_chunk_content_header, s_blf_chunk_end_of_file, s_blf_chunk_start_of_file};
+
+    crate::blf_file! {
+        pub struct test_blf_file {
+            authors: Vec<s_blf_chunk_author>,
+            start_of_file: s_blf_chunk_start_of_file,
+            content_header: Option<s_blf_chunk_content_header>,
+            end_of_file: s_blf_chunk_end_of_file,
+        }
+    }
+
+    #[test]
+    fn blf_file_reads_optional_and_repeated_chunks() {
+        let mut data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("test"))
+            .add_chunk(s_blf_chunk_author::default())
+            .add_chunk(s_blf_chunk_author::default())
+            .add_chunk(s_blf_chunk_end_of_file::default())
+            .write()
+            .unwrap();
+        data.extend_from_slice(&[0xAB; 7]);
+
+        let blf_file = test_blf_file::read(&mut Cursor::new(data)).unwrap();
+
+        assert_eq!(blf_file.authors.len(), 2);
+        assert_eq!(blf_file.start_of_file, s_blf_chunk_start_of_file::new("test"));
+        assert!(blf_file.content_header.is_none());
+    }
+
+    #[test]
+    fn blf_file_requires_non_optional_chunks() {
+        let data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_author::default())
+            .add_chunk(s_blf_chunk_end_of_file::default())
+            .write()
+            .unwrap();
+
+        assert!(test_blf_file::read(&mut Cursor::new(data)).is_err());
+    }
+
+    #[test]
+    fn blf_file_stops_at_noise_claiming_an_oversized_chunk() {
+        let mut data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("test"))
+            .write()
+            .unwrap();
+        data.extend_from_slice(&[0xFF; 16]);
+
+        let error = test_blf_file::read(&mut Cursor::new(data)).unwrap_err();
+
+        assert!(error.to_string().contains("not found"));
+    }
+}
@@ -7,14 +7,19 @@ pub(crate) mod ares;
 pub(crate) mod haloonline;
 pub(crate) mod haloreach_mcc;
 pub(crate) mod mcc;
+pub mod s_blf_chunk_raw;
+
+pub use s_blf_chunk_raw::*;
 
 use std::error::Error;
-use std::fs;
 use std::fs::File;
 use std::io::{Cursor, Read, Seek};
 use binrw::BinReaderExt;
 use serde::Deserialize;
-use blf_lib::BINRW_RESULT;
+use blf_lib::{assert_ok, BINRW_RESULT};
+use blf_lib::blam::common::memory::rsa_signature::RsaPublicKey;
+use blf_lib::blf::reader::BlfFileReader;
+use blf_lib::blf::registry::read_headers;
 use blf_lib::blf::s_blf_header;
 pub use blf_lib_derivable::blf::chunks::*;
 use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
@@ -39,16 +44,8 @@ pub fn find_and_validate_eof(buffer: &[u8]) -> BLFLibResult {
         let mut body_bytes = vec![0u8; (header.chunk_size as usize) - s_blf_header::size()];
         if header.signature == chunk_signature::from_string("_eof") && header.version.major == 1 {
             cursor.read_exact(body_bytes.as_mut_slice())?;
-            let authentication_type: u8 = body_bytes[4];
             let mut chunk_position = (cursor.position() as usize) - (body_bytes.len() + s_blf_header::size());
-
-            match authentication_type {
-                0 => { s_blf_chunk_end_of_file::read(body_bytes.clone(), Some(header.clone()), &buffer[0..chunk_position])?; }
-                1 => { s_blf_chunk_end_of_file_with_crc::read(body_bytes.clone(), Some(header.clone()), &buffer[0..chunk_position])?; }
-                2 => { s_blf_chunk_end_of_file_with_sha1::read(body_bytes.clone(), Some(header.clone()), &buffer[0..chunk_position])?; }
-                3 => { s_blf_chunk_end_of_file_with_rsa::read(body_bytes.clone(), Some(header.clone()), &buffer[0..chunk_position])?; }
-                _ => { return Err(format!("Unknown _eof authentication type {}", authentication_type).into()); }
-            }
+            validate_eof(&header, body_bytes, &buffer[0..chunk_position])?;
 
             // There should only be one _eof, so we can return now.
             break;
@@ -59,6 +56,34 @@ pub fn find_and_validate_eof(buffer: &[u8]) -> BLFLibResult {
     Ok(())
 }
 
+/// Reads an _eof body as the variant its authentication type names, which checks it against the data before it.
+fn validate_eof(header: &s_blf_header, body_bytes: Vec<u8>, previously_read: &[u8]) -> BLFLibResult {
+    let authentication_type: u8 = *body_bytes.get(4).ok_or("The _eof chunk is too small to have an authentication type")?;
+
+    match authentication_type {
+        0 => { s_blf_chunk_end_of_file::read(body_bytes, Some(header.clone()), previously_read)?; }
+        1 => { s_blf_chunk_end_of_file_with_crc::read(body_bytes, Some(header.clone()), previously_read)?; }
+        2 => { s_blf_chunk_end_of_file_with_sha1::read(body_bytes, Some(header.clone()), previously_read)?; }
+        3 => { s_blf_chunk_end_of_file_with_rsa::read(body_bytes, Some(header.clone()), previously_read)?; }
+        _ => { return Err(format!("Unknown _eof authentication type {}", authentication_type).into()); }
+    }
+
+    Ok(())
+}
+
+pub fn verify_rsa_signature(buffer: &[u8], public_key: &RsaPublicKey) -> BLFLibResult {
+    let (offset, header) = read_headers(buffer)
+        .into_iter()
+        .find(|(_, header)| header.signature == chunk_signature::from_string("_eof"))
+        .ok_or(BLFLibError::ChunkNotFound { signature: chunk_signature::from_string("_eof"), version: None })?;
+
+    let body_bytes = buffer[offset + s_blf_header::size()..offset + header.chunk_size as usize].to_vec();
+    assert_ok!(body_bytes.len() > 4 && body_bytes[4] == 3, BLFLibError::end_of_file_validation("_eof is not signed with RSA"));
+
+    s_blf_chunk_end_of_file_with_rsa::read(body_bytes, Some(header), &buffer[0..offset])?
+        .verify(&buffer[0..offset], public_key)
+}
+
 pub fn find_chunk<'a, T: BlfChunk + SerializableBlfChunk + ReadableBlfChunk>(buffer: &[u8]) -> Result<T, Box<dyn Error>> {
     let mut cursor = Cursor::new(buffer);
     let mut headerBytes = [0u8; s_blf_header::size()];
@@ -78,30 +103,30 @@ pub fn find_chunk<'a, T: BlfChunk + SerializableBlfChunk + ReadableBlfChunk>(buf
         }
         cursor.seek_relative((header.chunk_size - s_blf_header::size() as u32) as i64)?;
     }
-    Err(format!("{} Chunk not found!", T::get_signature()).into())
+    Err(BLFLibError::ChunkNotFound { signature: T::get_signature(), version: Some(T::get_version()) }.into())
 }
 
 pub fn find_chunk_in_file<T: BlfChunk + SerializableBlfChunk + ReadableBlfChunk>(path: impl Into<String>) -> BLFLibResult<T> {
-    let path = path.into();
-    let mut file = File::open(&path)?;
-    let mut headerBytes = [0u8; s_blf_header::size()];
-    let mut header: s_blf_header;
+    let mut reader = BlfFileReader::open(path)?;
+    let mut chunk: Option<T> = None;
 
-    find_and_validate_eof(&fs::read(&path)?)?;
+    while let Some(next) = reader.next() {
+        let (header, offset) = next?;
 
-    while file.read_exact(&mut headerBytes).is_ok() {
-        header = s_blf_header::decode(&headerBytes)?;
-        if header.signature == T::get_signature() && header.version == T::get_version() {
-            let mut body_bytes = vec![0u8; (header.chunk_size as usize) - s_blf_header::size()];
-            file.read_exact(body_bytes.as_mut_slice())?;
-            return Ok(T::read(body_bytes, Some(header), &Vec::new())?);
+        if chunk.is_none() && header.signature == T::get_signature() && header.version == T::get_version() {
+            chunk = Some(reader.read_chunk(&header, offset)?);
         }
-        if header.chunk_size == 0 {
-            break;
+
+        // Like find_and_validate_eof, the _eof is checked against everything before it,
+        // so that much of the file is only read once the _eof is reached.
+        if header.signature == chunk_signature::from_string("_eof") && header.version.major == 1 {
+            let body_bytes = reader.read_body(&header, offset)?;
+            let previously_read = reader.read_range(0, offset as usize)?;
+            validate_eof(&header, body_bytes, &previously_read)?;
         }
-        file.seek_relative((header.chunk_size - s_blf_header::size() as u32) as i64)?;
     }
-    Err(format!("{} chunk not found in file {}", T::get_signature(), &path).into())
+
+    chunk.ok_or(BLFLibError::ChunkNotFound { signature: T::get_signature(), version: Some(T::get_version()) })
 }
 
 pub fn search_for_chunk<'a, T: BlfChunk + SerializableBlfChunk + ReadableBlfChunk>(buffer: Vec<u8>) -> BLFLibResult<Option<T>> {
@@ -119,20 +144,7 @@ pub fn search_for_chunk<'a, T: BlfChunk + SerializableBlfChunk + ReadableBlfChun
 }
 
 pub fn search_for_chunk_in_file<T: BlfChunk + SerializableBlfChunk + ReadableBlfChunk>(path: impl Into<String>) -> BLFLibResult<Option<T>> {
-    let mut fileBytes = Vec::<u8>::new();
-    File::open(path.into())?.read_to_end(&mut fileBytes)?;
-
-    for i in 0..(fileBytes.len() - 0xC) {
-        let header_bytes = &fileBytes.as_slice()[i..i+0xC];
-        let header = s_blf_header::decode(header_bytes)?;
-
-        if header.signature == T::get_signature() && header.version == T::get_version() {
-            let body_bytes = fileBytes.as_slice()[i+0xC..i+header.chunk_size as usize].to_vec();
-            return Ok(Some(T::read(body_bytes, Some(header), &Vec::new())?));
-        }
-    }
-
-    Ok(None)
+    BlfFileReader::open(path)?.search_for_chunk()
 }
 
 pub fn read_chunk_json<T: BlfChunk + for<'d> Deserialize<'d>>(path: &str) -> BLFLibResult<T> {
@@ -4,7 +4,7 @@ use binrw::{binrw, BinRead, BinResult, BinWrite, Endian};
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use blf_lib_derivable::blf::chunks::BlfChunkHooks;
-use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
 use blf_lib_derive::BlfChunk;
 use crate::assert_ok;
 
@@ -30,7 +30,7 @@ impl BlfChunkHooks for s_blf_chunk_end_of_file {
     }
 
     fn after_read(&mut self, previously_read: &[u8]) -> BLFLibResult {
-        assert_ok!(self.file_size == previously_read.len() as u32, "_eof has an invalid size");
+        assert_ok!(self.file_size == previously_read.len() as u32, BLFLibError::end_of_file_validation("_eof has an invalid size"));
 
         Ok(())
     }
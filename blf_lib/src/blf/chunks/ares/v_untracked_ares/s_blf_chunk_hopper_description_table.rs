@@ -77,7 +77,7 @@ impl BinWrite for s_blf_chunk_hopper_description_table {
     type Args<'a> = ();
 
     fn write_options<W: Write + Seek>(&self, writer: &mut W, endian: Endian, args: Self::Args<'_>) -> BinResult<()> {
-        let mut bitstream = c_bitstream_writer::new(0x4000, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        let mut bitstream = c_bitstream_writer::new_growable(Some(0x4000), e_bitstream_byte_order::_bitstream_byte_order_big_endian);
         bitstream.begin_writing();
 
         bitstream.write_integer(self.description_count as u32, 6)?;
@@ -5,7 +5,7 @@ use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use blf_lib::blam::common::memory::crc::crc_checksum_buffer;
 use blf_lib_derivable::blf::chunks::BlfChunkHooks;
-use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
 use blf_lib_derive::BlfChunk;
 use crate::assert_ok;
 
@@ -33,8 +33,8 @@ impl BlfChunkHooks for s_blf_chunk_end_of_file_with_crc {
     fn after_read(&mut self, previously_read: &[u8]) -> BLFLibResult {
         let expected_crc = self.crc;
         let actual_crc = crc_checksum_buffer(0xFFFFFFFF, previously_read);
-        assert_ok!(expected_crc == actual_crc, "s_blf_chunk_end_of_file_with_crc has an invalid crc");
-        assert_ok!(self.file_size == previously_read.len() as u32, "_eof has an invalid size");
+        assert_ok!(expected_crc == actual_crc, BLFLibError::end_of_file_validation("s_blf_chunk_end_of_file_with_crc has an invalid crc"));
+        assert_ok!(self.file_size == previously_read.len() as u32, BLFLibError::end_of_file_validation("_eof has an invalid size"));
 
         Ok(())
     }
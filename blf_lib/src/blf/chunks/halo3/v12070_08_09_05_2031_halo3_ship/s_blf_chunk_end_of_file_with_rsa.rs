@@ -3,9 +3,9 @@ use binrw::{binrw, BinRead, BinResult, BinWrite, Endian};
 #[cfg(feature = "napi")]
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
-use blf_lib::blam::common::memory::secure_signature::s_network_http_request_hash;
+use blf_lib::blam::common::memory::rsa_signature::{rsa_sign_buffer, rsa_verify_buffer, RsaPrivateKey, RsaPublicKey};
 use blf_lib_derivable::blf::chunks::BlfChunkHooks;
-use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
 use blf_lib_derive::BlfChunk;
 use crate::assert_ok;
 use crate::types::array::StaticArray;
@@ -25,15 +25,16 @@ pub struct s_blf_chunk_end_of_file_with_rsa
 
 impl BlfChunkHooks for s_blf_chunk_end_of_file_with_rsa {
     fn before_write(&mut self, previously_written: &Vec<u8>) -> BLFLibResult {
-        self.file_size = previously_written.len() as u32;
+        // Signing needs a private key, so it can't happen here, see BlfFileBuilder::write_signed.
+        assert_ok!(self.file_size == previously_written.len() as u32 && self.rsa.get().iter().any(|byte| *byte != 0),
+            "s_blf_chunk_end_of_file_with_rsa must be signed before it is written, see BlfFileBuilder::write_signed.");
 
-        // We don't have any private keys to do this with!
-        Err("s_blf_chunk_end_of_file_with_rsa does not support write yet.".into())
+        Ok(())
     }
 
     fn after_read(&mut self, previously_read: &[u8]) -> BLFLibResult {
-        // TODO: Validate
-        assert_ok!(self.file_size == previously_read.len() as u32, "_eof has an invalid size");
+        // Validating needs the signer's public key, see verify.
+        assert_ok!(self.file_size == previously_read.len() as u32, BLFLibError::end_of_file_validation("_eof has an invalid size"));
         Ok(())
     }
 }
@@ -45,4 +46,16 @@ impl s_blf_chunk_end_of_file_with_rsa {
             rsa: StaticArray::default(),
         }
     }
+
+    pub fn sign(&mut self, previously_written: &[u8], private_key: &RsaPrivateKey) -> BLFLibResult {
+        self.file_size = previously_written.len() as u32;
+        self.rsa = StaticArray::from_slice(&rsa_sign_buffer(previously_written, private_key)?)?;
+
+        Ok(())
+    }
+
+    pub fn verify(&self, previously_read: &[u8], public_key: &RsaPublicKey) -> BLFLibResult {
+        assert_ok!(self.file_size == previously_read.len() as u32, BLFLibError::end_of_file_validation("_eof has an invalid size"));
+        rsa_verify_buffer(previously_read, self.rsa.get(), public_key)
+    }
 }
\ No newline at end of file
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use blf_lib::blam::common::memory::secure_signature::s_network_http_request_hash;
 use blf_lib::blf::get_buffer_hash;
 use blf_lib_derivable::blf::chunks::BlfChunkHooks;
-use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
 use blf_lib_derive::BlfChunk;
 use crate::assert_ok;
 
@@ -34,8 +34,8 @@ impl BlfChunkHooks for s_blf_chunk_end_of_file_with_sha1 {
     fn after_read(&mut self, previously_read: &[u8]) -> BLFLibResult {
         let expected_hash = &self.sha1;
         let actual_hash = &get_buffer_hash(&previously_read)?;
-        assert_ok!(actual_hash == expected_hash, "s_blf_chunk_end_of_file_with_sha1 has an invalid sha1");
-        assert_ok!(self.file_size == previously_read.len() as u32, "_eof has an invalid size");
+        assert_ok!(actual_hash == expected_hash, BLFLibError::end_of_file_validation("s_blf_chunk_end_of_file_with_sha1 has an invalid sha1"));
+        assert_ok!(self.file_size == previously_read.len() as u32, BLFLibError::end_of_file_validation("_eof has an invalid size"));
 
         Ok(())
     }
@@ -85,13 +85,14 @@ impl BinWrite for s_blf_chunk_game_set {
     type Args<'a> = ();
 
     fn write_options<W: Write + Seek>(&self, writer: &mut W, endian: Endian, args: Self::Args<'_>) -> BinResult<()> {
-        let mut bitstream = c_bitstream_writer::new(0x1BC0, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        let mut bitstream = c_bitstream_writer::new_growable(Some(0x1BC0), e_bitstream_byte_order::_bitstream_byte_order_big_endian);
         bitstream.begin_writing();
 
         BINRW_RESULT!(bitstream.write_integer(self.game_entry_count as u32, 6))?;
 
         for i in 0..self.game_entry_count {
             let game_entry = &self.game_entries[i];
+            BINRW_RESULT!(bitstream.write_identifier(&format!("game-entries[{i}]")))?;
             BINRW_RESULT!(bitstream.write_integer(game_entry.weight, 32))?;
             BINRW_RESULT!(bitstream.write_integer(game_entry.minimum_player_count as u32, 4))?;
             BINRW_RESULT!(bitstream.write_bool(game_entry.skip_after_veto))?;
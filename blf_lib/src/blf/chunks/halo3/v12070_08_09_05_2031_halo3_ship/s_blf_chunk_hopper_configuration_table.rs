@@ -309,7 +309,7 @@ impl BinWrite for s_blf_chunk_hopper_configuration_table {
     type Args<'a> = ();
 
     fn write_options<W: Write + Seek>(&self, writer: &mut W, endian: Endian, args: Self::Args<'_>) -> BinResult<()> {
-        let mut bitstream = c_bitstream_writer::new(0x4C98, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        let mut bitstream = c_bitstream_writer::new_growable(Some(0x4C98), e_bitstream_byte_order::_bitstream_byte_order_big_endian);
         bitstream.begin_writing();
 
         // Encode hopper_category_count
@@ -318,6 +318,7 @@ impl BinWrite for s_blf_chunk_hopper_configuration_table {
         // Encode each hopper category
         for i in 0..self.hopper_category_count as usize {
             let category = &self.hopper_categories[i];
+            bitstream.write_identifier(&format!("hopper-categories[{i}]"))?;
             bitstream.write_integer(category.category_identifier as u32, 16)?;
             bitstream.write_integer(category.category_image_index as u32, 6)?;
             bitstream.write_string_utf8(&category.category_name.get_string()?, 16)?;
@@ -329,6 +330,7 @@ impl BinWrite for s_blf_chunk_hopper_configuration_table {
         // Encode each hopper configuration
         for i in 0..self.hopper_configuration_count as usize {
             let configuration = &self.hopper_configurations[i];
+            bitstream.write_identifier(&format!("hopper-configurations[{i}]"))?;
             bitstream.write_string_utf8(&configuration.hopper_name.get_string()?, 16)?;
             bitstream.write_raw_data(&configuration.game_set_hash.data.get(), 0xA0)?;
             bitstream.write_integer(configuration.hopper_identifier as u32, 16)?;
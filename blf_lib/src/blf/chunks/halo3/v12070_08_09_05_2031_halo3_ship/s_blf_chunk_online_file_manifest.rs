@@ -3,6 +3,7 @@ use binrw::{binrw, BinRead, BinWrite};
 use serde::{Deserialize, Serialize};
 use blf_lib::blam::common::memory::secure_signature::s_network_http_request_hash;
 use blf_lib_derivable::blf::chunks::BlfChunkHooks;
+use blf_lib_derivable::result::BLFLibResult;
 use blf_lib_derive::BlfChunk;
 use crate::types::string::StaticString;
 
@@ -41,4 +42,15 @@ impl s_blf_chunk_online_file_manifest {
 
         Ok(())
     }
+
+    /// Replaces the hash of each file with the one from get_file_hash, files it returns None for keep their hash.
+    pub fn update_file_hashes(&mut self, mut get_file_hash: impl FnMut(&str) -> BLFLibResult<Option<s_network_http_request_hash>>) -> BLFLibResult {
+        for file in self.data.iter_mut() {
+            if let Some(hash) = get_file_hash(&file.cache_key.get_string()?)? {
+                file.hash = hash;
+            }
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
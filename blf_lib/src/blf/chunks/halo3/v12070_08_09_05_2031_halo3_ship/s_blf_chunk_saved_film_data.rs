@@ -0,0 +1,56 @@
+use std::io::{Read, Seek, Write};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use serde::{Deserialize, Serialize};
+use blf_lib_derivable::blf::chunks::{BlfChunk, BlfChunkHooks};
+use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derive::BlfChunk;
+use crate::blf::reader::BlfFileReader;
+
+/// Film data follows the film header as a run of `flmd` chunks, each holding a block of the update stream.
+/// The update stream isn't decoded yet, it's kept as-is so films can be rewritten.
+#[derive(BlfChunk,PartialEq,Debug,Clone,Serialize,Deserialize,Default)]
+#[Header("flmd", 1.1)]
+pub struct s_blf_chunk_saved_film_data {
+    pub data: Vec<u8>,
+}
+
+impl BlfChunkHooks for s_blf_chunk_saved_film_data {}
+
+impl BinRead for s_blf_chunk_saved_film_data {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(reader: &mut R, _endian: Endian, _args: Self::Args<'_>) -> BinResult<Self> {
+        let mut film_data = Self::default();
+        reader.read_to_end(&mut film_data.data)?;
+
+        Ok(film_data)
+    }
+}
+
+impl BinWrite for s_blf_chunk_saved_film_data {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _endian: Endian, _args: Self::Args<'_>) -> BinResult<()> {
+        writer.write_all(&self.data)?;
+
+        Ok(())
+    }
+}
+
+impl s_blf_chunk_saved_film_data {
+    /// Reads every film data chunk in a film, in order, and returns their update stream joined together.
+    pub fn read_update_stream<R: Read + Seek>(reader: R) -> BLFLibResult<Vec<u8>> {
+        let mut blf_reader = BlfFileReader::new(reader)?;
+        let mut update_stream = Vec::<u8>::new();
+
+        while let Some(next) = blf_reader.next() {
+            let (header, offset) = next?;
+
+            if header.signature == Self::get_signature() {
+                update_stream.extend(blf_reader.read_body(&header, offset)?);
+            }
+        }
+
+        Ok(update_stream)
+    }
+}
@@ -59,8 +59,8 @@ impl BinWrite for s_blf_chunk_game_variant {
         _endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()> {
-        let mut bitstream_writer = c_bitstream_writer::new(
-            variant_storage_capacity,
+        let mut bitstream_writer = c_bitstream_writer::new_growable(
+            Some(variant_storage_capacity),
             e_bitstream_byte_order::_bitstream_byte_order_big_endian,
         );
         bitstream_writer.begin_writing();
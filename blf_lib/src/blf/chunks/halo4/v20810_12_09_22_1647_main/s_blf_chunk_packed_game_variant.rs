@@ -51,8 +51,8 @@ impl BinWrite for s_blf_chunk_packed_game_variant {
         _endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()> {
-        let mut bitstream_writer = c_bitstream_writer::new(
-            31744,
+        let mut bitstream_writer = c_bitstream_writer::new_growable(
+            Some(31744),
             e_bitstream_byte_order::_bitstream_byte_order_big_endian,
         );
         bitstream_writer.begin_writing();
@@ -55,8 +55,8 @@ impl BinWrite for s_blf_chunk_game_variant {
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()> {
-        let mut bitstream_writer = c_bitstream_writer::new(
-            variant_storage_length,
+        let mut bitstream_writer = c_bitstream_writer::new_growable(
+            Some(variant_storage_length),
             e_bitstream_byte_order::_bitstream_byte_order_big_endian,
         );
         bitstream_writer.begin_writing();
@@ -95,7 +95,7 @@ impl BinWrite for s_blf_chunk_game_set {
 
         // 3. Pack
         let compressed_length: u16 = compressed_data.len() as u16;
-        let mut packed_writer = c_bitstream_writer::new(0xD404, e_bitstream_byte_order::from_binrw_endian(endian));
+        let mut packed_writer = c_bitstream_writer::new_growable(Some(0xD404), e_bitstream_byte_order::from_binrw_endian(endian));
         packed_writer.begin_writing();
 
         packed_writer.write_integer(compressed_length as u32, 14)?;
@@ -47,7 +47,7 @@ impl BinWrite for s_blf_chunk_game_variant {
     type Args<'a> = ();
 
     fn write_options<W: Write + Seek>(&self, writer: &mut W, endian: Endian, args: Self::Args<'_>) -> BinResult<()> {
-        let mut bitstream_writer = c_bitstream_writer::new(variant_storage_length, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        let mut bitstream_writer = c_bitstream_writer::new_growable(Some(variant_storage_length), e_bitstream_byte_order::_bitstream_byte_order_big_endian);
         bitstream_writer.begin_writing();
         self.game_variant.encode(&mut bitstream_writer)?;
         bitstream_writer.finish_writing();
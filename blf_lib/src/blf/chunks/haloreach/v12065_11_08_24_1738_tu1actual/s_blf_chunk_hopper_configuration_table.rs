@@ -102,8 +102,7 @@ impl BinWrite for s_blf_chunk_hopper_configuration_table {
         // 3. Pack
         let compressed_length: u16 = compressed_data.len() as u16;
         let uncompressed_length: u32 = encoded_chunk.len() as u32;
-        // TODO: allow the writer to grow if it runs out of space.
-        let mut packed_writer = c_bitstream_writer::new(0x8F48, e_bitstream_byte_order::from_binrw_endian(endian));
+        let mut packed_writer = c_bitstream_writer::new_growable(None, e_bitstream_byte_order::from_binrw_endian(endian));
         packed_writer.begin_writing();
 
         packed_writer.write_integer((compressed_length + 4) as u32, 14)?;
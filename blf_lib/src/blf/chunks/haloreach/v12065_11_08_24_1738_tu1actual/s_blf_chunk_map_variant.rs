@@ -56,8 +56,8 @@ impl BinWrite for s_blf_chunk_map_variant {
     type Args<'a> = ();
 
     fn write_options<W: Write + Seek>(&self, writer: &mut W, endian: Endian, args: Self::Args<'_>) -> BinResult<()> {
-        let mut bitstream = c_bitstream_writer::new(
-            MAP_VARIANT_STORAGE_CAPACITY,
+        let mut bitstream = c_bitstream_writer::new_growable(
+            Some(MAP_VARIANT_STORAGE_CAPACITY),
             e_bitstream_byte_order::_bitstream_byte_order_big_endian,
         );
         bitstream.begin_writing();
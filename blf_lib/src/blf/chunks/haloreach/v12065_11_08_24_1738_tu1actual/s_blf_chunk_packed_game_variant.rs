@@ -38,7 +38,7 @@ impl BinWrite for s_blf_chunk_matchmaking_game_variant {
     type Args<'a> = ();
 
     fn write_options<W: Write + Seek>(&self, writer: &mut W, endian: Endian, args: Self::Args<'_>) -> BinResult<()> {
-        let mut bitstream_writer = c_bitstream_writer::new(0x5028, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        let mut bitstream_writer = c_bitstream_writer::new_growable(Some(0x5028), e_bitstream_byte_order::_bitstream_byte_order_big_endian);
         bitstream_writer.begin_writing();
         self.game_variant.encode(&mut bitstream_writer)?;
         bitstream_writer.finish_writing();
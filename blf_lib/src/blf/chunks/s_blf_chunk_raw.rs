@@ -0,0 +1,40 @@
+use binrw::binrw;
+use blf_lib_derivable::blf::chunks::{BlfChunkHooks, DynamicBlfChunk};
+use blf_lib_derivable::types::chunk_signature::chunk_signature;
+use blf_lib_derivable::types::chunk_version::chunk_version;
+
+/// Holds the body of a chunk as-is, for chunks blf_lib doesn't implement.
+/// Written back byte-for-byte, so files containing them can still be round-tripped.
+#[binrw]
+#[derive(Default, PartialEq, Debug, Clone)]
+#[brw(big)]
+pub struct s_blf_chunk_raw {
+    #[brw(ignore)]
+    pub signature: chunk_signature,
+    #[brw(ignore)]
+    pub version: chunk_version,
+    #[br(parse_with = binrw::helpers::until_eof)]
+    pub data: Vec<u8>,
+}
+
+impl BlfChunkHooks for s_blf_chunk_raw {}
+
+impl DynamicBlfChunk for s_blf_chunk_raw {
+    fn signature(&self) -> chunk_signature {
+        self.signature
+    }
+
+    fn version(&self) -> chunk_version {
+        self.version
+    }
+}
+
+impl s_blf_chunk_raw {
+    pub fn new(signature: chunk_signature, version: chunk_version, data: Vec<u8>) -> s_blf_chunk_raw {
+        s_blf_chunk_raw {
+            signature,
+            version,
+            data,
+        }
+    }
+}
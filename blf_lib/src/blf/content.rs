@@ -0,0 +1,502 @@
+use std::io::Cursor;
+use num_traits::ToPrimitive;
+use serde::Serialize;
+use serde_json::Value;
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+use blf_lib::blam::common::math::real_math::real_point3d;
+use blf_lib::blam::common::memory::rsa_signature::RsaPrivateKey;
+use blf_lib::blf::BlfFileBuilder;
+use blf_lib::blf::chunks::{search_for_chunk, BlfChunk, ReadableBlfChunk, SerializableBlfChunk};
+use blf_lib::blf::versions::halo3::{k_title_halo3, v12070_08_09_05_2031_halo3_ship};
+use blf_lib::blf::versions::halo3odst::{k_title_halo3odst, v13895_09_04_27_2201_atlas_release};
+use blf_lib::blf::versions::haloreach::{k_title_haloreach, v12065_11_08_24_1738_tu1actual};
+use blf_lib::blf::reader::BlfFileReader;
+use blf_lib::blf::s_blf_header;
+use blf_lib_derivable::types::chunk_signature::chunk_signature;
+use blf_lib::types::time::time64_t;
+use blf_lib::types::u64::Unsigned64;
+use blf_lib::{assert_ok, OPTION_TO_RESULT};
+use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
+
+// Reads content saved by Halo 3, ODST and Reach: file share items, screenshots and films.
+// Content is often wrapped in a console package, so chunks are searched for rather than read in order.
+
+/// The content header of a piece of content, in the same shape for each title.
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Default, PartialEq, Debug, Clone, Serialize)]
+pub struct s_content_summary {
+    pub title: String,
+    pub build_number: u16,
+    pub file_type: i32,
+    pub name: String,
+    pub description: String,
+    /// For Reach, this is whoever last modified the content.
+    pub author: String,
+    pub author_xuid: Unsigned64,
+    pub date: time64_t,
+    pub unique_id: Unsigned64,
+    pub game_id: Unsigned64,
+    pub map_id: i32,
+    pub hopper_id: Option<u16>,
+}
+
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Default, PartialEq, Debug, Clone, Serialize)]
+pub struct s_screenshot {
+    pub summary: s_content_summary,
+    #[serde(skip)]
+    pub jpeg_data: Vec<u8>,
+    /// Reach's screenshot camera hasn't been mapped, so these are only read for Halo 3 and ODST.
+    pub camera_position: Option<real_point3d>,
+    pub game_tick: Option<u32>,
+    pub film_tick: Option<u32>,
+}
+
+/// The variants a film was recorded with, each written as a standalone BLF file.
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Default, PartialEq, Debug, Clone, Serialize)]
+pub struct s_film_variants {
+    pub summary: s_content_summary,
+    pub map_variant_name: String,
+    pub map_variant_author: String,
+    #[serde(skip)]
+    pub map_variant: Vec<u8>,
+    pub game_variant_name: String,
+    pub game_variant_author: String,
+    pub game_engine: u32,
+    #[serde(skip)]
+    pub game_variant: Vec<u8>,
+}
+
+fn search<T: BlfChunk + SerializableBlfChunk + ReadableBlfChunk>(data: &[u8]) -> BLFLibResult<Option<T>> {
+    if data.len() <= s_blf_header::size() {
+        return Ok(None);
+    }
+
+    search_for_chunk::<T>(data.to_vec())
+}
+
+fn require<T: BlfChunk>(chunk: Option<T>) -> BLFLibResult<T> {
+    chunk.ok_or(BLFLibError::ChunkNotFound { signature: T::get_signature(), version: Some(T::get_version()) })
+}
+
+/// Reads the content header, the header's version tells which title saved the content.
+pub fn read_content_summary(data: &[u8]) -> BLFLibResult<s_content_summary> {
+    if let Some(header) = search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header>(data)? {
+        let metadata = header.metadata;
+        return Ok(s_content_summary {
+            title: k_title_halo3.to_string(),
+            build_number: header.build_number,
+            file_type: metadata.file_type,
+            name: metadata.name.get_string(),
+            description: metadata.description.get_string()?,
+            author: metadata.author.get_string()?,
+            author_xuid: metadata.author_id,
+            date: metadata.date,
+            unique_id: metadata.unique_id,
+            game_id: metadata.game_id,
+            map_id: metadata.map_id,
+            hopper_id: (metadata.hopper_id != -1).then_some(metadata.hopper_id as u16),
+        });
+    }
+
+    if let Some(header) = search::<v13895_09_04_27_2201_atlas_release::s_blf_chunk_content_header>(data)? {
+        let metadata = header.metadata;
+        return Ok(s_content_summary {
+            title: k_title_halo3odst.to_string(),
+            build_number: header.build_number,
+            file_type: metadata.file_type,
+            name: metadata.name.get_string(),
+            description: metadata.description.get_string()?,
+            author: metadata.author.get_string()?,
+            author_xuid: metadata.author_id,
+            date: metadata.date,
+            unique_id: metadata.unique_id,
+            game_id: metadata.game_id,
+            map_id: metadata.map_id,
+            hopper_id: None,
+        });
+    }
+
+    if let Some(header) = search::<v12065_11_08_24_1738_tu1actual::s_blf_chunk_content_header>(data)? {
+        let metadata = header.metadata;
+        return Ok(s_content_summary {
+            title: k_title_haloreach.to_string(),
+            build_number: header.build_number,
+            file_type: metadata.general.file_type as i32,
+            name: metadata.name.get_string(),
+            description: metadata.description.get_string(),
+            author: metadata.modification_history.name.get_string()?,
+            author_xuid: metadata.modification_history.xuid,
+            date: metadata.modification_history.timestamp,
+            unique_id: metadata.general.unique_id,
+            game_id: metadata.general.game_id,
+            map_id: metadata.general.map_id,
+            hopper_id: metadata.matchmaking_data.map(|matchmaking_data| matchmaking_data.hopper_identifier),
+        });
+    }
+
+    Err("No Halo 3, ODST or Reach content header was found.".into())
+}
+
+pub fn read_screenshot(data: &[u8]) -> BLFLibResult<s_screenshot> {
+    let summary = read_content_summary(data)?;
+
+    // ODST writes Halo 3's screenshot chunks.
+    if summary.title == k_title_haloreach {
+        let screenshot_data = require(search::<v12065_11_08_24_1738_tu1actual::s_blf_chunk_screenshot_data>(data)?)?;
+
+        return Ok(s_screenshot {
+            summary,
+            jpeg_data: screenshot_data.jpeg_data,
+            camera_position: None,
+            game_tick: None,
+            film_tick: None,
+        });
+    }
+
+    let screenshot_camera = require(search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_screenshot_camera>(data)?)?;
+    let screenshot_data = require(search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_screenshot_data>(data)?)?;
+
+    Ok(s_screenshot {
+        summary,
+        jpeg_data: screenshot_data.jpeg_data,
+        camera_position: Some(screenshot_camera.camera.camera.position),
+        game_tick: Some(screenshot_camera.game_tick),
+        film_tick: Some(screenshot_camera.film_tick),
+    })
+}
+
+/// Only Halo 3 films can be read, ODST and Reach film headers haven't been mapped.
+pub fn read_film_variants(data: &[u8]) -> BLFLibResult<s_film_variants> {
+    let summary = read_content_summary(data)?;
+
+    if summary.title != k_title_halo3 {
+        return Err(format!("{} films are not supported.", summary.title).into());
+    }
+
+    let film_header = require(search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_saved_film_header>(data)?)?;
+
+    let map_chunk = v12070_08_09_05_2031_halo3_ship::s_blf_chunk_map_variant::create(film_header.options.map_variant);
+    let game_chunk = v12070_08_09_05_2031_halo3_ship::s_blf_chunk_game_variant::create(film_header.options.multiplayer_variant);
+
+    let map_variant = BlfFileBuilder::new()
+        .add_chunk(v12070_08_09_05_2031_halo3_ship::s_blf_chunk_start_of_file::default())
+        .add_chunk(v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header::create_for_map_variant(&map_chunk.map_variant))
+        .add_chunk(map_chunk.clone())
+        .add_chunk(v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file::default())
+        .write()?;
+
+    let game_variant = BlfFileBuilder::new()
+        .add_chunk(v12070_08_09_05_2031_halo3_ship::s_blf_chunk_start_of_file::default())
+        .add_chunk(v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header::create_for_game_variant(&game_chunk.game_variant))
+        .add_chunk(game_chunk.clone())
+        .add_chunk(v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file::default())
+        .write()?;
+
+    Ok(s_film_variants {
+        summary,
+        map_variant_name: map_chunk.map_variant.m_metadata.name.get_string(),
+        map_variant_author: map_chunk.map_variant.m_metadata.author.get_string()?,
+        map_variant,
+        game_variant_name: game_chunk.game_variant.m_base_variant.m_metadata.name.get_string(),
+        game_variant_author: game_chunk.game_variant.m_base_variant.m_metadata.author.get_string()?,
+        game_engine: game_chunk.game_variant.m_game_engine.to_u32()
+            .ok_or_else(|| BLFLibError::from("Failed to convert the film's game engine to an integer."))?,
+        game_variant,
+    })
+}
+
+/// A content header field which doesn't match the content it describes.
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Default, PartialEq, Debug, Clone, Serialize)]
+pub struct s_content_header_mismatch {
+    /// The field's path within the content header, such as "metadata.map_id".
+    pub field: String,
+    pub header_value: String,
+    pub content_value: String,
+}
+
+// The content header found in a file, compared against the header generated from the content it accompanies.
+struct s_content_header_sync {
+    offset: usize,
+    length: usize,
+    mismatches: Vec<s_content_header_mismatch>,
+    expected: Vec<u8>,
+}
+
+// Packaged content has data before the BLF, so chunks are walked from the first _blf header.
+// Returns the BLF's offset and each chunk's header and offset.
+fn read_chunk_headers(data: &[u8]) -> BLFLibResult<(usize, Vec<(s_blf_header, usize)>)> {
+    let blf_offset = OPTION_TO_RESULT!(
+        data.windows(4).position(|signature| signature == b"_blf"),
+        "No BLF was found."
+    )?;
+
+    let mut cursor = Cursor::new(data);
+    cursor.set_position(blf_offset as u64);
+
+    let headers = BlfFileReader::new(cursor)?
+        .map(|next| next.map(|(header, offset)| (header, offset as usize)))
+        .collect::<BLFLibResult<Vec<_>>>()?;
+
+    Ok((blf_offset, headers))
+}
+
+fn find_chunk_offset<T: BlfChunk>(data: &[u8]) -> BLFLibResult<Option<(usize, usize)>> {
+    let (_, headers) = read_chunk_headers(data)?;
+
+    Ok(headers.into_iter()
+        .find(|(header, _)| header.signature == T::get_signature() && header.version == T::get_version())
+        .map(|(header, offset)| (offset, header.chunk_size as usize)))
+}
+
+// The _eof's crc, hash or signature covers the content header, so it's rewritten to match.
+fn resign_end_of_file(data: &mut [u8], private_key: Option<&RsaPrivateKey>) -> BLFLibResult {
+    let (blf_offset, headers) = read_chunk_headers(data)?;
+    let Some((header, offset)) = headers.into_iter().find(|(header, _)| header.signature == chunk_signature::from_string("_eof")) else {
+        return Ok(());
+    };
+
+    let chunk_size = header.chunk_size as usize;
+    assert_ok!(chunk_size > s_blf_header::size() + 4, "The _eof is too small to have an authentication type.");
+
+    let previously_written = data[blf_offset..offset].to_vec();
+    let end_of_file = match data[offset + s_blf_header::size() + 4] {
+        0 => return Ok(()),
+        1 => v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file_with_crc::default().write(&previously_written)?,
+        2 => v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file_with_sha1::default().write(&previously_written)?,
+        3 => {
+            let private_key = OPTION_TO_RESULT!(private_key, "The file's _eof is signed with RSA, a private key is needed to re-sign it.")?;
+            let mut end_of_file = v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file_with_rsa::default();
+            end_of_file.sign(&previously_written, private_key)?;
+            end_of_file.write(&previously_written)?
+        }
+        authentication_type => return Err(format!("Unknown _eof authentication type {authentication_type}").into()),
+    };
+
+    assert_ok!(end_of_file.len() == chunk_size, "The rewritten _eof is a different size to the original.");
+    data[offset..offset + chunk_size].copy_from_slice(&end_of_file);
+    Ok(())
+}
+
+fn find_mismatches(path: &str, header: &Value, content: &Value, mismatches: &mut Vec<s_content_header_mismatch>) {
+    if let (Value::Object(header_fields), Value::Object(content_fields)) = (header, content) {
+        let keys = header_fields.keys().chain(content_fields.keys().filter(|key| !header_fields.contains_key(*key)));
+
+        for key in keys {
+            let field = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+            find_mismatches(
+                &field,
+                header_fields.get(key).unwrap_or(&Value::Null),
+                content_fields.get(key).unwrap_or(&Value::Null),
+                mismatches,
+            );
+        }
+
+        return;
+    }
+
+    if header != content {
+        let to_string = |value: &Value| match value {
+            Value::String(string) => string.clone(),
+            _ => value.to_string(),
+        };
+
+        mismatches.push(s_content_header_mismatch {
+            field: path.to_string(),
+            header_value: to_string(header),
+            content_value: to_string(content),
+        });
+    }
+}
+
+fn compare_content_headers<T: BlfChunk + SerializableBlfChunk + Serialize>(data: &[u8], header: &T, mut expected: T) -> BLFLibResult<s_content_header_sync> {
+    let (offset, length) = OPTION_TO_RESULT!(find_chunk_offset::<T>(data)?, "Failed to find the content header.")?;
+
+    let mut mismatches = Vec::new();
+    find_mismatches("", &serde_json::to_value(header)?, &serde_json::to_value(&expected)?, &mut mismatches);
+
+    Ok(s_content_header_sync {
+        offset,
+        length,
+        mismatches,
+        expected: expected.write(&Vec::new())?,
+    })
+}
+
+// Build numbers aren't part of the content, so the file's own are kept.
+fn get_content_header_sync(data: &[u8]) -> BLFLibResult<s_content_header_sync> {
+    if let Some(header) = search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header>(data)? {
+        let mut expected = if let Some(map_variant) = search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_map_variant>(data)? {
+            v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header::create_for_map_variant(&map_variant.map_variant)
+        } else if let Some(game_variant) = search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_game_variant>(data)? {
+            v12070_08_09_05_2031_halo3_ship::s_blf_chunk_content_header::create_for_game_variant(&game_variant.game_variant)
+        } else if let Some(film_header) = search::<v12070_08_09_05_2031_halo3_ship::s_blf_chunk_saved_film_header>(data)? {
+            // The rest of a film's metadata isn't stored in the film header.
+            let mut expected = header.clone();
+            expected.metadata.map_id = film_header.options.map_id;
+            expected.metadata.campaign_id = film_header.options.campaign_id;
+            expected
+        } else {
+            return Err("The content header doesn't accompany a map variant, game variant or film.".into());
+        };
+
+        expected.build_number = header.build_number;
+        expected.build_sequence_number = header.build_sequence_number;
+        return compare_content_headers(data, &header, expected);
+    }
+
+    if let Some(header) = search::<v12065_11_08_24_1738_tu1actual::s_blf_chunk_content_header>(data)? {
+        let mut expected = if let Some(map_variant) = search::<v12065_11_08_24_1738_tu1actual::s_blf_chunk_map_variant>(data)? {
+            v12065_11_08_24_1738_tu1actual::s_blf_chunk_content_header::create_for_map_variant(&map_variant.map_variant)
+        } else if let Some(game_variant) = search::<v12065_11_08_24_1738_tu1actual::s_blf_chunk_game_variant>(data)? {
+            v12065_11_08_24_1738_tu1actual::s_blf_chunk_content_header::create_for_game_variant(&game_variant.game_variant)?
+        } else {
+            return Err("The content header doesn't accompany a map variant or game variant.".into());
+        };
+
+        expected.build_number = header.build_number;
+        expected.build_sequence_number = header.build_sequence_number;
+        return compare_content_headers(data, &header, expected);
+    }
+
+    Err("No Halo 3 or Reach content header was found.".into())
+}
+
+/// Compares a file's content header against the map variant, game variant or film it accompanies.
+/// The game rejects files whose header doesn't match their content.
+pub fn validate_content_header(data: &[u8]) -> BLFLibResult<Vec<s_content_header_mismatch>> {
+    Ok(get_content_header_sync(data)?.mismatches)
+}
+
+/// Regenerates a file's content header from the content it accompanies, and the _eof's crc, hash or signature to match.
+/// The rest of the file is untouched. Files signed with RSA can only be resynced with a private key to re-sign them.
+pub fn resync_content_header(data: &[u8], private_key: Option<&RsaPrivateKey>) -> BLFLibResult<Vec<u8>> {
+    let sync = get_content_header_sync(data)?;
+    assert_ok!(sync.expected.len() == sync.length, "The regenerated content header is a different size to the original.");
+
+    let mut resynced = data.to_vec();
+    resynced[sync.offset..sync.offset + sync.length].copy_from_slice(&sync.expected);
+    resign_end_of_file(&mut resynced, private_key)?;
+    Ok(resynced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blf_lib::types::numbers::Float32;
+    use blf_lib::blam::common::memory::rsa_signature::RsaPublicKey;
+    use blf_lib::blf::chunks::{find_and_validate_eof, verify_rsa_signature};
+    use blf_lib::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_content_header, s_blf_chunk_end_of_file, s_blf_chunk_end_of_file_with_sha1, s_blf_chunk_saved_film_header, s_blf_chunk_screenshot_camera, s_blf_chunk_screenshot_data, s_blf_chunk_start_of_file};
+
+    #[test]
+    fn reads_halo3_screenshot() {
+        let mut content_header = s_blf_chunk_content_header::default();
+        content_header.build_number = 12070;
+        content_header.metadata.map_id = 380;
+        content_header.metadata.hopper_id = -1;
+
+        let mut screenshot_camera = s_blf_chunk_screenshot_camera::default();
+        screenshot_camera.camera.camera.position = real_point3d { x: Float32(1.0), y: Float32(2.0), z: Float32(3.0) };
+        screenshot_camera.game_tick = 6056;
+        screenshot_camera.film_tick = 6064;
+
+        let mut screenshot_data = s_blf_chunk_screenshot_data::default();
+        screenshot_data.jpeg_data = vec![0xFF, 0xD8, 0xFF, 0xD9];
+
+        // Packaged screenshots have data before the BLF.
+        let mut data = vec![0u8; 0x20];
+        data.extend(BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::default())
+            .add_chunk(content_header)
+            .add_chunk(screenshot_camera)
+            .add_chunk(screenshot_data)
+            .add_chunk(s_blf_chunk_end_of_file::default())
+            .write()
+            .unwrap());
+
+        let screenshot = read_screenshot(&data).unwrap();
+        assert_eq!(screenshot.summary.title, k_title_halo3);
+        assert_eq!(screenshot.summary.map_id, 380);
+        assert_eq!(screenshot.summary.hopper_id, None);
+        assert_eq!(screenshot.jpeg_data, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+        assert_eq!(screenshot.game_tick, Some(6056));
+        assert_eq!(screenshot.film_tick, Some(6064));
+    }
+
+    #[test]
+    fn resyncs_film_content_header() {
+        let mut film_header = s_blf_chunk_saved_film_header::default();
+        film_header.options.map_id = 380;
+
+        let data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::default())
+            .add_chunk(s_blf_chunk_content_header::default())
+            .add_chunk(film_header)
+            .add_chunk(s_blf_chunk_end_of_file::default())
+            .write()
+            .unwrap();
+
+        let mismatches = validate_content_header(&data).unwrap();
+        assert_eq!(mismatches, vec![s_content_header_mismatch {
+            field: "metadata.map_id".to_string(),
+            header_value: "0".to_string(),
+            content_value: "380".to_string(),
+        }]);
+
+        let resynced = resync_content_header(&data, None).unwrap();
+        assert_eq!(resynced.len(), data.len());
+        assert!(validate_content_header(&resynced).unwrap().is_empty());
+        assert_eq!(read_content_summary(&resynced).unwrap().map_id, 380);
+    }
+
+    #[test]
+    fn resyncs_hashed_end_of_file() {
+        let mut film_header = s_blf_chunk_saved_film_header::default();
+        film_header.options.map_id = 380;
+
+        // Packaged content has data before the BLF.
+        let mut data = vec![0u8; 0x20];
+        data.extend(BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::default())
+            .add_chunk(s_blf_chunk_content_header::default())
+            .add_chunk(film_header)
+            .add_chunk(s_blf_chunk_end_of_file_with_sha1::default())
+            .write()
+            .unwrap());
+
+        let resynced = resync_content_header(&data, None).unwrap();
+        assert!(validate_content_header(&resynced).unwrap().is_empty());
+        assert!(find_and_validate_eof(&resynced[0x20..]).is_ok());
+        assert!(find_and_validate_eof(&data[0x20..]).is_ok());
+    }
+
+    #[test]
+    fn refuses_to_resync_rsa_signed_end_of_file_without_a_key() {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+
+        let mut film_header = s_blf_chunk_saved_film_header::default();
+        film_header.options.map_id = 380;
+
+        let data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::default())
+            .add_chunk(s_blf_chunk_content_header::default())
+            .add_chunk(film_header)
+            .write_signed(&private_key)
+            .unwrap();
+
+        assert!(resync_content_header(&data, None).is_err());
+
+        let resynced = resync_content_header(&data, Some(&private_key)).unwrap();
+        assert!(validate_content_header(&resynced).unwrap().is_empty());
+        assert!(verify_rsa_signature(&resynced, &RsaPublicKey::from(&private_key)).is_ok());
+    }
+
+    #[test]
+    fn requires_content_header() {
+        assert!(read_content_summary(&[0u8; 0x40]).is_err());
+        assert!(read_content_summary(&[]).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use blf_lib::blf::registry::{build_title_and_build, get_build_registrations, read_headers, s_blf_build_registration, score_build_chunks};
+use blf_lib::blf::s_blf_header;
+use blf_lib_derivable::types::chunk_signature::chunk_signature;
+
+// Titles which write little endian files, the rest are big endian.
+const k_little_endian_titles: [&str; 2] = ["ares", "haloonline"];
+
+// Build strings in athr chunks are truncated, so shorter matches aren't trusted.
+const k_minimum_build_string_length: usize = 8;
+
+const k_build_string_score: usize = 8;
+const k_build_number_score: usize = 4;
+const k_byte_order_score: usize = 4;
+
+/// What a file says about the build which wrote it.
+#[derive(Default, Debug, PartialEq)]
+pub struct s_blf_build_evidence {
+    /// From the _blf byte order mark.
+    pub little_endian: Option<bool>,
+    /// From chdr chunks.
+    pub build_numbers: Vec<u16>,
+    /// From athr and flmh chunks.
+    pub build_strings: Vec<String>,
+}
+
+impl s_blf_build_evidence {
+    pub fn read(buffer: &[u8]) -> s_blf_build_evidence {
+        let mut evidence = s_blf_build_evidence::default();
+
+        for (offset, header) in read_headers(buffer) {
+            let body = &buffer[offset + s_blf_header::size()..offset + header.chunk_size as usize];
+
+            if header.signature == chunk_signature::from_string("_blf") && body.len() >= 2 {
+                // The mark is always 0xFFFE, written in the file's byte order.
+                evidence.little_endian = match [body[0], body[1]] {
+                    [0xFF, 0xFE] => Some(false),
+                    [0xFE, 0xFF] => Some(true),
+                    _ => None,
+                };
+            }
+            else if header.signature == chunk_signature::from_string("chdr") && body.len() >= 2 {
+                let bytes = [body[0], body[1]];
+                evidence.build_numbers.push(match evidence.little_endian {
+                    Some(true) => u16::from_le_bytes(bytes),
+                    _ => u16::from_be_bytes(bytes),
+                });
+            }
+            else if header.signature == chunk_signature::from_string("athr") || header.signature == chunk_signature::from_string("flmh") {
+                evidence.build_strings.extend(find_build_strings(body));
+            }
+        }
+
+        evidence
+    }
+
+    pub fn score(&self, build: &s_blf_build_registration) -> usize {
+        let (title, _) = build_title_and_build(build);
+        let mut score = 0;
+
+        if self.little_endian == Some(k_little_endian_titles.contains(&title)) {
+            score += k_byte_order_score;
+        }
+
+        let build_number_prefix = build.build_string.split('.').next().unwrap_or_default();
+        if self.build_numbers.iter().any(|build_number| format!("{build_number:05}") == build_number_prefix) {
+            score += k_build_number_score;
+        }
+
+        if self.build_strings.iter().any(|build_string| build_string.starts_with(build.build_string) || build.build_string.starts_with(build_string.as_str())) {
+            score += k_build_string_score;
+        }
+
+        score
+    }
+}
+
+// Build strings look like 12070.08.09.05.2031.halo3_ship, but may be truncated.
+fn find_build_strings(body: &[u8]) -> Vec<String> {
+    body.split(|byte| !(byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'_' | b'-')))
+        .filter(|run| run.len() >= k_minimum_build_string_length && run.contains(&b'.') && run[0].is_ascii_digit())
+        .map(|run| String::from_utf8_lossy(run).to_string())
+        .collect()
+}
+
+/// Guesses which build a file was written by.
+/// Combines the _blf byte order mark, chdr build numbers, athr and flmh build strings, and which builds declare the file's chunks.
+/// Returns None if nothing in the file points to a known build.
+pub fn detect_build(buffer: &[u8]) -> Option<&'static s_blf_build_registration> {
+    let headers = read_headers(buffer);
+    let evidence = s_blf_build_evidence::read(buffer);
+
+    get_build_registrations()
+        .into_iter()
+        .map(|build| (build, evidence.score(build) + score_build_chunks(build, &headers)))
+        .filter(|(_, score)| *score > 0)
+        // max_by_key keeps the last maximum, so reverse to prefer the first.
+        .rev()
+        .max_by_key(|(_, score)| *score)
+        .map(|(build, _)| build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blf::BlfFileBuilder;
+    use crate::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_end_of_file_with_crc, s_blf_chunk_start_of_file};
+
+    #[test]
+    fn detect_build_prefers_declaring_build() {
+        let data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("detect"))
+            .add_chunk(s_blf_chunk_end_of_file_with_crc::default())
+            .write()
+            .unwrap();
+
+        let build = detect_build(&data).unwrap();
+
+        assert_eq!(build.title, "Halo 3");
+        assert_eq!(build.build_string, "12070.08.09.05.2031.halo3_ship");
+    }
+
+    #[test]
+    fn find_build_strings_matches_truncated_strings() {
+        let mut body = vec![0u8; 4];
+        body.extend_from_slice(b"11855.07.08.20.2317.halo3_s");
+        body.extend_from_slice(&[0, 0, 0x12]);
+
+        assert_eq!(find_build_strings(&body), vec![String::from("11855.07.08.20.2317.halo3_s")]);
+    }
+}
@@ -0,0 +1,215 @@
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use blf_lib::blf::s_blf_header;
+use blf_lib_derivable::blf::chunks::{BlfChunk, ReadableBlfChunk};
+use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derivable::types::chunk_signature::chunk_signature;
+
+const k_search_block_size: usize = 0x10000;
+
+/// Reads chunk headers one at a time from any Read + Seek, without holding the file in memory.
+/// Iterating yields each header with its offset, bodies are only read when asked for.
+/// Iteration stops after the _eof chunk, or at anything which doesn't look like a chunk.
+pub struct BlfFileReader<R: Read + Seek> {
+    reader: R,
+    position: u64,
+    length: u64,
+    finished: bool,
+}
+
+impl BlfFileReader<BufReader<File>> {
+    pub fn open(path: impl Into<String>) -> BLFLibResult<Self> {
+        Self::new(BufReader::new(File::open(path.into())?))
+    }
+}
+
+impl<R: Read + Seek> BlfFileReader<R> {
+    /// Reads from the reader's current position.
+    pub fn new(mut reader: R) -> BLFLibResult<Self> {
+        let position = reader.stream_position()?;
+        let length = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(position))?;
+
+        Ok(Self {
+            reader,
+            position,
+            length,
+            finished: false,
+        })
+    }
+
+    pub fn read_body(&mut self, header: &s_blf_header, offset: u64) -> BLFLibResult<Vec<u8>> {
+        let chunk_size = header.chunk_size as u64;
+        if chunk_size < s_blf_header::size() as u64 || offset + chunk_size > self.length {
+            return Err(format!("The {} chunk at 0x{offset:X} has an invalid size of {chunk_size} bytes", header.signature).into());
+        }
+
+        self.read_range(offset + s_blf_header::size() as u64, (chunk_size as usize) - s_blf_header::size())
+    }
+
+    pub fn read_range(&mut self, offset: u64, length: usize) -> BLFLibResult<Vec<u8>> {
+        let mut bytes = vec![0u8; length];
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.reader.read_exact(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Reads a chunk's body as T.
+    /// Previously read data isn't available, so chunks which need it (such as an _eof with a hash) will fail to validate.
+    pub fn read_chunk<T: ReadableBlfChunk>(&mut self, header: &s_blf_header, offset: u64) -> BLFLibResult<T> {
+        let body_bytes = self.read_body(header, offset)?;
+        T::read(body_bytes, Some(header.clone()), &Vec::new())
+    }
+
+    /// Reads the first T found from the current position.
+    pub fn find_chunk<T: ReadableBlfChunk>(&mut self) -> BLFLibResult<Option<T>> {
+        while let Some(next) = self.next() {
+            let (header, offset) = next?;
+
+            if header.signature == T::get_signature() && header.version == T::get_version() {
+                return Ok(Some(self.read_chunk(&header, offset)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Scans every byte from the current position for T's header, rather than walking chunk headers,
+    /// so chunks are found even when something other than a chunk comes before them.
+    /// The stream is read a block at a time.
+    pub fn search_for_chunk<T: ReadableBlfChunk>(&mut self) -> BLFLibResult<Option<T>> {
+        let header_size = s_blf_header::size();
+        let mut window = Vec::<u8>::new();
+        let mut window_offset = self.position;
+        let mut block = vec![0u8; k_search_block_size];
+        self.reader.seek(SeekFrom::Start(self.position))?;
+
+        loop {
+            let read = self.reader.read(&mut block)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            window.extend_from_slice(&block[..read]);
+
+            let mut index = 0;
+            while index + header_size <= window.len() {
+                let header = s_blf_header::decode(&window[index..index + header_size])?;
+                let offset = window_offset + index as u64;
+
+                if header.signature == T::get_signature()
+                    && header.version == T::get_version()
+                    && header.chunk_size as usize >= header_size
+                    && offset + header.chunk_size as u64 <= self.length
+                {
+                    return Ok(Some(self.read_chunk(&header, offset)?));
+                }
+
+                index += 1;
+            }
+
+            // Keep the bytes a header could still start at, it may straddle the next block.
+            window.drain(..index);
+            window_offset += index as u64;
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read + Seek> Iterator for BlfFileReader<R> {
+    type Item = BLFLibResult<(s_blf_header, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished || self.position + s_blf_header::size() as u64 > self.length {
+            return None;
+        }
+
+        let mut header_bytes = [0u8; s_blf_header::size()];
+        if let Err(err) = self.reader.seek(SeekFrom::Start(self.position))
+            .and_then(|_| self.reader.read_exact(&mut header_bytes)) {
+            self.finished = true;
+            return Some(Err(err.into()));
+        }
+
+        let header = match s_blf_header::decode(&header_bytes) {
+            Ok(header) => header,
+            Err(err) => {
+                self.finished = true;
+                return Some(Err(err));
+            }
+        };
+
+        // some BLF files have extra noise at the end.
+        let chunk_size = header.chunk_size as u64;
+        if chunk_size < s_blf_header::size() as u64 || self.position + chunk_size > self.length {
+            self.finished = true;
+            return None;
+        }
+
+        let offset = self.position;
+        self.position += chunk_size;
+        self.finished = header.signature == chunk_signature::from_string("_eof");
+
+        Some(Ok((header, offset)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use super::*;
+    use crate::blf::BlfFileBuilder;
+    use crate::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_end_of_file, s_blf_chunk_start_of_file};
+
+    #[test]
+    fn reads_headers_lazily() {
+        let mut data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("stream"))
+            .add_chunk(s_blf_chunk_end_of_file::default())
+            .write()
+            .unwrap();
+        data.extend_from_slice(&[0xAB; 7]);
+
+        let mut reader = BlfFileReader::new(Cursor::new(data)).unwrap();
+        let headers: Vec<(s_blf_header, u64)> = reader.by_ref().collect::<BLFLibResult<_>>().unwrap();
+
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[0].0.signature, s_blf_chunk_start_of_file::get_signature());
+        assert_eq!(headers[1].1, 0x30);
+
+        let start_of_file: s_blf_chunk_start_of_file = reader.read_chunk(&headers[0].0, headers[0].1).unwrap();
+        assert_eq!(start_of_file, s_blf_chunk_start_of_file::new("stream"));
+    }
+
+    #[test]
+    fn searches_past_leading_data() {
+        let mut data = vec![0xAB; 5];
+        data.extend(BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("search"))
+            .add_chunk(s_blf_chunk_end_of_file::default())
+            .write()
+            .unwrap());
+
+        assert!(BlfFileReader::new(Cursor::new(data.clone())).unwrap().next().is_none());
+
+        let mut reader = BlfFileReader::new(Cursor::new(data)).unwrap();
+        let start_of_file = reader.search_for_chunk::<s_blf_chunk_start_of_file>().unwrap();
+        assert_eq!(start_of_file, Some(s_blf_chunk_start_of_file::new("search")));
+    }
+
+    #[test]
+    fn rejects_undersized_bodies() {
+        let data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("stream"))
+            .write()
+            .unwrap();
+        let mut reader = BlfFileReader::new(Cursor::new(data)).unwrap();
+        let (mut header, offset) = reader.next().unwrap().unwrap();
+        header.chunk_size = 4;
+
+        assert!(reader.read_body(&header, offset).is_err());
+    }
+}
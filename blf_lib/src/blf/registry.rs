@@ -0,0 +1,323 @@
+use std::any::TypeId;
+use serde::Serialize;
+use blf_lib::blf::s_blf_header;
+use blf_lib::blf::chunks::s_blf_chunk_raw::s_blf_chunk_raw;
+use blf_lib_derivable::blf::chunks::{DynTitleAndBuild, SerializableBlfChunk};
+use blf_lib_derivable::result::BLFLibResult;
+use blf_lib_derivable::types::chunk_signature::chunk_signature;
+use blf_lib_derivable::types::chunk_version::chunk_version;
+
+/// A chunk type known to blf_lib, registered by #[derive(BlfChunk)].
+pub struct s_blf_chunk_registration {
+    pub signature: [u8; 4],
+    pub version: chunk_version,
+    // The module the chunk is declared in, eg blf_lib::blf::chunks::halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_author
+    pub module_path: &'static str,
+    pub create: fn() -> Box<dyn SerializableBlfChunk>,
+    pub type_id: fn() -> TypeId,
+    pub to_json: fn(&dyn SerializableBlfChunk) -> BLFLibResult<serde_json::Value>,
+}
+
+inventory::collect!(s_blf_chunk_registration);
+
+/// A build known to blf_lib, registered by #[derive(TitleAndBuild)].
+pub struct s_blf_build_registration {
+    pub title: &'static str,
+    pub build_string: &'static str,
+    pub module_path: &'static str,
+}
+
+inventory::collect!(s_blf_build_registration);
+
+impl s_blf_build_registration {
+    pub const fn new(title: &'static str, build_string: &'static str, module_path: &'static str) -> Self {
+        Self {
+            title,
+            build_string,
+            module_path,
+        }
+    }
+}
+
+impl DynTitleAndBuild for s_blf_build_registration {
+    fn title(&self) -> String {
+        String::from(self.title)
+    }
+
+    fn build_string(&self) -> String {
+        String::from(self.build_string)
+    }
+
+    fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+}
+
+impl s_blf_chunk_registration {
+    pub const fn new(
+        signature: [u8; 4],
+        version: chunk_version,
+        module_path: &'static str,
+        create: fn() -> Box<dyn SerializableBlfChunk>,
+        type_id: fn() -> TypeId,
+        to_json: fn(&dyn SerializableBlfChunk) -> BLFLibResult<serde_json::Value>,
+    ) -> Self {
+        Self {
+            signature,
+            version,
+            module_path,
+            create,
+            type_id,
+            to_json,
+        }
+    }
+
+    pub fn signature(&self) -> chunk_signature {
+        chunk_signature::new(self.signature.map(|byte| byte as std::ffi::c_char))
+    }
+
+    // Chunks live one module deeper than builds, so the chunk's own module is dropped.
+    fn title_and_build(&self) -> (&'static str, &'static str) {
+        let mut segments = self.module_path.rsplit("::").skip(1);
+        let build = segments.next().unwrap_or_default();
+        let title = segments.next().unwrap_or_default();
+        (title, build)
+    }
+}
+
+pub(crate) fn build_title_and_build(build: &dyn DynTitleAndBuild) -> (&'static str, &'static str) {
+    let mut segments = build.module_path().rsplit("::");
+    let build = segments.next().unwrap_or_default();
+    let title = segments.next().unwrap_or_default();
+    (title, build)
+}
+
+pub fn get_chunk_registrations() -> impl Iterator<Item = &'static s_blf_chunk_registration> {
+    inventory::iter::<s_blf_chunk_registration>.into_iter()
+}
+
+/// Every build declared in blf_lib::blf::versions.
+/// Other crates deriving TitleAndBuild (eg blf_cli's title converters) are registered too, but left out here.
+pub fn get_build_registrations() -> Vec<&'static s_blf_build_registration> {
+    let mut builds: Vec<&'static s_blf_build_registration> = inventory::iter::<s_blf_build_registration>.into_iter()
+        .filter(|build| build.module_path.starts_with("blf_lib::blf::versions::"))
+        .collect();
+
+    builds.sort_by(|a, b| b.module_path.cmp(a.module_path));
+    builds
+}
+
+/// Scores how well a build matches the given chunk headers.
+/// Each chunk declared by the build itself scores 2, and each chunk declared by another build of the same title scores 1.
+/// Builds reuse most of their chunks from each other, so this is only useful alongside other evidence, see detection::detect_build.
+pub fn score_build_chunks(build: &dyn DynTitleAndBuild, headers: &[(usize, s_blf_header)]) -> usize {
+    let (title, build) = build_title_and_build(build);
+
+    headers.iter()
+        .map(|(_, header)| {
+            let declaring_builds: Vec<(&str, &str)> = get_chunk_registrations()
+                .filter(|registration| registration.signature() == header.signature && registration.version == header.version)
+                .map(|registration| registration.title_and_build())
+                .collect();
+
+            if declaring_builds.contains(&(title, build)) { 2 }
+            else if declaring_builds.iter().any(|(declaring_title, _)| *declaring_title == title) { 1 }
+            else { 0 }
+        })
+        .sum()
+}
+
+/// Finds the registration for a chunk's concrete type.
+/// Returns None for chunks which aren't registered, such as s_blf_chunk_raw.
+pub fn find_registration_for_chunk(chunk: &dyn SerializableBlfChunk) -> Option<&'static s_blf_chunk_registration> {
+    let type_id = chunk.as_any().type_id();
+
+    get_chunk_registrations()
+        .find(|registration| (registration.type_id)() == type_id)
+}
+
+/// Serializes a chunk created from the registry to JSON.
+/// Returns None for chunks which aren't registered, such as s_blf_chunk_raw.
+pub fn chunk_to_json(chunk: &dyn SerializableBlfChunk) -> Option<BLFLibResult<serde_json::Value>> {
+    find_registration_for_chunk(chunk)
+        .map(|registration| (registration.to_json)(chunk))
+}
+
+// Used by #[derive(BlfChunk)].
+#[doc(hidden)]
+pub fn serialize_chunk<T: SerializableBlfChunk + Serialize>(chunk: &dyn SerializableBlfChunk) -> BLFLibResult<serde_json::Value> {
+    let chunk = chunk.as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| format!("Tried to serialize {} {} as the wrong type", chunk.signature(), chunk.version()))?;
+
+    Ok(serde_json::to_value(chunk)?)
+}
+
+/// Reads the header of every chunk in the buffer, along with its offset.
+/// Reading stops after the _eof chunk, or at anything which doesn't look like a chunk.
+pub fn read_headers(buffer: &[u8]) -> Vec<(usize, s_blf_header)> {
+    let mut headers = Vec::new();
+    let mut offset = 0usize;
+
+    while offset + s_blf_header::size() <= buffer.len() {
+        let header = match s_blf_header::decode(&buffer[offset..offset + s_blf_header::size()]) {
+            Ok(header) => header,
+            Err(_) => break,
+        };
+        let chunk_size = header.chunk_size as usize;
+
+        // some BLF files have extra noise at the end.
+        if chunk_size < s_blf_header::size() || offset + chunk_size > buffer.len() {
+            break;
+        }
+
+        headers.push((offset, header.clone()));
+        offset += chunk_size;
+
+        if header.signature == chunk_signature::from_string("_eof") {
+            break;
+        }
+    }
+
+    headers
+}
+
+/// Finds the chunk type to use for the given signature and version.
+/// Builds often reuse chunks declared by other builds, so matches are preferred in this order:
+/// the build itself, then any build of the same title, then any build of any title.
+pub fn find_chunk_registration(
+    build: &dyn DynTitleAndBuild,
+    signature: chunk_signature,
+    version: chunk_version,
+) -> Option<&'static s_blf_chunk_registration> {
+    find_chunk_registration_matching(build, signature, version, |_| true)
+}
+
+fn find_chunk_registration_matching(
+    build: &dyn DynTitleAndBuild,
+    signature: chunk_signature,
+    version: chunk_version,
+    filter: impl Fn(&s_blf_chunk_registration) -> bool,
+) -> Option<&'static s_blf_chunk_registration> {
+    let (title, build) = build_title_and_build(build);
+
+    let mut candidates: Vec<&'static s_blf_chunk_registration> = get_chunk_registrations()
+        .filter(|registration| registration.signature() == signature && registration.version == version)
+        .filter(|registration| filter(registration))
+        .collect();
+
+    // inventory doesn't guarantee any order, so sort for consistent results.
+    candidates.sort_by(|a, b| b.module_path.cmp(a.module_path));
+
+    candidates.iter()
+        .find(|registration| registration.title_and_build() == (title, build))
+        .or_else(|| candidates.iter().find(|registration| registration.title_and_build().0 == title))
+        .or_else(|| candidates.first())
+        .copied()
+}
+
+pub fn create_chunk(
+    build: &dyn DynTitleAndBuild,
+    signature: chunk_signature,
+    version: chunk_version,
+) -> Option<Box<dyn SerializableBlfChunk>> {
+    find_chunk_registration(build, signature, version).map(|registration| (registration.create)())
+}
+
+/// Reads every chunk in the buffer, using the chunk registry to pick a type for each.
+/// Unregistered chunks are returned as s_blf_chunk_raw.
+/// Reading stops after the _eof chunk, or at anything which doesn't look like a chunk.
+pub fn parse_any(buffer: &[u8], build: &dyn DynTitleAndBuild) -> BLFLibResult<Vec<Box<dyn SerializableBlfChunk>>> {
+    read_headers(buffer)
+        .into_iter()
+        .map(|(offset, header)| parse_chunk(buffer, offset, &header, build))
+        .collect()
+}
+
+/// Reads the chunk at the given offset, using the chunk registry to pick its type.
+/// Unregistered chunks are returned as s_blf_chunk_raw.
+pub fn parse_chunk(
+    buffer: &[u8],
+    offset: usize,
+    header: &s_blf_header,
+    build: &dyn DynTitleAndBuild,
+) -> BLFLibResult<Box<dyn SerializableBlfChunk>> {
+    let body = &buffer[offset + s_blf_header::size()..offset + header.chunk_size as usize];
+
+    // Every _eof shares a signature and version, the authentication type tells them apart.
+    let registration = if header.signature == chunk_signature::from_string("_eof") && body.len() > 4 {
+        let chunk_name = match body[4] {
+            0 => "s_blf_chunk_end_of_file",
+            1 => "s_blf_chunk_end_of_file_with_crc",
+            2 => "s_blf_chunk_end_of_file_with_sha1",
+            3 => "s_blf_chunk_end_of_file_with_rsa",
+            authentication_type => { return Err(format!("Unknown _eof authentication type {}", authentication_type).into()); }
+        };
+        find_chunk_registration_matching(build, header.signature, header.version, |registration| {
+            registration.module_path.rsplit("::").next() == Some(chunk_name)
+        })
+    } else {
+        find_chunk_registration(build, header.signature, header.version)
+    };
+
+    match registration.map(|registration| (registration.create)()) {
+        Some(mut chunk) => {
+            chunk.decode_body(body, &buffer[..offset])
+                .map_err(|err| err.in_chunk(offset as u64, header.signature, header.version))?;
+            Ok(chunk)
+        }
+        None => Ok(Box::new(s_blf_chunk_raw::new(header.signature, header.version, body.to_vec()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blf::BlfFileBuilder;
+    use crate::types::string::StaticString;
+    use crate::blf::versions::halo3::v12070_08_09_05_2031_halo3_ship::{s_blf_chunk_end_of_file_with_crc, s_blf_chunk_start_of_file, v12070_08_09_05_2031_halo3_ship};
+
+    #[test]
+    fn parse_any_reads_registered_and_unknown_chunks() {
+        let unknown = s_blf_chunk_raw::new(chunk_signature::from_string("zzzz"), chunk_version::new(1.0), vec![1, 2, 3, 4]);
+
+        let data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("parse any"))
+            .add_chunk(unknown.clone())
+            .add_chunk(s_blf_chunk_end_of_file_with_crc::default())
+            .write()
+            .unwrap();
+
+        let chunks = parse_any(&data, &v12070_08_09_05_2031_halo3_ship {}).unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks[0].as_any().downcast_ref::<s_blf_chunk_start_of_file>(),
+            Some(&s_blf_chunk_start_of_file::new("parse any"))
+        );
+        assert_eq!(chunks[1].as_any().downcast_ref::<s_blf_chunk_raw>(), Some(&unknown));
+        assert!(chunks[2].as_any().downcast_ref::<s_blf_chunk_end_of_file_with_crc>().is_some());
+    }
+
+    #[test]
+    fn parse_any_round_trips_raw_chunks() {
+        let data = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("raw"))
+            .add_chunk(s_blf_chunk_raw::new(chunk_signature::from_string("priv"), chunk_version::new(3.1), vec![0xDE, 0xAD, 0xBE, 0xEF, 0x00]))
+            .add_chunk(s_blf_chunk_end_of_file_with_crc::default())
+            .write()
+            .unwrap();
+
+        let mut builder = BlfFileBuilder::parse_any(&data, &v12070_08_09_05_2031_halo3_ship {}).unwrap();
+        builder.get_chunk_mut::<s_blf_chunk_start_of_file>().unwrap().name = StaticString::from_string_trimmed("edited".to_string());
+
+        let edited = BlfFileBuilder::new()
+            .add_chunk(s_blf_chunk_start_of_file::new("edited"))
+            .add_chunk(s_blf_chunk_raw::new(chunk_signature::from_string("priv"), chunk_version::new(3.1), vec![0xDE, 0xAD, 0xBE, 0xEF, 0x00]))
+            .add_chunk(s_blf_chunk_end_of_file_with_crc::default())
+            .write()
+            .unwrap();
+
+        assert_eq!(builder.write().unwrap(), edited);
+    }
+}
@@ -34,6 +34,7 @@ pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_file_transfers::*;
 pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_matchmaking_hopper_statistics::*;
 pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_map_image::*;
 pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_saved_film_header::*;
+pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_saved_film_data::*;
 
 #[derive(TitleAndBuild)]
 #[Title("Halo 3")]
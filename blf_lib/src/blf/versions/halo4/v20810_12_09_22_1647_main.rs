@@ -1,5 +1,6 @@
 use blf_lib_derive::TitleAndBuild;
 use crate::blf::chunks::halo3;
+use crate::blf::chunks::halo3odst;
 use crate::blf::chunks::halo4;
 
 pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_start_of_file::*;
@@ -7,6 +8,12 @@ pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file::*;
 pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file_with_crc::*;
 pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file_with_sha1::*;
 pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_end_of_file_with_rsa::*;
+pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_map_manifest::*;
+pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_hopper_description_table::*;
+pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_online_file_manifest::*;
+pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_banhammer_messages::*;
+pub use halo3::v12070_08_09_05_2031_halo3_ship::s_blf_chunk_matchmaking_tips::*;
+pub use halo3odst::v13895_09_04_27_2201_atlas_release::s_blf_chunk_author::*;
 pub use halo4::v20810_12_09_22_1647_main::s_blf_chunk_packed_game_variant::*;
 pub use halo4::v20810_12_09_22_1647_main::s_blf_chunk_game_variant::*;
 
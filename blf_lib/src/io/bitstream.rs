@@ -1,8 +1,10 @@
 mod bitstream_reader;
 mod bitstream_writer;
+mod bitstream_trace;
 
 pub use bitstream_reader::c_bitstream_reader;
 pub use bitstream_writer::c_bitstream_writer;
+pub use bitstream_trace::*;
 
 #[derive(Default, PartialEq, Eq, Debug, Copy, Clone)]
 pub enum e_bitstream_byte_order
@@ -6,13 +6,15 @@ use binrw::BinRead;
 use num_traits::FromPrimitive;
 use widestring::U16CString;
 use blf_lib::blam::common::math::real_math::{assert_valid_real_normal3d, cross_product3d, dot_product3d, k_real_epsilon, global_forward3d, global_left3d, global_up3d, normalize3d, valid_real_vector3d_axes3, arctangent, k_pi, rotate_vector_about_axis, valid_real_vector3d_axes2};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::math::real_math::{dequantize_real, dequantize_unit_vector3d};
 use blf_lib::{assert_ok, OPTION_TO_RESULT};
 use blf_lib::io::bitstream::{e_bitstream_byte_fill_direction};
 use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
 use crate::blam::common::math::integer_math::int32_point3d;
 use crate::blam::common::math::real_math::real_vector3d;
-use crate::blam::halo3::v12070_08_09_05_2031_halo3_ship::networking::transport::transport_security::s_transport_secure_address;
+use crate::blam::halo3::v12070_08_09_05_2031_halo3_ship::networking::transport::transport_security::{k_transport_secure_address_size_in_bits, s_transport_secure_address};
 use crate::io::bitstream::{e_bitstream_byte_order, e_bitstream_state};
+use crate::io::bitstream::bitstream_trace::{is_bitstream_trace_active, record_bitstream_read};
 use crate::io::bitstream::e_bitstream_byte_fill_direction::{_bitstream_byte_fill_direction_msb_to_lsb, _bitstream_byte_fill_direction_lsb_to_msb};
 use crate::types::numbers::Float32;
 
@@ -119,6 +121,10 @@ impl<'a> c_bitstream_reader<'a> {
         (self.current_stream_byte_position, self.current_stream_bit_position)
     }
 
+    pub fn get_current_bit_offset(&self) -> usize {
+        (self.current_stream_byte_position * 8) + self.current_stream_bit_position
+    }
+
     // READS
 
     pub fn read_raw_data(&mut self, size_in_bits: usize) -> BLFLibResult<Vec<u8>> {
@@ -170,7 +176,12 @@ impl<'a> c_bitstream_reader<'a> {
         }
 
         if remaining_stream_bits < size_in_bits {
-            return Err(format!("Tried to read {size_in_bits} bits but the stream only has {remaining_stream_bits} bits left!").into())
+            return Err(BLFLibError::BitstreamOverflow {
+                field: None,
+                bit_offset: self.get_current_bit_offset(),
+                size_in_bits,
+                remaining_bits: remaining_stream_bits,
+            })
         }
 
         if size_in_bits == 0 {
@@ -346,12 +357,13 @@ impl<'a> c_bitstream_reader<'a> {
         assert_ok!(size_in_bits <= 32);
         let size_in_bytes = (size_in_bits as f32 / 8f32 ).ceil() as usize;
         let mut bytes_vec = vec![0u8; size_in_bytes];
-        self.read_bits_internal(&mut bytes_vec, size_in_bits)?;
+        let bit_offset = self.get_current_bit_offset();
+        self.read_bits_internal(&mut bytes_vec, size_in_bits).map_err(|err| err.with_field(name))?;
         let bytes_slice = bytes_vec.as_slice();
 
         let mut byte_array = [0u8; 4];
 
-        Ok(T::try_from(match self.m_packed_byte_order {
+        let value = T::try_from(match self.m_packed_byte_order {
             e_bitstream_byte_order::_bitstream_byte_order_little_endian => {
                 byte_array[0..bytes_slice.len()].copy_from_slice(bytes_slice);
                 u32::from_le_bytes(byte_array)
@@ -366,7 +378,13 @@ impl<'a> c_bitstream_reader<'a> {
             name,
             size_in_bits,
             byte_array,
-        )))?)
+        )))?;
+
+        if is_bitstream_trace_active() {
+            record_bitstream_read(bit_offset, size_in_bits, name, value.to_string());
+        }
+
+        Ok(value)
     }
 
     pub fn read_big_flags(&mut self, name: &str, flags: &mut [bool]) -> BLFLibResult {
@@ -429,16 +447,23 @@ impl<'a> c_bitstream_reader<'a> {
         assert_ok!(size_in_bits > 0);
         assert_ok!(size_in_bits <= 32);
         let mut bytes = [0u8; 4];
-        self.read_bits_internal(&mut bytes, size_in_bits)?;
+        let bit_offset = self.get_current_bit_offset();
+        self.read_bits_internal(&mut bytes, size_in_bits).map_err(|err| err.with_field(name))?;
 
-        Ok(T::try_from(match self.m_packed_byte_order {
+        let value = T::try_from(match self.m_packed_byte_order {
             e_bitstream_byte_order::_bitstream_byte_order_little_endian => { f32::from_le_bytes(bytes) }
             e_bitstream_byte_order::_bitstream_byte_order_big_endian => { f32::from_be_bytes(bytes) }
         }).map_err(|e|BLFLibError::from(format!("\
             read_integer failed to convert u32 to type. size = {} data = {:?}",
             size_in_bits,
             bytes,
-        )))?)
+        )))?;
+
+        if is_bitstream_trace_active() {
+            record_bitstream_read(bit_offset, size_in_bits, name, value.to_string());
+        }
+
+        Ok(value)
     }
 
     pub fn read_i16(&mut self, size_in_bits: usize) -> BLFLibResult<i16> {
@@ -483,23 +508,17 @@ impl<'a> c_bitstream_reader<'a> {
     {
         assert_ok!(size_in_bits > 0);
         assert_ok!(size_in_bits <= 64);
-        let mut bytes = [0u8; 8];
-        self.read_bits_internal(&mut bytes, size_in_bits)?;
 
-        Ok(T::try_from(
-            match self.m_packed_byte_order {
-                e_bitstream_byte_order::_bitstream_byte_order_little_endian => {
-                    u64::from_le_bytes(bytes)
-                }
-                e_bitstream_byte_order::_bitstream_byte_order_big_endian => {
-                    u64::from_be_bytes(bytes)
-                }
-            }
-        ).map_err(|e|BLFLibError::from("read_qword failed to convert u64 to type."))?)
+        Ok(T::try_from(self.read_qword_internal(size_in_bits)?)
+            .map_err(|e|BLFLibError::from("read_qword failed to convert u64 to type."))?)
     }
 
-    pub fn read_identifier(identifier: String) { // param may be wrong.
-        unimplemented!()
+    /// Identifiers are debug markers, release builds don't write anything to the stream for them.
+    pub fn read_identifier(&mut self, identifier: &str) -> BLFLibResult {
+        assert_ok!(self.reading());
+        assert_ok!(!identifier.is_empty());
+
+        Ok(())
     }
 
     pub fn read_point3d(&mut self, point: &mut int32_point3d, axis_encoding_size_in_bits: usize) -> BLFLibResult {
@@ -524,12 +543,32 @@ impl<'a> c_bitstream_reader<'a> {
         Ok(())
     }
 
-    pub fn read_qword_internal(size_in_bits: u8) -> u64 {
-        unimplemented!()
+    pub fn read_qword_internal(&mut self, size_in_bits: usize) -> BLFLibResult<u64> {
+        let mut bytes_vec = vec![0u8; size_in_bits.div_ceil(8)];
+        self.read_bits_internal(&mut bytes_vec, size_in_bits)?;
+        let bytes_slice = bytes_vec.as_slice();
+
+        let mut byte_array = [0u8; 8];
+
+        // Like read_integer, partial qwords are aligned to the least significant end.
+        Ok(match self.m_packed_byte_order {
+            e_bitstream_byte_order::_bitstream_byte_order_little_endian => {
+                byte_array[0..bytes_slice.len()].copy_from_slice(bytes_slice);
+                u64::from_le_bytes(byte_array)
+            }
+            e_bitstream_byte_order::_bitstream_byte_order_big_endian => {
+                byte_array[8 - bytes_slice.len()..8].copy_from_slice(bytes_slice);
+                u64::from_be_bytes(byte_array)
+            }
+        })
     }
 
-    pub fn read_secure_address(address: &mut s_transport_secure_address) {
-        unimplemented!()
+    pub fn read_secure_address(&mut self, address: &mut s_transport_secure_address) -> BLFLibResult {
+        assert_ok!(self.reading());
+
+        *address = self.read_raw(k_transport_secure_address_size_in_bits)?;
+
+        Ok(())
     }
 
     pub fn angle_to_axes_internal(up: &real_vector3d, angle: impl Into<f32>, forward: &mut real_vector3d) -> BLFLibResult {
@@ -564,8 +603,10 @@ impl<'a> c_bitstream_reader<'a> {
         Ok(())
     }
 
-    pub fn read_string(_string: &mut String, max_string_size: u8) {
-        unimplemented!()
+    pub fn read_string(&mut self, string: &mut String, max_string_size: usize) -> BLFLibResult {
+        *string = self.read_string_extended_ascii(max_string_size)?;
+
+        Ok(())
     }
 
     // differs from blam API
@@ -624,12 +665,26 @@ impl<'a> c_bitstream_reader<'a> {
         Err("Exceeded max string size reading wchar string.".into())
     }
 
-    pub fn read_unit_vector(unit_vector: &mut real_vector3d, size_in_bits: u8) {
-        unimplemented!()
+    pub fn read_unit_vector(&mut self, unit_vector: &mut real_vector3d, size_in_bits: usize) -> BLFLibResult {
+        assert_ok!(self.reading());
+
+        let quantized: i32 = self.read_integer("unit-vector", size_in_bits)?;
+        dequantize_unit_vector3d(quantized, unit_vector, size_in_bits)
     }
 
-    pub fn read_vector(vector: &mut real_vector3d, min_value: f32, max_value: f32, step_count_size_in_bits: f32, size_in_bits: u8) {
-        unimplemented!()
+    /// Vectors are written as a quantized magnitude followed by their direction as a unit vector.
+    pub fn read_vector(&mut self, vector: &mut real_vector3d, min_value: f32, max_value: f32, step_count_size_in_bits: usize, size_in_bits: usize) -> BLFLibResult {
+        assert_ok!(self.reading());
+
+        let quantized_magnitude: i32 = self.read_integer("vector-magnitude", step_count_size_in_bits)?;
+        let magnitude = dequantize_real(quantized_magnitude, min_value, max_value, 1 << step_count_size_in_bits, false, false);
+
+        self.read_unit_vector(vector, size_in_bits)?;
+        vector.i = Float32(vector.i * magnitude);
+        vector.j = Float32(vector.j * magnitude);
+        vector.k = Float32(vector.k * magnitude);
+
+        Ok(())
     }
 
     pub fn begin_reading(&mut self) {
@@ -663,12 +718,18 @@ impl<'a> c_bitstream_reader<'a> {
         self.reset(e_bitstream_state::_bitstream_state_initial);
     }
 
-    fn skip(bits_to_skip: u32) {
-        unimplemented!()
+    pub fn skip(&mut self, bits_to_skip: usize) -> BLFLibResult {
+        assert_ok!(!self.would_overflow(bits_to_skip));
+
+        let new_bit_position = self.get_current_bit_offset() + bits_to_skip;
+        self.current_stream_byte_position = new_bit_position / 8;
+        self.current_stream_bit_position = new_bit_position % 8;
+
+        Ok(())
     }
 
-    fn would_overflow(size_in_bits: u32) -> bool {
-        unimplemented!()
+    pub fn would_overflow(&self, size_in_bits: usize) -> bool {
+        self.get_current_bit_offset() + size_in_bits > self.m_data_size_bytes * 8
     }
 
     pub fn axes_compute_reference_internal(
@@ -840,4 +901,23 @@ mod bitstream_reader_tests {
         // game entry 1 game variant (truncated)
         assert_eq!(sut.read_string_utf8(3).unwrap(), "5_");
     }
+
+    #[test]
+    fn read_past_end_reports_field() {
+        let test_data: [u8; 1] = [0b001_11111];
+
+        let mut sut = c_bitstream_reader::new(&test_data, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        sut.begin_reading();
+
+        assert_eq!(sut.read_integer::<u32>("first", 3).unwrap(), 0b001);
+        match sut.read_integer::<u32>("second", 8) {
+            Err(BLFLibError::BitstreamOverflow { field, bit_offset, size_in_bits, remaining_bits }) => {
+                assert_eq!(field.as_deref(), Some("second"));
+                assert_eq!(bit_offset, 3);
+                assert_eq!(size_in_bits, 8);
+                assert_eq!(remaining_bits, 5);
+            }
+            result => panic!("Expected a bitstream overflow, got {result:?}"),
+        }
+    }
 }
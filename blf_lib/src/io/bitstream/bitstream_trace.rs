@@ -0,0 +1,138 @@
+use std::cell::RefCell;
+use std::fmt::Write;
+
+/// A named read made by a c_bitstream_reader while tracing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct s_bitstream_trace_entry {
+    /// Relative to the start of the reader's data.
+    pub bit_offset: usize,
+    pub size_in_bits: usize,
+    pub name: String,
+    pub value: String,
+}
+
+thread_local! {
+    static k_bitstream_trace: RefCell<Option<Vec<s_bitstream_trace_entry>>> = const { RefCell::new(None) };
+}
+
+/// Records every named read made on this thread by any c_bitstream_reader, until end_bitstream_trace is called.
+pub fn begin_bitstream_trace() {
+    k_bitstream_trace.with(|trace| *trace.borrow_mut() = Some(Vec::new()));
+}
+
+/// Stops tracing, returning every read recorded since begin_bitstream_trace.
+pub fn end_bitstream_trace() -> Vec<s_bitstream_trace_entry> {
+    k_bitstream_trace.with(|trace| trace.borrow_mut().take()).unwrap_or_default()
+}
+
+/// Traces every named read made while running the given function.
+pub fn trace_bitstream<T>(function: impl FnOnce() -> T) -> (T, Vec<s_bitstream_trace_entry>) {
+    begin_bitstream_trace();
+    let result = function();
+    (result, end_bitstream_trace())
+}
+
+pub(crate) fn is_bitstream_trace_active() -> bool {
+    k_bitstream_trace.with(|trace| trace.borrow().is_some())
+}
+
+pub(crate) fn record_bitstream_read(bit_offset: usize, size_in_bits: usize, name: &str, value: String) {
+    k_bitstream_trace.with(|trace| {
+        if let Some(entries) = trace.borrow_mut().as_mut() {
+            entries.push(s_bitstream_trace_entry {
+                bit_offset,
+                size_in_bits,
+                name: name.to_string(),
+                value,
+            });
+        }
+    });
+}
+
+/// Formats a trace as an annotated bit map of the data it was read from, one line per read.
+/// Bits are shown in stream order, most significant bit first, and bits which weren't read by a named read are called out.
+pub fn format_bitstream_trace(data: &[u8], entries: &[s_bitstream_trace_entry]) -> String {
+    let mut output = String::new();
+    let mut expected_bit_offset = 0usize;
+
+    for entry in entries {
+        if entry.bit_offset > expected_bit_offset {
+            writeln!(output, "0x{:04X}.{}  {:>3}  ({} unnamed bits)",
+                expected_bit_offset / 8,
+                expected_bit_offset % 8,
+                entry.bit_offset - expected_bit_offset,
+                entry.bit_offset - expected_bit_offset,
+            ).unwrap();
+        }
+
+        writeln!(output, "0x{:04X}.{}  {:>3}  {:<32}  {} = {}",
+            entry.bit_offset / 8,
+            entry.bit_offset % 8,
+            entry.size_in_bits,
+            format_bits(data, entry.bit_offset, entry.size_in_bits),
+            entry.name,
+            entry.value,
+        ).unwrap();
+
+        expected_bit_offset = entry.bit_offset + entry.size_in_bits;
+    }
+
+    let total_bits = data.len() * 8;
+    if total_bits > expected_bit_offset {
+        writeln!(output, "0x{:04X}.{}  {:>3}  ({} unread bits)",
+            expected_bit_offset / 8,
+            expected_bit_offset % 8,
+            total_bits - expected_bit_offset,
+            total_bits - expected_bit_offset,
+        ).unwrap();
+    }
+
+    output
+}
+
+// Long fields are truncated, the value column has the full value.
+fn format_bits(data: &[u8], bit_offset: usize, size_in_bits: usize) -> String {
+    let mut bits: String = (bit_offset..bit_offset + size_in_bits.min(32))
+        .map(|bit| match data.get(bit / 8) {
+            Some(byte) if (byte >> (7 - (bit % 8))) & 1 == 1 => '1',
+            Some(_) => '0',
+            None => '?',
+        })
+        .collect();
+
+    if size_in_bits > 32 {
+        bits.replace_range(29..32, "...");
+    }
+
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::bitstream::{c_bitstream_reader, e_bitstream_byte_order};
+
+    #[test]
+    fn trace_records_named_reads() {
+        let test_data: [u8; 2] = [0b001_11111, 0b11111111];
+
+        let (_, entries) = trace_bitstream(|| {
+            let mut sut = c_bitstream_reader::new(&test_data, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+            sut.begin_reading();
+            sut.read_integer::<u32>("first", 3).unwrap();
+            sut.read_integer::<u32>("second", 5).unwrap();
+        });
+
+        assert_eq!(entries, vec![
+            s_bitstream_trace_entry { bit_offset: 0, size_in_bits: 3, name: String::from("first"), value: String::from("1") },
+            s_bitstream_trace_entry { bit_offset: 3, size_in_bits: 5, name: String::from("second"), value: String::from("31") },
+        ]);
+        assert_eq!(
+            format_bitstream_trace(&test_data, &entries),
+            "0x0000.0    3  001                               first = 1\n\
+             0x0000.3    5  11111                             second = 31\n\
+             0x0001.0    8  (8 unread bits)\n"
+        );
+        assert!(!is_bitstream_trace_active());
+    }
+}
@@ -1,25 +1,32 @@
 
 use std::cmp::min;
-use std::error::Error;
 use std::io::Cursor;
 use binrw::BinWrite;
 use num_traits::ToPrimitive;
 use widestring::U16CString;
 use blf_lib::{assert_ok, OPTION_TO_RESULT};
-use blf_lib::blam::common::math::real_math::{assert_valid_real_normal3d, cross_product3d, dot_product3d, k_real_epsilon, global_forward3d, global_left3d, global_up3d, normalize3d, valid_real_vector3d_axes3, arctangent, k_pi};
+use blf_lib::blam::common::math::real_math::{assert_valid_real_normal3d, cross_product3d, dot_product3d, k_real_epsilon, global_forward3d, global_left3d, global_up3d, normalize3d, valid_real_vector3d_axes3, arctangent, k_pi, magnitude_squared3d, square_root};
+use blf_lib::blam::haloreach::v12065_11_08_24_1738_tu1actual::math::real_math::{quantize_real, quantize_unit_vector3d};
 use blf_lib::io::bitstream::e_bitstream_byte_fill_direction;
 use blf_lib::io::bitstream::e_bitstream_byte_fill_direction::{_bitstream_byte_fill_direction_lsb_to_msb, _bitstream_byte_fill_direction_msb_to_lsb};
 use blf_lib_derivable::result::{BLFLibError, BLFLibResult};
 use crate::blam::common::math::integer_math::int32_point3d;
 use crate::blam::common::math::real_math::real_vector3d;
-use crate::blam::halo3::v12070_08_09_05_2031_halo3_ship::networking::transport::transport_security::s_transport_secure_address;
-use crate::io::bitstream::{e_bitstream_byte_order, e_bitstream_state};
+use crate::blam::halo3::v12070_08_09_05_2031_halo3_ship::networking::transport::transport_security::{k_transport_secure_address_size_in_bits, s_transport_secure_address};
+use crate::io::bitstream::{c_bitstream_reader, e_bitstream_byte_order, e_bitstream_state};
+
+const k_growable_initial_size_bytes: usize = 0x100;
 
 pub struct c_bitstream_writer
 {
     m_data: Vec<u8>,
     m_data_size_bytes: usize,
     m_state: e_bitstream_state,
+    /// Growable streams resize their data as it's written, up to the maximum size if there is one.
+    m_growable: bool,
+    m_maximum_size_bytes: Option<usize>,
+    /// The last identifier written, used to name the field which overflowed the stream.
+    m_current_identifier: Option<String>,
 
     m_byte_order: e_bitstream_byte_order,
     /// Some old versions of halo will pack BE values in LE and visa versa.
@@ -53,6 +60,9 @@ impl c_bitstream_writer {
             m_data: vec![0u8; size],
             m_data_size_bytes: size,
             m_state: e_bitstream_state::_bitstream_state_initial,
+            m_growable: false,
+            m_maximum_size_bytes: Some(size),
+            m_current_identifier: None,
 
             m_byte_order: byte_order,
             m_packed_byte_order: byte_order,
@@ -69,6 +79,9 @@ impl c_bitstream_writer {
             m_data: vec![0u8; size],
             m_data_size_bytes: size,
             m_state: e_bitstream_state::_bitstream_state_initial,
+            m_growable: false,
+            m_maximum_size_bytes: Some(size),
+            m_current_identifier: None,
 
             m_byte_order: byte_order,
             m_packed_byte_order: byte_order.swap(),
@@ -80,6 +93,24 @@ impl c_bitstream_writer {
         }
     }
 
+    /// Creates a stream which grows as it's written to, rather than being allocated up front.
+    /// Writing past the maximum size, if one is provided, fails.
+    pub fn new_growable(maximum_size: Option<usize>, byte_order: e_bitstream_byte_order) -> c_bitstream_writer {
+        let initial_size = min(k_growable_initial_size_bytes, maximum_size.unwrap_or(usize::MAX));
+        let mut writer = Self::new(initial_size, byte_order);
+        writer.m_growable = true;
+        writer.m_maximum_size_bytes = maximum_size;
+        writer
+    }
+
+    pub fn new_growable_with_legacy_settings(maximum_size: Option<usize>, byte_order: e_bitstream_byte_order) -> c_bitstream_writer {
+        let initial_size = min(k_growable_initial_size_bytes, maximum_size.unwrap_or(usize::MAX));
+        let mut writer = Self::new_with_legacy_settings(initial_size, byte_order);
+        writer.m_growable = true;
+        writer.m_maximum_size_bytes = maximum_size;
+        writer
+    }
+
     pub fn get_byte_order(&self) -> e_bitstream_byte_order {
         self.m_byte_order
     }
@@ -88,6 +119,15 @@ impl c_bitstream_writer {
         (self.current_stream_byte_position, self.current_stream_bit_position)
     }
 
+    pub fn get_current_bit_offset(&self) -> usize {
+        (self.current_stream_byte_position * 8) + self.current_stream_bit_position
+    }
+
+    /// The number of bits written so far, this is the stream's final length once writing has finished.
+    pub fn get_data_size_in_bits(&self) -> usize {
+        self.get_current_bit_offset()
+    }
+
 
     // WRITES
 
@@ -205,6 +245,9 @@ impl c_bitstream_writer {
     }
 
     pub fn write_qword(&mut self, value: impl Into<u64>, size_in_bits: usize) -> BLFLibResult {
+        assert_ok!(size_in_bits > 0);
+        assert_ok!(size_in_bits <= 64);
+
         match self.m_packed_byte_order {
             e_bitstream_byte_order::_bitstream_byte_order_little_endian => {
                 self.write_bits_internal(&value.into().to_le_bytes(), size_in_bits)?;
@@ -222,11 +265,15 @@ impl c_bitstream_writer {
         Ok(())
     }
 
-    fn write_bits_internal(&mut self, data: &[u8], size_in_bits: usize) -> Result<(), Box<dyn Error>> {
+    fn write_bits_internal(&mut self, data: &[u8], size_in_bits: usize) -> BLFLibResult {
         if data.len() < (size_in_bits as f32 / 8f32).ceil() as usize {
             return Err(format!("Tried to write {size_in_bits} bits but only {} were provided!", (data.len() * 8)).into())
         }
 
+        if self.would_overflow(size_in_bits) {
+            self.grow(size_in_bits)?;
+        }
+
         // println!("memory:bitstream:bitstream_writer write_bits_internal: writing {size_in_bits}");
 
         let surplus_bytes = data.len() - (size_in_bits as f32 / 8f32).ceil() as usize;
@@ -336,8 +383,14 @@ impl c_bitstream_writer {
         Ok(())
     }
 
-    pub fn write_identifier(identifier: String) {
-        unimplemented!()
+    /// Identifiers are debug markers, release builds don't write anything to the stream for them.
+    pub fn write_identifier(&mut self, identifier: &str) -> BLFLibResult {
+        assert_ok!(self.writing());
+        assert_ok!(!identifier.is_empty());
+
+        self.m_current_identifier = Some(identifier.to_string());
+
+        Ok(())
     }
 
     pub fn write_point3d(&mut self, point: &int32_point3d, axis_encoding_size_in_bits: usize) -> BLFLibResult {
@@ -395,12 +448,14 @@ impl c_bitstream_writer {
         Ok(())
     }
 
-    pub fn write_secure_address(address: &s_transport_secure_address) -> BLFLibResult {
-        unimplemented!()
+    pub fn write_secure_address(&mut self, address: &s_transport_secure_address) -> BLFLibResult {
+        assert_ok!(self.writing());
+
+        self.write_raw(address.clone(), k_transport_secure_address_size_in_bits)
     }
 
-    pub fn write_string(_string: &String, max_string_size: u32) -> BLFLibResult {
-        unimplemented!()
+    pub fn write_string(&mut self, string: &String, max_string_size: u32) -> BLFLibResult {
+        self.write_string_extended_ascii(string, max_string_size)
     }
 
     pub fn write_string_utf8(&mut self, char_string: &String, max_string_size: u32) -> BLFLibResult {
@@ -465,44 +520,58 @@ impl c_bitstream_writer {
         Ok(())
     }
 
-    pub fn write_unit_vector(unit_vector: &real_vector3d, size_in_bits: u8) {
-        unimplemented!()
-    }
-
-    pub fn write_vector(vector: &real_vector3d, min_value: f32, max_value: f32, step_count_size_in_bits: u32, size_in_bits: u8) { unimplemented!() }
-
-    // GUTS
+    pub fn write_unit_vector(&mut self, unit_vector: &real_vector3d, size_in_bits: usize) -> BLFLibResult {
+        assert_ok!(self.writing());
+        assert_ok!(assert_valid_real_normal3d(unit_vector));
 
-    pub fn append(stream: &c_bitstream_writer) {
-        unimplemented!()
+        self.write_integer(quantize_unit_vector3d(unit_vector, size_in_bits)? as u32, size_in_bits)
     }
 
-    pub fn begin_consistency_check() -> bool {
-        unimplemented!()
-    }
+    /// Vectors are written as a quantized magnitude followed by their direction as a unit vector.
+    pub fn write_vector(&mut self, vector: &real_vector3d, min_value: f32, max_value: f32, step_count_size_in_bits: usize, size_in_bits: usize) -> BLFLibResult {
+        assert_ok!(self.writing());
 
-    pub fn begin_writing(&mut self) {
-        self.reset(e_bitstream_state::_bitstream_state_writing);
-    }
+        let magnitude = square_root(magnitude_squared3d(vector)).clamp(min_value, max_value);
+        self.write_integer(
+            quantize_real(magnitude, min_value, max_value, 1 << step_count_size_in_bits, false, false) as u32,
+            step_count_size_in_bits
+        )?;
 
-    pub fn data_is_untrusted(is_untrusted: bool) {
-        unimplemented!()
+        // Zero length vectors have no direction, up is written in its place.
+        if magnitude < k_real_epsilon {
+            self.write_unit_vector(&global_up3d, size_in_bits)
+        } else {
+            let mut direction = *vector;
+            normalize3d(&mut direction);
+            self.write_unit_vector(&direction, size_in_bits)
+        }
     }
 
-    pub fn discard_remaining_data() {
-        unimplemented!()
-    }
+    // GUTS
 
-    fn encode_qword_to_memory(value: u64, size_in_bits: u8) {
-        unimplemented!()
-    }
+    /// Writes everything written to another stream so far onto the end of this one.
+    pub fn append(&mut self, stream: &c_bitstream_writer) -> BLFLibResult {
+        assert_ok!(self.writing());
 
-    pub fn overflowed() -> bool {
-        unimplemented!()
+        let mut reader = match stream.m_byte_pack_direction {
+            _bitstream_byte_fill_direction_lsb_to_msb => c_bitstream_reader::new_with_legacy_settings(&stream.m_data, stream.m_byte_order),
+            _bitstream_byte_fill_direction_msb_to_lsb => c_bitstream_reader::new(&stream.m_data, stream.m_byte_order),
+        };
+        reader.begin_reading();
+
+        let mut remaining_bits = stream.get_current_bit_offset();
+        while remaining_bits > 0 {
+            let size_in_bits = min(32, remaining_bits);
+            let value: u32 = reader.read_integer("appended-data", size_in_bits)?;
+            self.write_integer(value, size_in_bits)?;
+            remaining_bits -= size_in_bits;
+        }
+
+        Ok(())
     }
 
-    pub fn error_occurred() -> bool {
-        unimplemented!()
+    pub fn begin_writing(&mut self) {
+        self.reset(e_bitstream_state::_bitstream_state_writing);
     }
 
     pub fn writing(&self) -> bool {
@@ -533,12 +602,37 @@ impl c_bitstream_writer {
     //     self.reset(e_bitstream_state::_bitstream_state_initial);
     // }
 
-    fn skip(bits_to_skip: u32) {
-        unimplemented!()
+    pub fn skip(&mut self, bits_to_skip: usize) -> BLFLibResult {
+        self.seek_relative(bits_to_skip)
+    }
+
+    pub fn would_overflow(&self, size_in_bits: usize) -> bool {
+        self.get_current_bit_offset() + size_in_bits > self.m_data.len() * 8
     }
 
-    fn would_overflow(size_in_bits: u32) -> bool {
-        unimplemented!()
+    fn grow(&mut self, size_in_bits: usize) -> BLFLibResult {
+        let required_size_bytes = (self.get_current_bit_offset() + size_in_bits).div_ceil(8);
+        let maximum_size_bytes = if self.m_growable { self.m_maximum_size_bytes } else { Some(self.m_data.len()) };
+
+        if let Some(maximum_size_bytes) = maximum_size_bytes {
+            if required_size_bytes > maximum_size_bytes {
+                return Err(BLFLibError::BitstreamSizeExceeded {
+                    field: self.m_current_identifier.clone(),
+                    bit_offset: self.get_current_bit_offset(),
+                    size_in_bits,
+                    maximum_size_bytes,
+                })
+            }
+        }
+
+        // Double the capacity so that writing many small values doesn't resize every time.
+        let new_size_bytes = min(
+            (self.m_data.len() * 2).max(required_size_bytes),
+            maximum_size_bytes.unwrap_or(usize::MAX),
+        );
+        self.m_data.resize(new_size_bytes, 0);
+
+        Ok(())
     }
 
     // fn write_accumulator_to_memory(a1: u64, a2: u32) {
@@ -585,6 +679,7 @@ impl c_bitstream_writer {
 #[cfg(test)]
 mod bitstream_writer_tests {
     use super::*;
+    use crate::blam::halo3::v12070_08_09_05_2031_halo3_ship::math::real_math as halo3;
 
     #[test]
     fn write_legacy_be() {
@@ -764,4 +859,183 @@ mod bitstream_writer_tests {
         let actual = sut.get_data().unwrap();
         assert_eq!(actual, expected);
     }
-}
\ No newline at end of file
+
+    fn round_trip(
+        byte_order: e_bitstream_byte_order,
+        write: impl Fn(&mut c_bitstream_writer) -> BLFLibResult,
+        read: impl Fn(&mut c_bitstream_reader) -> BLFLibResult,
+    ) {
+        let mut writer = c_bitstream_writer::new(0x100, byte_order);
+        writer.begin_writing();
+        write(&mut writer).unwrap();
+        writer.finish_writing();
+        let data = writer.get_data().unwrap();
+
+        let mut reader = c_bitstream_reader::new(&data, byte_order);
+        reader.begin_reading();
+        read(&mut reader).unwrap();
+    }
+
+    #[test]
+    fn qword_round_trip() {
+        for byte_order in [e_bitstream_byte_order::_bitstream_byte_order_big_endian, e_bitstream_byte_order::_bitstream_byte_order_little_endian] {
+            round_trip(byte_order, |writer| {
+                writer.write_integer(0b101u32, 3)?;
+                writer.write_qword(0x1ABCDEFu64, 25)?;
+                writer.write_qword(0x0123456789ABCDEFu64, 64)
+            }, |reader| {
+                assert_eq!(reader.read_integer::<u32>("prefix", 3)?, 0b101);
+                assert_eq!(reader.read_qword::<u64>(25)?, 0x1ABCDEF);
+                assert_eq!(reader.read_qword::<u64>(64)?, 0x0123456789ABCDEF);
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn unit_vector_round_trip() {
+        let vectors = [
+            real_vector3d::new(0.0, 0.0, 1.0),
+            real_vector3d::new(0.0, -1.0, 0.0),
+            real_vector3d::new(0.6, -0.8, 0.0),
+            real_vector3d::new(-0.48, 0.6, 0.64),
+        ];
+
+        for vector in vectors {
+            round_trip(e_bitstream_byte_order::_bitstream_byte_order_big_endian, |writer| {
+                writer.write_unit_vector(&vector, 19)
+            }, |reader| {
+                let mut decoded = real_vector3d::default();
+                reader.read_unit_vector(&mut decoded, 19)?;
+                assert!(dot_product3d(&vector, &decoded) > 0.999, "{vector:?} decoded as {decoded:?}");
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn unit_vectors_match_known_encodings() {
+        // Face 2 (+k) with both axes at the exact midpoint, 146 of 294 values at 19 bits.
+        let quantized_up = 146 + 2 * 0x15555 + 146 * 0x126;
+
+        round_trip(e_bitstream_byte_order::_bitstream_byte_order_big_endian, |writer| {
+            writer.write_unit_vector(&global_up3d, 19)?;
+            writer.write_integer(quantized_up as u32, 19)
+        }, |reader| {
+            assert_eq!(reader.read_integer::<u32>("unit-vector", 19)?, quantized_up);
+            let mut decoded = real_vector3d::default();
+            reader.read_unit_vector(&mut decoded, 19)?;
+            assert!(dot_product3d(&decoded, &global_up3d) > 0.9999, "{quantized_up} decoded as {decoded:?}");
+            Ok(())
+        });
+
+        // Halo 3 packs the face into the low 3 bits followed by two 8 bit axes, 127 is the midpoint.
+        let mut halo3_up = real_vector3d::default();
+        halo3::dequantize_unit_vector3d(2 | (127 << 3) | (127 << 11), &mut halo3_up).unwrap();
+        assert_eq!(halo3_up, global_up3d);
+        assert_eq!(halo3::quantize_normalized_vector3d(&global_forward3d), (127 << 3) | (127 << 11));
+    }
+
+    #[test]
+    fn vector_round_trip() {
+        let vectors = [
+            real_vector3d::new(0.0, 0.0, 0.0),
+            real_vector3d::new(3.0, 4.0, 0.0),
+            real_vector3d::new(-1.0, 2.0, -2.0),
+        ];
+
+        for vector in vectors {
+            round_trip(e_bitstream_byte_order::_bitstream_byte_order_big_endian, |writer| {
+                writer.write_vector(&vector, 0.0, 10.0, 12, 19)
+            }, |reader| {
+                let mut decoded = real_vector3d::default();
+                reader.read_vector(&mut decoded, 0.0, 10.0, 12, 19)?;
+                assert!((decoded.i - vector.i).abs() < 0.05, "{vector:?} decoded as {decoded:?}");
+                assert!((decoded.j - vector.j).abs() < 0.05, "{vector:?} decoded as {decoded:?}");
+                assert!((decoded.k - vector.k).abs() < 0.05, "{vector:?} decoded as {decoded:?}");
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn string_and_secure_address_round_trip() {
+        let mut address = s_transport_secure_address::default();
+        address.data.get_mut()[0] = 0xAB;
+        address.data.get_mut()[27] = 0xCD;
+
+        round_trip(e_bitstream_byte_order::_bitstream_byte_order_big_endian, |writer| {
+            writer.write_bool(true)?;
+            writer.write_string(&String::from("caf\u{e9}"), 16)?;
+            writer.write_identifier("secure-address")?;
+            writer.write_secure_address(&address)
+        }, |reader| {
+            let mut string = String::new();
+            let mut decoded_address = s_transport_secure_address::default();
+            assert!(reader.read_bool::<bool>("flag")?);
+            reader.read_string(&mut string, 16)?;
+            reader.read_identifier("secure-address")?;
+            reader.read_secure_address(&mut decoded_address)?;
+            assert_eq!(string, "caf\u{e9}");
+            assert_eq!(decoded_address, address);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn append_and_overflow() {
+        let mut appended = c_bitstream_writer::new(4, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        appended.begin_writing();
+        appended.write_integer(0b10110u32, 5).unwrap();
+        appended.write_integer(0xABCu32, 12).unwrap();
+
+        let mut sut = c_bitstream_writer::new(3, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        sut.begin_writing();
+        sut.write_integer(0b011u32, 3).unwrap();
+        sut.append(&appended).unwrap();
+        assert!(sut.would_overflow(5));
+        assert!(sut.write_integer(0u32, 5).is_err());
+        sut.finish_writing();
+
+        let data = sut.get_data().unwrap();
+        let mut reader = c_bitstream_reader::new(&data, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        reader.begin_reading();
+        assert_eq!(reader.read_integer::<u32>("prefix", 3).unwrap(), 0b011);
+        assert_eq!(reader.read_integer::<u32>("first", 5).unwrap(), 0b10110);
+        assert_eq!(reader.read_integer::<u32>("second", 12).unwrap(), 0xABC);
+    }
+
+    #[test]
+    fn growable_writer_grows() {
+        let mut sut = c_bitstream_writer::new_growable(None, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        sut.begin_writing();
+        for i in 0..0x300u32 {
+            sut.write_integer(i & 0x7F, 7).unwrap();
+        }
+        sut.write_bool(true).unwrap();
+        sut.finish_writing();
+
+        assert_eq!(sut.get_data_size_in_bits(), 0x300 * 7 + 1);
+        let data = sut.get_data().unwrap();
+        assert_eq!(data.len(), (0x300 * 7 + 1usize).div_ceil(8));
+
+        let mut reader = c_bitstream_reader::new(&data, e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        reader.begin_reading();
+        for i in 0..0x300u32 {
+            assert_eq!(reader.read_integer::<u32>("value", 7).unwrap(), i & 0x7F);
+        }
+        assert!(reader.read_bool::<bool>("end").unwrap());
+    }
+
+    #[test]
+    fn growable_writer_maximum_size() {
+        let mut sut = c_bitstream_writer::new_growable(Some(2), e_bitstream_byte_order::_bitstream_byte_order_big_endian);
+        sut.begin_writing();
+        sut.write_identifier("payload").unwrap();
+        sut.write_integer(0xFFFFu32, 16).unwrap();
+
+        let error = sut.write_bool(true).unwrap_err();
+        assert!(matches!(&error, BLFLibError::BitstreamSizeExceeded { field: Some(field), maximum_size_bytes: 2, .. } if field == "payload"));
+        assert!(error.to_string().contains("exceeds 2 bytes"));
+    }
+}
@@ -16,6 +16,10 @@ pub mod result;
 
 pub use blf_lib_derive::*;
 
+// Used by #[derive(BlfChunk)] to populate the chunk registry.
+#[doc(hidden)]
+pub use inventory;
+
 pub mod derive {
     pub use blf_lib_derive::*;
 }
\ No newline at end of file